@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS rate_limit_buckets(
+    key TEXT PRIMARY KEY,
+    theoretical_arrival TIMESTAMPTZ NOT NULL
+);
\ No newline at end of file
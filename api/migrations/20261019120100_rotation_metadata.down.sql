@@ -0,0 +1,5 @@
+ALTER TABLE rotations
+    DROP COLUMN description,
+    DROP COLUMN location,
+    DROP COLUMN duration_weeks,
+    DROP COLUMN category;
\ No newline at end of file
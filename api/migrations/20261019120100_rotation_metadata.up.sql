@@ -0,0 +1,5 @@
+ALTER TABLE rotations
+    ADD COLUMN IF NOT EXISTS description TEXT NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS location TEXT NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS duration_weeks INT DEFAULT NULL CHECK (duration_weeks > 0),
+    ADD COLUMN IF NOT EXISTS category TEXT NOT NULL DEFAULT 'core' CHECK (category IN ('core', 'elective'));
\ No newline at end of file
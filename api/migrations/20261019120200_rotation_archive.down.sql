@@ -0,0 +1,4 @@
+ALTER TABLE rotations
+    DROP COLUMN archived_at,
+    DROP COLUMN deletion_token,
+    DROP COLUMN deletion_token_expiry;
\ No newline at end of file
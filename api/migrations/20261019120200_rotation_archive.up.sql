@@ -0,0 +1,4 @@
+ALTER TABLE rotations
+    ADD COLUMN IF NOT EXISTS archived_at TIMESTAMPTZ DEFAULT NULL,
+    ADD COLUMN IF NOT EXISTS deletion_token TEXT DEFAULT NULL,
+    ADD COLUMN IF NOT EXISTS deletion_token_expiry TIMESTAMPTZ DEFAULT NULL;
\ No newline at end of file
@@ -0,0 +1,3 @@
+ALTER TABLE supertasks DROP COLUMN position;
+ALTER TABLE tasks DROP COLUMN position;
+ALTER TABLE subtasks DROP COLUMN position;
\ No newline at end of file
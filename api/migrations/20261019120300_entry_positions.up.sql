@@ -0,0 +1,24 @@
+-- New entries take the next position from a sequence so that they are ordered after their existing siblings.
+ALTER TABLE supertasks ADD COLUMN IF NOT EXISTS position INT;
+UPDATE supertasks SET position = id;
+CREATE SEQUENCE IF NOT EXISTS supertasks_position_seq AS INT OWNED BY supertasks.position;
+SELECT setval('supertasks_position_seq', COALESCE((SELECT MAX(position) FROM supertasks), 0) + 1, false);
+ALTER TABLE supertasks
+    ALTER COLUMN position SET DEFAULT nextval('supertasks_position_seq'),
+    ALTER COLUMN position SET NOT NULL;
+
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS position INT;
+UPDATE tasks SET position = id;
+CREATE SEQUENCE IF NOT EXISTS tasks_position_seq AS INT OWNED BY tasks.position;
+SELECT setval('tasks_position_seq', COALESCE((SELECT MAX(position) FROM tasks), 0) + 1, false);
+ALTER TABLE tasks
+    ALTER COLUMN position SET DEFAULT nextval('tasks_position_seq'),
+    ALTER COLUMN position SET NOT NULL;
+
+ALTER TABLE subtasks ADD COLUMN IF NOT EXISTS position INT;
+UPDATE subtasks SET position = id;
+CREATE SEQUENCE IF NOT EXISTS subtasks_position_seq AS INT OWNED BY subtasks.position;
+SELECT setval('subtasks_position_seq', COALESCE((SELECT MAX(position) FROM subtasks), 0) + 1, false);
+ALTER TABLE subtasks
+    ALTER COLUMN position SET DEFAULT nextval('subtasks_position_seq'),
+    ALTER COLUMN position SET NOT NULL;
\ No newline at end of file
@@ -0,0 +1,7 @@
+ALTER TABLE user_tasks DROP CONSTRAINT user_tasks_subtask_rotation_fkey;
+ALTER TABLE subtasks DROP CONSTRAINT subtasks_task_rotation_fkey;
+ALTER TABLE tasks DROP CONSTRAINT tasks_supertask_rotation_fkey;
+
+ALTER TABLE subtasks DROP CONSTRAINT subtasks_id_rotation_id_key;
+ALTER TABLE tasks DROP CONSTRAINT tasks_id_rotation_id_key;
+ALTER TABLE supertasks DROP CONSTRAINT supertasks_id_rotation_id_key;
\ No newline at end of file
@@ -0,0 +1,64 @@
+-- Report and repair entries whose rotation disagrees with their parent's, treating the parent as the source of truth.
+DO $$
+DECLARE
+    violation RECORD;
+BEGIN
+    FOR violation IN
+        SELECT tasks.id, tasks.rotation_id, supertasks.rotation_id AS expected_rotation_id
+        FROM tasks
+        JOIN supertasks ON supertasks.id = tasks.supertask_id
+        WHERE tasks.rotation_id <> supertasks.rotation_id
+    LOOP
+        RAISE WARNING 'Task % is in rotation % but its supertask is in rotation %', violation.id, violation.rotation_id, violation.expected_rotation_id;
+    END LOOP;
+
+    UPDATE tasks
+    SET rotation_id = supertasks.rotation_id
+    FROM supertasks
+    WHERE supertasks.id = tasks.supertask_id AND tasks.rotation_id <> supertasks.rotation_id;
+
+    FOR violation IN
+        SELECT subtasks.id, subtasks.rotation_id, tasks.rotation_id AS expected_rotation_id
+        FROM subtasks
+        JOIN tasks ON tasks.id = subtasks.task_id
+        WHERE subtasks.rotation_id <> tasks.rotation_id
+    LOOP
+        RAISE WARNING 'Subtask % is in rotation % but its task is in rotation %', violation.id, violation.rotation_id, violation.expected_rotation_id;
+    END LOOP;
+
+    UPDATE subtasks
+    SET rotation_id = tasks.rotation_id
+    FROM tasks
+    WHERE tasks.id = subtasks.task_id AND subtasks.rotation_id <> tasks.rotation_id;
+
+    FOR violation IN
+        SELECT user_tasks.id, user_tasks.rotation_id, subtasks.rotation_id AS expected_rotation_id
+        FROM user_tasks
+        JOIN subtasks ON subtasks.id = user_tasks.subtask_id
+        WHERE user_tasks.rotation_id <> subtasks.rotation_id
+    LOOP
+        RAISE WARNING 'User task % is in rotation % but its subtask is in rotation %', violation.id, violation.rotation_id, violation.expected_rotation_id;
+    END LOOP;
+
+    UPDATE user_tasks
+    SET rotation_id = subtasks.rotation_id
+    FROM subtasks
+    WHERE subtasks.id = user_tasks.subtask_id AND user_tasks.rotation_id <> subtasks.rotation_id;
+END $$;
+
+ALTER TABLE supertasks ADD CONSTRAINT supertasks_id_rotation_id_key UNIQUE (id, rotation_id);
+ALTER TABLE tasks ADD CONSTRAINT tasks_id_rotation_id_key UNIQUE (id, rotation_id);
+ALTER TABLE subtasks ADD CONSTRAINT subtasks_id_rotation_id_key UNIQUE (id, rotation_id);
+
+-- Children must be in the same rotation as their parent. Moving a parent to another rotation carries its children along.
+ALTER TABLE tasks
+    ADD CONSTRAINT tasks_supertask_rotation_fkey FOREIGN KEY (supertask_id, rotation_id)
+    REFERENCES supertasks(id, rotation_id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE subtasks
+    ADD CONSTRAINT subtasks_task_rotation_fkey FOREIGN KEY (task_id, rotation_id)
+    REFERENCES tasks(id, rotation_id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE user_tasks
+    ADD CONSTRAINT user_tasks_subtask_rotation_fkey FOREIGN KEY (subtask_id, rotation_id)
+    REFERENCES subtasks(id, rotation_id) ON DELETE CASCADE ON UPDATE CASCADE;
\ No newline at end of file
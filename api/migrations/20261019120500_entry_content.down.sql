@@ -0,0 +1,3 @@
+ALTER TABLE supertasks DROP COLUMN IF EXISTS description, DROP COLUMN IF EXISTS learning_objectives, DROP COLUMN IF EXISTS reference_links;
+ALTER TABLE tasks DROP COLUMN IF EXISTS description, DROP COLUMN IF EXISTS learning_objectives, DROP COLUMN IF EXISTS reference_links;
+ALTER TABLE subtasks DROP COLUMN IF EXISTS description, DROP COLUMN IF EXISTS learning_objectives, DROP COLUMN IF EXISTS reference_links;
\ No newline at end of file
@@ -0,0 +1,15 @@
+-- Rich content is kept alongside each entry, but is only fetched when an entry is opened.
+ALTER TABLE supertasks
+    ADD COLUMN IF NOT EXISTS description TEXT NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS learning_objectives TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN IF NOT EXISTS reference_links JSONB NOT NULL DEFAULT '[]';
+
+ALTER TABLE tasks
+    ADD COLUMN IF NOT EXISTS description TEXT NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS learning_objectives TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN IF NOT EXISTS reference_links JSONB NOT NULL DEFAULT '[]';
+
+ALTER TABLE subtasks
+    ADD COLUMN IF NOT EXISTS description TEXT NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS learning_objectives TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN IF NOT EXISTS reference_links JSONB NOT NULL DEFAULT '[]';
\ No newline at end of file
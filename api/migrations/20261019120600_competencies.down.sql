@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS subtask_competencies;
+DROP TABLE IF EXISTS competencies;
\ No newline at end of file
@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS competencies(
+    id SERIAL PRIMARY KEY,
+    framework TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    UNIQUE (framework, title)
+);
+
+CREATE TABLE IF NOT EXISTS subtask_competencies(
+    subtask_id INT NOT NULL,
+    competency_id INT NOT NULL,
+    PRIMARY KEY (subtask_id, competency_id),
+    FOREIGN KEY (subtask_id) REFERENCES subtasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (competency_id) REFERENCES competencies(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS subtask_competencies_competency_id_idx ON subtask_competencies(competency_id);
\ No newline at end of file
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS user_task_encounters;
+ALTER TABLE subtasks DROP COLUMN IF EXISTS required_count;
\ No newline at end of file
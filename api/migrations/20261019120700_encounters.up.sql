@@ -0,0 +1,13 @@
+-- Subtasks requiring more than one encounter are completed once enough encounters have been logged.
+ALTER TABLE subtasks ADD COLUMN IF NOT EXISTS required_count INT NOT NULL DEFAULT 1 CHECK (required_count BETWEEN 1 AND 1000);
+
+CREATE TABLE IF NOT EXISTS user_task_encounters(
+    id SERIAL PRIMARY KEY,
+    user_task_id INT NOT NULL,
+    encountered_on DATE NOT NULL,
+    comment TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_task_id) REFERENCES user_tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS user_task_encounters_user_task_id_idx ON user_task_encounters(user_task_id);
\ No newline at end of file
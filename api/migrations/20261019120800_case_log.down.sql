@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS case_log_entry_subtasks;
+DROP TABLE IF EXISTS case_log_entries;
+DROP TABLE IF EXISTS case_log_options;
\ No newline at end of file
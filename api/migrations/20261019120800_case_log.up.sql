@@ -0,0 +1,59 @@
+-- The values trainees can choose from for each enumerated case log field, managed by admins.
+CREATE TABLE IF NOT EXISTS case_log_options(
+    id SERIAL PRIMARY KEY,
+    field TEXT NOT NULL CHECK (field IN ('setting', 'diagnosis_category', 'treatment_intent', 'role')),
+    value TEXT NOT NULL,
+    UNIQUE (field, value)
+);
+
+INSERT INTO case_log_options (field, value) VALUES
+    ('setting', 'Inpatient'),
+    ('setting', 'Outpatient Clinic'),
+    ('setting', 'Emergency'),
+    ('setting', 'Tumour Board'),
+    ('setting', 'Simulation'),
+    ('diagnosis_category', 'Breast'),
+    ('diagnosis_category', 'Central Nervous System'),
+    ('diagnosis_category', 'Gastrointestinal'),
+    ('diagnosis_category', 'Genitourinary'),
+    ('diagnosis_category', 'Gynecologic'),
+    ('diagnosis_category', 'Head and Neck'),
+    ('diagnosis_category', 'Hematologic'),
+    ('diagnosis_category', 'Lung'),
+    ('diagnosis_category', 'Pediatric'),
+    ('diagnosis_category', 'Sarcoma'),
+    ('diagnosis_category', 'Skin'),
+    ('diagnosis_category', 'Other'),
+    ('treatment_intent', 'Curative'),
+    ('treatment_intent', 'Palliative'),
+    ('treatment_intent', 'Not Applicable'),
+    ('role', 'Observed'),
+    ('role', 'Assisted'),
+    ('role', 'Performed Under Supervision'),
+    ('role', 'Performed Independently'),
+    ('role', 'Taught')
+ON CONFLICT DO NOTHING;
+
+-- Case log entries are de-identified, so they only hold categories and free text that has been checked for patient identifiers.
+CREATE TABLE IF NOT EXISTS case_log_entries(
+    id SERIAL PRIMARY KEY,
+    user_id INT NOT NULL,
+    encountered_on DATE NOT NULL,
+    setting TEXT NOT NULL,
+    diagnosis_category TEXT NOT NULL,
+    treatment_intent TEXT NOT NULL,
+    role TEXT NOT NULL,
+    learning_points TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS case_log_entries_user_id_idx ON case_log_entries(user_id, encountered_on);
+
+CREATE TABLE IF NOT EXISTS case_log_entry_subtasks(
+    case_log_entry_id INT NOT NULL,
+    subtask_id INT NOT NULL,
+    PRIMARY KEY (case_log_entry_id, subtask_id),
+    FOREIGN KEY (case_log_entry_id) REFERENCES case_log_entries(id) ON DELETE CASCADE,
+    FOREIGN KEY (subtask_id) REFERENCES subtasks(id) ON DELETE CASCADE
+);
\ No newline at end of file
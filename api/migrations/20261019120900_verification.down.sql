@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS supervisors;
+DROP TABLE IF EXISTS user_task_verifications;
+ALTER TABLE user_tasks DROP COLUMN IF EXISTS status;
\ No newline at end of file
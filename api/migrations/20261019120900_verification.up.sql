@@ -0,0 +1,31 @@
+-- Every user task moves through a verification workflow: a trainee submits a completed task and a supervisor or director either verifies it or returns it with a reason.
+-- Changing the completion of a task that is no longer a draft returns it to a draft, since the previous decision no longer applies.
+ALTER TABLE user_tasks
+    ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'draft' CHECK (status IN ('draft', 'submitted', 'verified', 'returned'));
+
+-- The decisions made on each user task, kept so that the history of a task can be audited.
+CREATE TABLE IF NOT EXISTS user_task_verifications(
+    id SERIAL PRIMARY KEY,
+    user_task_id INT NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('submitted', 'verified', 'returned')),
+    reason TEXT NOT NULL DEFAULT '',
+    decided_by INT,
+    decided_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_task_id) REFERENCES user_tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (decided_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS user_task_verifications_user_task_id_idx ON user_task_verifications(user_task_id);
+
+-- Supervisors are regular users assigned by an admin to verify the tasks of particular trainees.
+-- Admins act as program directors and may verify the tasks of any trainee.
+CREATE TABLE IF NOT EXISTS supervisors(
+    supervisor_id INT NOT NULL,
+    trainee_id INT NOT NULL,
+    PRIMARY KEY (supervisor_id, trainee_id),
+    CHECK (supervisor_id <> trainee_id),
+    FOREIGN KEY (supervisor_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (trainee_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS supervisors_trainee_id_idx ON supervisors(trainee_id);
\ No newline at end of file
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS user_task_changes;
+ALTER TABLE user_tasks
+    DROP COLUMN IF EXISTS completed_at,
+    DROP COLUMN IF EXISTS updated_at,
+    DROP COLUMN IF EXISTS created_at;
\ No newline at end of file
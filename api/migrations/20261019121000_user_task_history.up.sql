@@ -0,0 +1,25 @@
+-- When progress on each user task was started, last changed and completed.
+-- Progress recorded before these were kept takes the time of the migration, and tasks that were already completed have no known completion time.
+ALTER TABLE user_tasks
+    ADD COLUMN IF NOT EXISTS created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN IF NOT EXISTS completed_at TIMESTAMPTZ DEFAULT NULL;
+
+-- Every change to the completion or comment of a user task, holding the state of the task after the change.
+-- Changes derived from other data, such as a new required count on a subtask, have no user who made them.
+CREATE TABLE IF NOT EXISTS user_task_changes(
+    id SERIAL PRIMARY KEY,
+    user_task_id INT NOT NULL,
+    is_completed BOOLEAN NOT NULL,
+    comment TEXT NOT NULL,
+    changed_by INT,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_task_id) REFERENCES user_tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (changed_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS user_task_changes_user_task_id_idx ON user_task_changes(user_task_id);
+
+-- Existing progress starts its history with its state at the time of the migration.
+INSERT INTO user_task_changes (user_task_id, is_completed, comment)
+SELECT id, is_completed, comment FROM user_tasks;
\ No newline at end of file
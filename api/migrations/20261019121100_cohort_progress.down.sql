@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS subtasks_task_id_idx;
+DROP INDEX IF EXISTS tasks_supertask_id_idx;
+DROP INDEX IF EXISTS user_tasks_rotation_id_idx;
\ No newline at end of file
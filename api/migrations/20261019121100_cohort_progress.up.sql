@@ -0,0 +1,4 @@
+-- Indexes for computing the progress of every trainee in a rotation at once.
+CREATE INDEX IF NOT EXISTS user_tasks_rotation_id_idx ON user_tasks(rotation_id, user_id);
+CREATE INDEX IF NOT EXISTS tasks_supertask_id_idx ON tasks(supertask_id);
+CREATE INDEX IF NOT EXISTS subtasks_task_id_idx ON subtasks(task_id);
\ No newline at end of file
@@ -0,0 +1,5 @@
+ALTER TABLE subtasks DROP COLUMN IF EXISTS retired_at;
+ALTER TABLE tasks DROP COLUMN IF EXISTS retired_at;
+ALTER TABLE supertasks DROP COLUMN IF EXISTS retired_at;
+DROP TABLE IF EXISTS curriculum_enrollments;
+DROP TABLE IF EXISTS curriculum_versions;
\ No newline at end of file
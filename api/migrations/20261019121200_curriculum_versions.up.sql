@@ -0,0 +1,35 @@
+-- Published versions of the curriculum of a rotation, each an immutable snapshot of its entry structure at the time.
+-- The live entries are the draft that admins edit, and publishing it creates the next version.
+CREATE TABLE IF NOT EXISTS curriculum_versions(
+    id SERIAL PRIMARY KEY,
+    rotation_id INT NOT NULL,
+    version INT NOT NULL,
+    notes TEXT NOT NULL DEFAULT '',
+    structure JSONB NOT NULL,
+    subtask_ids INT[] NOT NULL,
+    published_by INT,
+    published_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (rotation_id, version),
+    FOREIGN KEY (rotation_id) REFERENCES rotations(id) ON DELETE CASCADE,
+    FOREIGN KEY (published_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+-- The version of a rotation's curriculum each trainee follows, which is the one they started on until they are migrated to another.
+CREATE TABLE IF NOT EXISTS curriculum_enrollments(
+    user_id INT NOT NULL,
+    rotation_id INT NOT NULL,
+    version_id INT NOT NULL,
+    enrolled_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, rotation_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (rotation_id) REFERENCES rotations(id) ON DELETE CASCADE,
+    FOREIGN KEY (version_id) REFERENCES curriculum_versions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS curriculum_enrollments_version_id_idx ON curriculum_enrollments(version_id);
+
+-- Once a rotation has a published version, deleted entries are retired from the draft rather than deleted,
+-- so that progress against the versions that include them is kept.
+ALTER TABLE supertasks ADD COLUMN IF NOT EXISTS retired_at TIMESTAMPTZ;
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS retired_at TIMESTAMPTZ;
+ALTER TABLE subtasks ADD COLUMN IF NOT EXISTS retired_at TIMESTAMPTZ;
\ No newline at end of file
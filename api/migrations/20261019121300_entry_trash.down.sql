@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS subtasks_deleted_at_idx;
+DROP INDEX IF EXISTS tasks_deleted_at_idx;
+DROP INDEX IF EXISTS supertasks_deleted_at_idx;
+
+ALTER TABLE subtasks RENAME COLUMN deleted_at TO retired_at;
+ALTER TABLE tasks RENAME COLUMN deleted_at TO retired_at;
+ALTER TABLE supertasks RENAME COLUMN deleted_at TO retired_at;
\ No newline at end of file
@@ -0,0 +1,10 @@
+-- Deleted entries are kept in a trash they can be restored from until they are purged after a retention period,
+-- which also covers entries retired from the draft of a published curriculum.
+-- Entries deleted together share the same deletion time, which is how restoring an entry finds the rest of its subtree.
+ALTER TABLE supertasks RENAME COLUMN retired_at TO deleted_at;
+ALTER TABLE tasks RENAME COLUMN retired_at TO deleted_at;
+ALTER TABLE subtasks RENAME COLUMN retired_at TO deleted_at;
+
+CREATE INDEX IF NOT EXISTS supertasks_deleted_at_idx ON supertasks(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS tasks_deleted_at_idx ON tasks(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS subtasks_deleted_at_idx ON subtasks(deleted_at) WHERE deleted_at IS NOT NULL;
\ No newline at end of file
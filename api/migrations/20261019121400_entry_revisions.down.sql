@@ -0,0 +1,3 @@
+ALTER TABLE subtasks DROP COLUMN IF EXISTS revision;
+ALTER TABLE tasks DROP COLUMN IF EXISTS revision;
+ALTER TABLE supertasks DROP COLUMN IF EXISTS revision;
\ No newline at end of file
@@ -0,0 +1,5 @@
+-- Every entry has a revision that is incremented whenever it is changed, which is used as its entity tag
+-- so that concurrent edits of the same entry are detected instead of silently overwriting each other.
+ALTER TABLE supertasks ADD COLUMN IF NOT EXISTS revision INT NOT NULL DEFAULT 1;
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS revision INT NOT NULL DEFAULT 1;
+ALTER TABLE subtasks ADD COLUMN IF NOT EXISTS revision INT NOT NULL DEFAULT 1;
\ No newline at end of file
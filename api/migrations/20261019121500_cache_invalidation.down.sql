@@ -0,0 +1,2 @@
+DROP TRIGGER IF EXISTS rotations_cache_invalidation ON rotations;
+DROP FUNCTION IF EXISTS notify_rotation_changed();
\ No newline at end of file
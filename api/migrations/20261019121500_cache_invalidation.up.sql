@@ -0,0 +1,12 @@
+-- Every change to a rotation, including to its entries which update when it was last updated, notifies each instance of the API
+-- so that it can invalidate what it has cached about the rotation.
+CREATE OR REPLACE FUNCTION notify_rotation_changed() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify('cache_invalidation', json_build_object('rotationId', COALESCE(NEW.id, OLD.id))::TEXT);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER rotations_cache_invalidation
+AFTER INSERT OR UPDATE OR DELETE ON rotations
+FOR EACH ROW EXECUTE FUNCTION notify_rotation_changed();
\ No newline at end of file
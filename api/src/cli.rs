@@ -0,0 +1,100 @@
+use crate::models::{curriculum::{CurriculumDocument, CurriculumFormat}, entry_structure::EntryStructure, rotation::Rotation};
+use std::{fs, path::Path};
+use anyhow::{anyhow, Result};
+use sqlx::PgPool;
+
+const USAGE: &str = "\
+Usage:
+    oncology-quest-api export-curriculum <rotation id> [--format json|yaml]
+    oncology-quest-api import-curriculum <file> [--rotation-id <rotation id>] [--dry-run]";
+
+/// Runs an administrative command against the database instead of starting the server.
+///
+/// # Arguments
+///
+/// * `pool` - The Postgres connection pool to use for the command.
+/// * `args` - The command line arguments, excluding the program name.
+pub async fn run(pool: &PgPool, args: &[String]) -> Result<()> {
+    match args.first().map(String::as_str) {
+        Some("export-curriculum") => export_curriculum(pool, &args[1..]).await,
+        Some("import-curriculum") => import_curriculum(pool, &args[1..]).await,
+        _ => Err(anyhow!(USAGE))
+    }
+}
+
+/// Finds the value following a flag, such as `--format yaml`.
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|index| args.get(index + 1))
+        .map(String::as_str)
+}
+
+async fn export_curriculum(pool: &PgPool, args: &[String]) -> Result<()> {
+    let rotation_id = args
+        .first()
+        .and_then(|rotation_id| rotation_id.parse::<i32>().ok())
+        .ok_or_else(|| anyhow!(USAGE))?;
+
+    let format = match flag_value(args, "--format") {
+        Some("yaml") => CurriculumFormat::Yaml,
+        Some("json") | None => CurriculumFormat::Json,
+        Some(_) => return Err(anyhow!(USAGE))
+    };
+
+    let rotation = Rotation::fetch_by_id(pool, rotation_id)
+        .await?
+        .ok_or_else(|| anyhow!("Rotation {} does not exist.", rotation_id))?;
+
+    let entry_structure = EntryStructure::fetch(pool, rotation_id).await?;
+    let document = CurriculumDocument::from_structure(rotation.name(), &entry_structure);
+
+    println!("{}", document.serialize(format)?);
+
+    Ok(())
+}
+
+async fn import_curriculum(pool: &PgPool, args: &[String]) -> Result<()> {
+    let path = Path::new(args.first().ok_or_else(|| anyhow!(USAGE))?);
+
+    let rotation_id = match flag_value(args, "--rotation-id") {
+        Some(rotation_id) => Some(rotation_id.parse::<i32>().map_err(|_| anyhow!(USAGE))?),
+        None => None
+    };
+
+    let dry_run = args.iter().any(|arg| arg == "--dry-run");
+
+    let format = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("yaml" | "yml") => CurriculumFormat::Yaml,
+        _ => CurriculumFormat::Json
+    };
+
+    let document = CurriculumDocument::parse(&fs::read(path)?, format)
+        .map_err(|error| anyhow!("{}: {}", error.path, error.message))?;
+
+    let errors = document.validate();
+    if !errors.is_empty() {
+        let errors = errors
+            .iter()
+            .map(|error| format!("{}: {}", error.path, error.message))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        return Err(anyhow!("The curriculum is invalid:\n{}", errors));
+    }
+
+    let (rotation, created) = Rotation::import_curriculum(pool, &document, rotation_id, dry_run)
+        .await?
+        .ok_or_else(|| anyhow!("Rotation {} does not exist or has been archived.", rotation_id.unwrap_or_default()))?;
+
+    println!(
+        "{} {} supertasks, {} tasks and {} subtasks into rotation {}.",
+        if dry_run { "Would import" } else { "Imported" },
+        created.supertasks,
+        created.tasks,
+        created.subtasks,
+        if dry_run && rotation_id.is_none() { "(new)".to_string() } else { rotation.id().to_string() }
+    );
+
+    Ok(())
+}
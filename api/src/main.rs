@@ -5,15 +5,21 @@ mod services;
 mod models;
 mod utilities;
 mod middlewares;
+mod cli;
 
 use actix_web::{web::Data, App, HttpServer};
-use utilities::memory_cache::MemoryCache;
-use std::{io::Result, env::var};
+use utilities::{memory_cache::MemoryCache, cache_invalidation};
+use models::entry_structure::EntryStructure;
+use std::{io::Result, env::{args, var}, process::exit, time::Duration};
 use dotenv::dotenv;
-use services::config::config;
+use services::config::{config, RateLimiters};
 use actix_cors::Cors;
 use sqlx::PgPool;
 
+const RATE_LIMIT_PURGE_INTERVAL: Duration = Duration::from_secs(600);
+const ENTRY_TRASH_PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+const DEFAULT_ENTRY_TRASH_RETENTION_DAYS: i64 = 30;
+
 #[cfg(feature = "production")]
 mod prod_config {
     use actix_web::http::header::{self, HeaderName};
@@ -29,8 +35,6 @@ async fn main() -> Result<()> {
     // Load from .env file
     dotenv().ok();
 
-    let host_ip = var("HOST_IP").expect("Expected host IP.");
-    let host_port = var("HOST_PORT").expect("Expected host port.");
     let database_url = var("DATABASE_URL").expect("Expected database URL.");
 
     // Setup Postgres connection pool
@@ -38,6 +42,57 @@ async fn main() -> Result<()> {
         .await
         .expect("Failed to create database connection pool.");
 
+    // Run an administrative command instead of the server if one was given.
+    let args = args().skip(1).collect::<Vec<_>>();
+    if !args.is_empty() {
+        if let Err(error) = cli::run(&pool, &args).await {
+            eprintln!("{}", error);
+            exit(1);
+        }
+
+        return Ok(());
+    }
+
+    let host_ip = var("HOST_IP").expect("Expected host IP.");
+    let host_port = var("HOST_PORT").expect("Expected host port.");
+
+    // Build rate limiters once so that every worker shares the same limits.
+    let rate_limiters = RateLimiters::from_env(&pool);
+
+    // Periodically drop rate limiting state that has fully replenished.
+    let purged_rate_limiters = rate_limiters.clone();
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(RATE_LIMIT_PURGE_INTERVAL);
+
+        loop {
+            interval.tick().await;
+            purged_rate_limiters.purge_expired().await;
+        }
+    });
+
+    // Periodically empty the trash of entries that have been deleted for longer than the retention period.
+    let trash_retention = chrono::Duration::days(
+        var("ENTRY_TRASH_RETENTION_DAYS")
+            .map(|days| days.parse().expect("Expected entry trash retention to be a whole number of days."))
+            .unwrap_or(DEFAULT_ENTRY_TRASH_RETENTION_DAYS)
+    );
+
+    let trash_pool = pool.clone();
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(ENTRY_TRASH_PURGE_INTERVAL);
+
+        loop {
+            interval.tick().await;
+            let _ = EntryStructure::purge_deleted(&trash_pool, chrono::Utc::now() - trash_retention).await;
+        }
+    });
+
+    // Build the memory cache once so that every worker shares it.
+    let memory_cache = Data::new(MemoryCache::from_env());
+
+    // Evict what is cached about rotations changed by any instance, including this one.
+    actix_web::rt::spawn(cache_invalidation::listen(pool.clone(), memory_cache.clone()));
+
     // Print server details.
     println!("Server running on: {}:{}", host_ip, host_port);
 
@@ -47,8 +102,8 @@ async fn main() -> Result<()> {
         // Initialize the application.
         App::new()
             .app_data(Data::new(pool.clone()))
-            .app_data(Data::new(MemoryCache::new()))
-            .configure(config)
+            .app_data(memory_cache.clone())
+            .configure(|cfg| config(cfg, &rate_limiters))
             .wrap(cors())
     })
     .bind(format!("{}:{}",
@@ -0,0 +1,45 @@
+use std::future::{ready, Ready};
+use actix_web::{dev::Payload, error::{ErrorBadRequest, ErrorPreconditionRequired}, http::header::{self, EntityTag, Header, IfMatch}, FromRequest, HttpRequest};
+
+/// The revisions of an entry that a request changing it was made against, from its `If-Match` header.
+///
+/// The header is required, so that an entry is never changed by a client that has not seen its current revision.
+/// Sending `If-Match: *` allows the entry to be at any revision.
+pub struct IfMatchRevisions(Option<Box<[i32]>>);
+
+impl IfMatchRevisions {
+    /// The revisions the entry may be at, or `None` if it may be at any revision.
+    #[inline(always)]
+    pub fn revisions(&self) -> Option<&[i32]> {
+        self.0.as_deref()
+    }
+}
+
+/// Creates the entity tag of an entry at a revision.
+pub fn revision_tag(revision: i32) -> EntityTag {
+    EntityTag::new_strong(revision.to_string())
+}
+
+impl FromRequest for IfMatchRevisions {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        if !req.headers().contains_key(header::IF_MATCH) {
+            return ready(Err(ErrorPreconditionRequired("No If-Match header found.")));
+        }
+
+        // Weak tags never match, since If-Match uses the strong comparison.
+        let revisions = match IfMatch::parse(req) {
+            Ok(IfMatch::Any) => None,
+            Ok(IfMatch::Items(tags)) => Some(tags
+                .iter()
+                .filter(|tag| !tag.weak)
+                .filter_map(|tag| tag.tag().parse().ok())
+                .collect()),
+            Err(_) => return ready(Err(ErrorBadRequest("Invalid If-Match header.")))
+        };
+
+        ready(Ok(Self(revisions)))
+    }
+}
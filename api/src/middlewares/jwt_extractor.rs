@@ -47,11 +47,17 @@ impl TryFrom<JwtClaim<String>> for JwtUserClaim {
     }
 }
 
-impl FromRequest for JwtUserClaim {
-    type Error = actix_web::Error;
-    type Future = Ready<Result<Self, Self::Error>>;
-
-    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+impl JwtUserClaim {
+    /// Decodes and validates the claim carried in the authorization header of a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - The request to read the authorization header from.
+    ///
+    /// # Returns
+    ///
+    /// The decoded claim, or an error response if the header is missing, invalid or expired.
+    pub fn decode_from(req: &HttpRequest) -> Result<Self, actix_web::Error> {
         if let Some(auth_header) = req.headers().get(header::AUTHORIZATION) {
             if let Ok(token) = auth_header.to_str() {
                 let token_data = decode::<JwtClaim<String>>(
@@ -63,22 +69,28 @@ impl FromRequest for JwtUserClaim {
                 return match token_data {
                     Ok(data) => {
                         if data.claims.exp < Utc::now().timestamp() {
-                            return ready(Err(ErrorUnauthorized("Token has expired.")));
+                            return Err(ErrorUnauthorized("Token has expired."));
                         }
 
-                        let claim = match data.claims.try_into() {
-                            Ok(claim) => claim,
-                            Err(_) => return ready(Err(ErrorInternalServerError("Failed to parse token data.")))
-                        };
-
-                        ready(Ok(claim))
+                        data.claims
+                            .try_into()
+                            .map_err(|_| ErrorInternalServerError("Failed to parse token data."))
                     },
-                    Err(e) => ready(Err(ErrorInternalServerError(e.to_string())))
+                    Err(e) => Err(ErrorInternalServerError(e.to_string()))
                 };
             }
         }
 
-        ready(Err(ErrorUnauthorized("No authorization header found.")))
+        Err(ErrorUnauthorized("No authorization header found."))
+    }
+}
+
+impl FromRequest for JwtUserClaim {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(Self::decode_from(req))
     }
 
     fn extract(req: &HttpRequest) -> Self::Future {
@@ -1 +1,3 @@
-pub mod jwt_extractor;
\ No newline at end of file
+pub mod jwt_extractor;
+pub mod rate_limiter;
+pub mod if_match_extractor;
\ No newline at end of file
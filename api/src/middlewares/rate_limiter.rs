@@ -0,0 +1,282 @@
+use super::jwt_extractor::JwtUserClaim;
+use crate::utilities::trusted_proxies::TrustedProxies;
+use std::{fmt, future::{ready, Ready}, net::{IpAddr, Ipv4Addr}, num::NonZeroU32, rc::Rc, str::FromStr, sync::Arc, time::Duration};
+use actix_web::{body::{EitherBody, MessageBody}, dev::{Service, ServiceRequest, ServiceResponse, Transform}, http::header, Error, HttpResponse};
+use futures_util::future::LocalBoxFuture;
+use governor::{clock::{Clock, DefaultClock}, DefaultKeyedRateLimiter, Quota};
+use anyhow::{anyhow, Result};
+use sqlx::PgPool;
+
+/// The number of seconds to replenish a single request, and the number of requests that may be made in a burst.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    period_seconds: u64,
+    burst_size: u32
+}
+
+impl RateLimit {
+    pub const fn new(period_seconds: u64, burst_size: u32) -> Self {
+        Self { period_seconds, burst_size }
+    }
+
+    /// The time allowed for a burst to run ahead of the steady replenish rate.
+    #[inline(always)]
+    fn tolerance_seconds(&self) -> f64 {
+        (self.period_seconds * (self.burst_size as u64 - 1)) as f64
+    }
+}
+
+impl FromStr for RateLimit {
+    type Err = anyhow::Error;
+
+    /// Parses a rate limit in the form `<period seconds>:<burst size>`, such as `4:2`.
+    fn from_str(value: &str) -> Result<Self> {
+        let (period_seconds, burst_size) = value
+            .trim()
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Expected rate limit in the form <period seconds>:<burst size>"))?;
+
+        let period_seconds = period_seconds.trim().parse::<u64>()?;
+        let burst_size = burst_size.trim().parse::<u32>()?;
+
+        if period_seconds == 0 || burst_size == 0 {
+            return Err(anyhow!("Rate limit period and burst size must be positive"));
+        }
+
+        Ok(Self { period_seconds, burst_size })
+    }
+}
+
+/// Where rate limiting state is kept.
+#[derive(Clone)]
+pub enum RateLimiterBackend {
+    /// State is held in the memory of this instance.
+    Memory,
+
+    /// State is shared between every instance through the database.
+    Postgres(PgPool)
+}
+
+/// The identity that requests are counted against.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+enum RateLimitKey {
+    User(i32),
+    Ip(IpAddr)
+}
+
+impl fmt::Display for RateLimitKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::User(id) => write!(f, "user:{}", id),
+            Self::Ip(ip) => write!(f, "ip:{}", ip)
+        }
+    }
+}
+
+enum RateLimiterState {
+    Memory(DefaultKeyedRateLimiter<RateLimitKey>),
+    Postgres(PgPool)
+}
+
+struct RateLimiterInner {
+    name: &'static str,
+    limit: RateLimit,
+    trusted_proxies: Arc<TrustedProxies>,
+    state: RateLimiterState
+}
+
+/// Middleware that limits the rate of requests made by each client.
+///
+/// Requests are keyed on the authenticated user when a valid JWT is present, and on the client address otherwise.
+/// The client address is taken from `X-Forwarded-For` only when the connecting peer is a trusted proxy.
+#[derive(Clone)]
+pub struct RateLimiter(Option<Arc<RateLimiterInner>>);
+
+impl RateLimiter {
+    /// Creates a rate limiter.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A name unique to this limiter, used to separate its state from other limiters in the database.
+    /// * `limit` - The rate limit to enforce.
+    /// * `backend` - Where to keep the rate limiting state.
+    /// * `trusted_proxies` - The proxies whose `X-Forwarded-For` headers are trusted.
+    pub fn new(name: &'static str, limit: RateLimit, backend: RateLimiterBackend, trusted_proxies: Arc<TrustedProxies>) -> Self {
+        let state = match backend {
+            RateLimiterBackend::Memory => {
+                let quota = Quota::with_period(Duration::from_secs(limit.period_seconds))
+                    .expect("Rate limit period must be positive.")
+                    .allow_burst(NonZeroU32::new(limit.burst_size).expect("Rate limit burst size must be positive."));
+
+                RateLimiterState::Memory(governor::RateLimiter::keyed(quota))
+            },
+            RateLimiterBackend::Postgres(pool) => RateLimiterState::Postgres(pool)
+        };
+
+        Self(Some(Arc::new(RateLimiterInner {
+            name,
+            limit,
+            trusted_proxies,
+            state
+        })))
+    }
+
+    /// Creates a rate limiter that lets every request through.
+    pub fn permissive() -> Self {
+        Self(None)
+    }
+
+    /// Drops rate limiting state for clients whose limits have fully replenished.
+    pub async fn purge_expired(&self) -> Result<()> {
+        let inner = match &self.0 {
+            Some(inner) => inner,
+            None => return Ok(())
+        };
+
+        match &inner.state {
+            RateLimiterState::Memory(limiter) => {
+                limiter.retain_recent();
+                limiter.shrink_to_fit();
+            },
+            RateLimiterState::Postgres(pool) => {
+                sqlx::query!(
+                    r#"
+                    DELETE FROM rate_limit_buckets
+                    WHERE theoretical_arrival < NOW() AND left(key, length($1) + 1) = $1 || ':';
+                    "#,
+                    inner.name
+                )
+                .execute(pool)
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RateLimiterInner {
+    fn key(&self, req: &ServiceRequest) -> RateLimitKey {
+        if let Ok(claim) = JwtUserClaim::decode_from(req.request()) {
+            return RateLimitKey::User(claim.sub.id);
+        }
+
+        let peer = req
+            .peer_addr()
+            .map(|addr| addr.ip())
+            .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+
+        let forwarded_for = req
+            .headers()
+            .get(header::X_FORWARDED_FOR)
+            .and_then(|value| value.to_str().ok());
+
+        RateLimitKey::Ip(self.trusted_proxies.client_ip(peer, forwarded_for))
+    }
+
+    /// Records a request against the limit of the client that made it.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the request is allowed, otherwise the number of seconds until the client may try again.
+    async fn check(&self, req: &ServiceRequest) -> Option<u64> {
+        let key = self.key(req);
+
+        match &self.state {
+            RateLimiterState::Memory(limiter) => limiter
+                .check_key(&key)
+                .err()
+                .map(|not_until| not_until.wait_time_from(DefaultClock::default().now()).as_secs()),
+            RateLimiterState::Postgres(pool) => {
+                // Generic cell rate algorithm, where each bucket stores the theoretical arrival time of the next request.
+                let record = sqlx::query!(
+                    r#"
+                    WITH previous AS (
+                        SELECT theoretical_arrival FROM rate_limit_buckets WHERE key = $1
+                    ), attempt AS (
+                        INSERT INTO rate_limit_buckets (key, theoretical_arrival)
+                        VALUES ($1, NOW() + make_interval(secs => $2))
+                        ON CONFLICT (key) DO UPDATE
+                        SET theoretical_arrival = GREATEST(rate_limit_buckets.theoretical_arrival, NOW()) + make_interval(secs => $2)
+                        WHERE rate_limit_buckets.theoretical_arrival - NOW() <= make_interval(secs => $3)
+                        RETURNING 1
+                    )
+                    SELECT
+                        EXISTS(SELECT 1 FROM attempt) AS "allowed!",
+                        EXTRACT(EPOCH FROM (SELECT theoretical_arrival FROM previous) - NOW())::FLOAT8 - $3 AS retry_after_seconds;
+                    "#,
+                    format!("{}:{}", self.name, key),
+                    self.limit.period_seconds as f64,
+                    self.limit.tolerance_seconds()
+                )
+                .fetch_one(pool)
+                .await;
+
+                // Fail open so that a database hiccup does not lock every client out.
+                match record {
+                    Ok(record) if !record.allowed => Some(record.retry_after_seconds.unwrap_or(0.0).max(0.0).ceil() as u64),
+                    _ => None
+                }
+            }
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimiter
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = RateLimiterMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimiterMiddleware {
+            service: Rc::new(service),
+            limiter: self.0.clone()
+        }))
+    }
+}
+
+pub struct RateLimiterMiddleware<S> {
+    service: Rc<S>,
+    limiter: Option<Arc<RateLimiterInner>>
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimiterMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let limiter = self.limiter.clone();
+
+        Box::pin(async move {
+            if let Some(limiter) = limiter {
+                if let Some(retry_after_seconds) = limiter.check(&req).await {
+                    let response = HttpResponse::TooManyRequests()
+                        .insert_header((header::RETRY_AFTER, retry_after_seconds))
+                        .finish();
+
+                    return Ok(req.into_response(response).map_into_right_body());
+                }
+            }
+
+            service
+                .call(req)
+                .await
+                .map(ServiceResponse::map_into_left_body)
+        })
+    }
+}
+
@@ -0,0 +1,419 @@
+use super::prelude::*;
+use crate::utilities::{identifiers::contains_identifier, parsable::EntryTitle};
+use std::collections::HashMap;
+use chrono::{Days, NaiveDate};
+
+/// The maximum number of characters in the learning points of a case.
+const MAX_LEARNING_POINTS_LENGTH: usize = 2000;
+
+/// The case log fields that can only hold one of the values configured for them.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum CaseLogField {
+    Setting,
+    DiagnosisCategory,
+    TreatmentIntent,
+    Role
+}
+
+impl CaseLogField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Setting => "setting",
+            Self::DiagnosisCategory => "diagnosis_category",
+            Self::TreatmentIntent => "treatment_intent",
+            Self::Role => "role"
+        }
+    }
+}
+
+impl From<String> for CaseLogField {
+    fn from(value: String) -> Self {
+        // The database constrains the field, so anything else is unreachable.
+        match value.as_str() {
+            "diagnosis_category" => Self::DiagnosisCategory,
+            "treatment_intent" => Self::TreatmentIntent,
+            "role" => Self::Role,
+            _ => Self::Setting
+        }
+    }
+}
+
+/// A value a trainee can choose for an enumerated case log field.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CaseLogOption {
+    id: i32,
+    field: CaseLogField,
+    value: String
+}
+
+/// A de-identified case a trainee has seen, optionally linked to the subtasks it counts towards.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CaseLogEntry {
+    id: i32,
+    user_id: i32,
+    encountered_on: NaiveDate,
+    setting: String,
+    diagnosis_category: String,
+    treatment_intent: String,
+    role: String,
+    learning_points: String,
+    subtask_ids: Vec<i32>,
+    created_at: DateTime<Utc>
+}
+
+/// The fields of a case as entered by a trainee, which must be validated before being stored.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CaseLogInput {
+    pub encountered_on: NaiveDate,
+    pub setting: String,
+    pub diagnosis_category: String,
+    pub treatment_intent: String,
+    pub role: String,
+
+    #[serde(default)]
+    pub learning_points: String,
+
+    #[serde(default)]
+    pub subtask_ids: Vec<i32>
+}
+
+/// A problem with a field of a case.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CaseLogError {
+    pub field: String,
+    pub message: String
+}
+
+impl CaseLogError {
+    fn new(field: &str, message: impl Into<String>) -> Self {
+        Self { field: field.to_string(), message: message.into() }
+    }
+}
+
+/// The number of cases a trainee has logged, in total and for each value of every enumerated field.
+#[derive(Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CaseLogSummary {
+    total: i64,
+    counts: HashMap<CaseLogField, HashMap<String, i64>>
+}
+
+impl CaseLogOption {
+    pub async fn fetch_all(pool: &PgPool) -> Result<Box<[Self]>> {
+        let options = sqlx::query_as!(
+            CaseLogOption,
+            "SELECT id, field, value FROM case_log_options ORDER BY field, value;"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(options.into_boxed_slice())
+    }
+
+    /// Adds a value to an enumerated field.
+    ///
+    /// # Returns
+    ///
+    /// The new option, or `None` if the field already has that value.
+    pub async fn insert(pool: &PgPool, field: CaseLogField, value: &EntryTitle) -> Result<Option<Self>> {
+        let option = sqlx::query_as!(
+            CaseLogOption,
+            r#"
+            INSERT INTO case_log_options (field, value) VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            RETURNING id, field, value;
+            "#,
+            field.as_str(),
+            value.as_str()
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(option)
+    }
+
+    /// Removes a value from an enumerated field. Cases that have already been logged with the value keep it.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the option was deleted, `false` if it does not exist.
+    pub async fn delete(pool: &PgPool, id: i32) -> Result<bool> {
+        let result = sqlx::query!(
+            "DELETE FROM case_log_options WHERE id = $1;",
+            id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+impl CaseLogInput {
+    /// Validates every field of the case, collecting all problems rather than stopping at the first.
+    ///
+    /// Enumerated fields must hold one of their configured values, the subtasks must exist,
+    /// and the learning points must not look like they contain a patient identifier.
+    ///
+    /// # Returns
+    ///
+    /// Every problem found, which is empty if the case is valid.
+    pub async fn validate(&self, pool: &PgPool) -> Result<Vec<CaseLogError>> {
+        let mut errors = Vec::new();
+
+        // A day of leeway allows for trainees in time zones ahead of the server.
+        if self.encountered_on > Utc::now().date_naive() + Days::new(1) {
+            errors.push(CaseLogError::new("encounteredOn", "The date cannot be in the future"));
+        }
+
+        let options = sqlx::query!("SELECT field, value FROM case_log_options;")
+            .fetch_all(pool)
+            .await?;
+
+        let fields = [
+            (CaseLogField::Setting, "setting", &self.setting),
+            (CaseLogField::DiagnosisCategory, "diagnosisCategory", &self.diagnosis_category),
+            (CaseLogField::TreatmentIntent, "treatmentIntent", &self.treatment_intent),
+            (CaseLogField::Role, "role", &self.role)
+        ];
+
+        for (field, name, value) in fields {
+            let is_option = options
+                .iter()
+                .any(|option| option.field == field.as_str() && option.value == *value);
+
+            if !is_option {
+                errors.push(CaseLogError::new(name, "Not one of the configured values"));
+            }
+        }
+
+        if self.learning_points.chars().count() > MAX_LEARNING_POINTS_LENGTH {
+            errors.push(CaseLogError::new("learningPoints", format!("Must be at most {} characters", MAX_LEARNING_POINTS_LENGTH)));
+        }
+
+        if self.learning_points.contains(['<', '>']) {
+            errors.push(CaseLogError::new("learningPoints", "Must be plain text"));
+        }
+
+        if contains_identifier(&self.learning_points) {
+            errors.push(CaseLogError::new("learningPoints", "Must not contain anything that looks like a patient identifier"));
+        }
+
+        let existing_subtasks = sqlx::query!(
+            r#"SELECT COUNT(*) AS "count!" FROM subtasks WHERE id = ANY($1) AND deleted_at IS NULL;"#,
+            &self.subtask_ids
+        )
+        .fetch_one(pool)
+        .await?
+        .count;
+
+        let mut subtask_ids = self.subtask_ids.clone();
+        subtask_ids.sort_unstable();
+        subtask_ids.dedup();
+
+        if existing_subtasks != subtask_ids.len() as i64 {
+            errors.push(CaseLogError::new("subtaskIds", "Every subtask must exist"));
+        }
+
+        Ok(errors)
+    }
+}
+
+impl CaseLogEntry {
+    /// Logs a case for a trainee. The case must have been validated beforehand.
+    pub async fn insert(pool: &PgPool, user_id: i32, input: &CaseLogInput) -> Result<Self> {
+        let mut transaction = pool.begin().await?;
+
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO case_log_entries (user_id, encountered_on, setting, diagnosis_category, treatment_intent, role, learning_points)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id;
+            "#,
+            user_id,
+            input.encountered_on,
+            input.setting,
+            input.diagnosis_category,
+            input.treatment_intent,
+            input.role,
+            input.learning_points.trim()
+        )
+        .fetch_one(&mut *transaction)
+        .await?
+        .id;
+
+        sqlx::query!(
+            "INSERT INTO case_log_entry_subtasks (case_log_entry_id, subtask_id) SELECT DISTINCT $1::INT, UNNEST($2::INT[]);",
+            id,
+            &input.subtask_ids
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Self::fetch_one(pool, user_id, id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Case log entry was not inserted."))
+    }
+
+    /// Replaces the fields of one of a trainee's own cases. The case must have been validated beforehand.
+    ///
+    /// # Returns
+    ///
+    /// The updated case, or `None` if the trainee has no such case.
+    pub async fn update(pool: &PgPool, user_id: i32, id: i32, input: &CaseLogInput) -> Result<Option<Self>> {
+        let mut transaction = pool.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE case_log_entries
+            SET encountered_on = $3, setting = $4, diagnosis_category = $5, treatment_intent = $6, role = $7, learning_points = $8
+            WHERE id = $1 AND user_id = $2;
+            "#,
+            id,
+            user_id,
+            input.encountered_on,
+            input.setting,
+            input.diagnosis_category,
+            input.treatment_intent,
+            input.role,
+            input.learning_points.trim()
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        sqlx::query!(
+            "DELETE FROM case_log_entry_subtasks WHERE case_log_entry_id = $1;",
+            id
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO case_log_entry_subtasks (case_log_entry_id, subtask_id) SELECT DISTINCT $1::INT, UNNEST($2::INT[]);",
+            id,
+            &input.subtask_ids
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Self::fetch_one(pool, user_id, id).await
+    }
+
+    /// Deletes one of a trainee's own cases.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the case was deleted, `false` if the trainee has no such case.
+    pub async fn delete(pool: &PgPool, user_id: i32, id: i32) -> Result<bool> {
+        let result = sqlx::query!(
+            "DELETE FROM case_log_entries WHERE id = $1 AND user_id = $2;",
+            id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn fetch_one(pool: &PgPool, user_id: i32, id: i32) -> Result<Option<Self>> {
+        let entry = sqlx::query_as!(
+            CaseLogEntry,
+            r#"
+            SELECT
+                id, user_id, encountered_on, setting, diagnosis_category, treatment_intent, role, learning_points, created_at,
+                ARRAY(SELECT subtask_id FROM case_log_entry_subtasks WHERE case_log_entry_id = case_log_entries.id ORDER BY subtask_id) AS "subtask_ids!"
+            FROM case_log_entries
+            WHERE id = $1 AND user_id = $2;
+            "#,
+            id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(entry)
+    }
+
+    /// Fetches every case a trainee has logged, most recent first.
+    pub async fn fetch_all(pool: &PgPool, user_id: i32) -> Result<Box<[Self]>> {
+        let entries = sqlx::query_as!(
+            CaseLogEntry,
+            r#"
+            SELECT
+                id, user_id, encountered_on, setting, diagnosis_category, treatment_intent, role, learning_points, created_at,
+                ARRAY(SELECT subtask_id FROM case_log_entry_subtasks WHERE case_log_entry_id = case_log_entries.id ORDER BY subtask_id) AS "subtask_ids!"
+            FROM case_log_entries
+            WHERE user_id = $1
+            ORDER BY encountered_on DESC, id DESC;
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(entries.into_boxed_slice())
+    }
+}
+
+impl CaseLogSummary {
+    /// Counts the cases logged by a trainee, or by every trainee, optionally within a range of dates.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `user_id` - The ID of the trainee, or `None` for every trainee.
+    /// * `from` - The earliest date to count cases from, inclusive.
+    /// * `to` - The latest date to count cases until, inclusive.
+    pub async fn fetch(pool: &PgPool, user_id: Option<i32>, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<Self> {
+        let rows = sqlx::query!(
+            r#"
+            WITH cases AS (
+                SELECT * FROM case_log_entries
+                WHERE ($1::INT IS NULL OR user_id = $1)
+                    AND ($2::DATE IS NULL OR encountered_on >= $2)
+                    AND ($3::DATE IS NULL OR encountered_on <= $3)
+            )
+            SELECT 'setting' AS "field!", setting AS "value!", COUNT(*) AS "count!" FROM cases GROUP BY setting
+            UNION ALL
+            SELECT 'diagnosis_category', diagnosis_category, COUNT(*) FROM cases GROUP BY diagnosis_category
+            UNION ALL
+            SELECT 'treatment_intent', treatment_intent, COUNT(*) FROM cases GROUP BY treatment_intent
+            UNION ALL
+            SELECT 'role', role, COUNT(*) FROM cases GROUP BY role;
+            "#,
+            user_id,
+            from,
+            to
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut summary = Self::default();
+        for row in rows.into_iter() {
+            let field = CaseLogField::from(row.field);
+            if field == CaseLogField::Setting {
+                summary.total += row.count;
+            }
+
+            summary.counts
+                .entry(field)
+                .or_default()
+                .insert(row.value, row.count);
+        }
+
+        Ok(summary)
+    }
+}
@@ -0,0 +1,218 @@
+use super::prelude::*;
+use crate::utilities::parsable::{Description, EntryTitle, Name};
+use std::collections::HashMap;
+
+/// A competency from an assessment framework, such as a CanMEDS role or an EPA, that subtasks can be tagged with.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Competency {
+    id: i32,
+    framework: Name,
+    title: EntryTitle,
+    description: Description
+}
+
+/// How many of the subtasks tagged with a competency a trainee has completed, across every rotation.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompetencyProgress {
+    user_id: i32,
+    competency_id: i32,
+    completed_subtasks: i64,
+    total_subtasks: i64
+}
+
+/// Checks whether an error was caused by a competency with the same framework and title already existing.
+pub fn is_duplicate(error: &anyhow::Error) -> bool {
+    match error.downcast_ref::<sqlx::Error>() {
+        Some(sqlx::Error::Database(error)) => error.is_unique_violation(),
+        _ => false
+    }
+}
+
+impl Competency {
+    pub async fn insert(pool: &PgPool, framework: &Name, title: &EntryTitle, description: &Description) -> Result<Self> {
+        let competency = sqlx::query_as!(
+            Competency,
+            r#"
+            INSERT INTO competencies (framework, title, description) VALUES ($1, $2, $3)
+            RETURNING id, framework, title, description;
+            "#,
+            framework.as_str(),
+            title.as_str(),
+            description.as_str()
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(competency)
+    }
+
+    /// Fetches the whole competency catalog, grouped by framework.
+    pub async fn fetch_all(pool: &PgPool) -> Result<Box<[Self]>> {
+        let competencies = sqlx::query_as!(
+            Competency,
+            "SELECT id, framework, title, description FROM competencies ORDER BY framework, title;"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(competencies.into_boxed_slice())
+    }
+
+    /// Updates a competency.
+    ///
+    /// # Returns
+    ///
+    /// The updated competency, or `None` if the competency does not exist.
+    pub async fn update(pool: &PgPool, id: i32, framework: &Name, title: &EntryTitle, description: &Description) -> Result<Option<Self>> {
+        let competency = sqlx::query_as!(
+            Competency,
+            r#"
+            UPDATE competencies SET framework = $1, title = $2, description = $3
+            WHERE id = $4
+            RETURNING id, framework, title, description;
+            "#,
+            framework.as_str(),
+            title.as_str(),
+            description.as_str(),
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(competency)
+    }
+
+    /// Deletes a competency, untagging every subtask that was tagged with it.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the competency was deleted, `false` if it does not exist.
+    pub async fn delete(pool: &PgPool, id: i32) -> Result<bool> {
+        let result = sqlx::query!(
+            "DELETE FROM competencies WHERE id = $1;",
+            id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Replaces the competencies a subtask is tagged with.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `subtask_id` - The ID of the subtask, which must exist.
+    /// * `competency_ids` - The IDs of every competency the subtask should be tagged with.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the subtask was tagged, `false` if any of the competencies do not exist.
+    pub async fn tag_subtask(pool: &PgPool, subtask_id: i32, competency_ids: &[i32]) -> Result<bool> {
+        let mut transaction = pool.begin().await?;
+
+        let existing = sqlx::query!(
+            r#"SELECT COUNT(*) AS "count!" FROM competencies WHERE id = ANY($1);"#,
+            competency_ids
+        )
+        .fetch_one(&mut *transaction)
+        .await?
+        .count;
+
+        let mut unique_ids = competency_ids.to_vec();
+        unique_ids.sort_unstable();
+        unique_ids.dedup();
+
+        if existing != unique_ids.len() as i64 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            "DELETE FROM subtask_competencies WHERE subtask_id = $1;",
+            subtask_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO subtask_competencies (subtask_id, competency_id) SELECT $1, UNNEST($2::INT[]);",
+            subtask_id,
+            &unique_ids
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Fetches the competencies each subtask of a rotation is tagged with.
+    ///
+    /// # Returns
+    ///
+    /// A map from the ID of each tagged subtask to the IDs of its competencies.
+    pub async fn fetch_subtask_tags(pool: &PgPool, rotation_id: i32) -> Result<HashMap<i32, Vec<i32>>> {
+        let records = sqlx::query!(
+            r#"
+            SELECT subtask_competencies.subtask_id, subtask_competencies.competency_id
+            FROM subtask_competencies
+            JOIN subtasks ON subtasks.id = subtask_competencies.subtask_id
+            WHERE subtasks.rotation_id = $1 AND subtasks.deleted_at IS NULL
+            ORDER BY subtask_competencies.subtask_id, subtask_competencies.competency_id;
+            "#,
+            rotation_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut tags: HashMap<i32, Vec<i32>> = HashMap::new();
+        for record in records.into_iter() {
+            tags
+                .entry(record.subtask_id)
+                .or_default()
+                .push(record.competency_id);
+        }
+
+        Ok(tags)
+    }
+
+    /// Computes how many of the subtasks tagged with each competency every trainee has completed, across all rotations.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `user_id` - The ID of a single trainee to compute the progress of, or `None` for every trainee.
+    ///
+    /// # Returns
+    ///
+    /// The progress of each trainee on each competency, including competencies they have not started.
+    pub async fn fetch_progress(pool: &PgPool, user_id: Option<i32>) -> Result<Box<[CompetencyProgress]>> {
+        let progress = sqlx::query_as!(
+            CompetencyProgress,
+            r#"
+            SELECT
+                users.id AS user_id,
+                competencies.id AS competency_id,
+                COUNT(DISTINCT user_tasks.subtask_id) FILTER (WHERE user_tasks.is_completed) AS "completed_subtasks!",
+                COUNT(DISTINCT subtask_competencies.subtask_id) AS "total_subtasks!"
+            FROM users
+            CROSS JOIN competencies
+            LEFT JOIN subtask_competencies ON subtask_competencies.competency_id = competencies.id
+                AND EXISTS (SELECT 1 FROM subtasks WHERE subtasks.id = subtask_competencies.subtask_id AND subtasks.deleted_at IS NULL)
+            LEFT JOIN user_tasks ON user_tasks.subtask_id = subtask_competencies.subtask_id AND user_tasks.user_id = users.id
+            WHERE NOT users.is_admin AND ($1::INT IS NULL OR users.id = $1)
+            GROUP BY users.id, competencies.id
+            ORDER BY users.id, competencies.id;
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(progress.into_boxed_slice())
+    }
+}
@@ -0,0 +1,303 @@
+use super::{entry_structure::EntryStructure, prelude::*};
+use crate::utilities::parsable::{EntryTitle, Name};
+use std::collections::{HashMap, HashSet};
+use sqlx::{Postgres, Transaction};
+
+/// The version of the curriculum document format written by this server.
+pub const CURRICULUM_FORMAT_VERSION: u32 = 1;
+
+/// The serialization formats curriculum documents can be written in.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CurriculumFormat {
+    #[default]
+    Json,
+    Yaml
+}
+
+impl CurriculumFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Yaml => "application/yaml"
+        }
+    }
+}
+
+/// A portable, ID-free representation of a rotation's entry structure.
+///
+/// Curricula are serialized as JSON or YAML so that they can be kept in version control and shared between programs.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CurriculumDocument {
+    pub version: u32,
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub supertasks: Vec<CurriculumSupertask>
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CurriculumSupertask {
+    pub title: String,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tasks: Vec<CurriculumTask>
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CurriculumTask {
+    pub title: String,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subtasks: Vec<CurriculumSubtask>
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CurriculumSubtask {
+    pub title: String
+}
+
+/// A problem with a curriculum document, along with where in the document it was found.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CurriculumError {
+    /// The location of the problem, such as `supertasks[0].tasks[2].title`.
+    pub path: String,
+    pub message: String
+}
+
+impl CurriculumError {
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { path: path.into(), message: message.into() }
+    }
+}
+
+/// The number of entries created at each level by an import.
+#[derive(Serialize, Debug, Default, Clone, Copy)]
+pub struct CurriculumImportSummary {
+    pub supertasks: u64,
+    pub tasks: u64,
+    pub subtasks: u64
+}
+
+/// Validates the title of an entry, and that it is not the same as the title of a sibling entry.
+fn validate_title(errors: &mut Vec<CurriculumError>, siblings: &mut HashSet<String>, path: String, title: &str) {
+    match EntryTitle::parse(title.to_string()) {
+        Ok(title) => {
+            if !siblings.insert(title.as_str().to_lowercase()) {
+                errors.push(CurriculumError::new(format!("{}.title", path), "Duplicate title within the same parent"));
+            }
+        },
+        Err(_) => errors.push(CurriculumError::new(format!("{}.title", path), "Invalid title"))
+    }
+}
+
+impl CurriculumDocument {
+    /// Builds a curriculum document from the entry structure of a rotation.
+    pub fn from_structure(name: &Name, structure: &EntryStructure) -> Self {
+        let supertasks = structure
+            .hierarchies()
+            .iter()
+            .map(|supertask| CurriculumSupertask {
+                title: supertask.entry().title().as_str().to_string(),
+                tasks: supertask
+                    .children()
+                    .iter()
+                    .map(|task| CurriculumTask {
+                        title: task.entry().title().as_str().to_string(),
+                        subtasks: task
+                            .children()
+                            .iter()
+                            .map(|subtask| CurriculumSubtask { title: subtask.title().as_str().to_string() })
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            version: CURRICULUM_FORMAT_VERSION,
+            name: name.as_str().to_string(),
+            supertasks
+        }
+    }
+
+    /// Parses a document, reporting where in the document parsing failed.
+    pub fn parse(bytes: &[u8], format: CurriculumFormat) -> Result<Self, CurriculumError> {
+        match format {
+            CurriculumFormat::Json => serde_json::from_slice(bytes)
+                .map_err(|error| CurriculumError::new(format!("line {}, column {}", error.line(), error.column()), error.to_string())),
+            CurriculumFormat::Yaml => serde_yaml::from_slice(bytes)
+                .map_err(|error| {
+                    let path = match error.location() {
+                        Some(location) => format!("line {}, column {}", location.line(), location.column()),
+                        None => String::new()
+                    };
+
+                    CurriculumError::new(path, error.to_string())
+                })
+        }
+    }
+
+    /// Serializes the document in the given format.
+    pub fn serialize(&self, format: CurriculumFormat) -> Result<String> {
+        Ok(match format {
+            CurriculumFormat::Json => serde_json::to_string_pretty(self)?,
+            CurriculumFormat::Yaml => serde_yaml::to_string(self)?
+        })
+    }
+
+    /// Validates every part of the document, collecting all problems rather than stopping at the first.
+    ///
+    /// # Returns
+    ///
+    /// Every problem found, which is empty if the document is valid.
+    pub fn validate(&self) -> Vec<CurriculumError> {
+        let mut errors = Vec::new();
+
+        if self.version == 0 || self.version > CURRICULUM_FORMAT_VERSION {
+            errors.push(CurriculumError::new("version", format!("Unsupported version, expected at most {}", CURRICULUM_FORMAT_VERSION)));
+        }
+
+        if Name::parse(self.name.clone()).is_err() {
+            errors.push(CurriculumError::new("name", "Invalid rotation name"));
+        }
+
+        let mut supertask_titles = HashSet::new();
+        for (supertask_index, supertask) in self.supertasks.iter().enumerate() {
+            let supertask_path = format!("supertasks[{}]", supertask_index);
+            validate_title(&mut errors, &mut supertask_titles, supertask_path.clone(), &supertask.title);
+
+            let mut task_titles = HashSet::new();
+            for (task_index, task) in supertask.tasks.iter().enumerate() {
+                let task_path = format!("{}.tasks[{}]", supertask_path, task_index);
+                validate_title(&mut errors, &mut task_titles, task_path.clone(), &task.title);
+
+                let mut subtask_titles = HashSet::new();
+                for (subtask_index, subtask) in task.subtasks.iter().enumerate() {
+                    let subtask_path = format!("{}.subtasks[{}]", task_path, subtask_index);
+                    validate_title(&mut errors, &mut subtask_titles, subtask_path, &subtask.title);
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Merges the document into the entry structure of a rotation.
+    ///
+    /// Entries are matched to existing entries with the same title under the same parent, and only missing entries are created.
+    /// Existing entries that are not in the document are left untouched. The document must have been validated beforehand.
+    ///
+    /// # Parameters
+    ///
+    /// * `transaction` - The transaction to perform the merge in.
+    /// * `rotation_id` - The ID of the rotation to merge into.
+    ///
+    /// # Returns
+    ///
+    /// The number of entries created at each level.
+    pub async fn merge_into(&self, transaction: &mut Transaction<'_, Postgres>, rotation_id: i32) -> Result<CurriculumImportSummary> {
+        let mut summary = CurriculumImportSummary::default();
+
+        let mut existing_supertasks = sqlx::query!(
+            "SELECT id, title FROM supertasks WHERE rotation_id = $1 AND deleted_at IS NULL;",
+            rotation_id
+        )
+        .fetch_all(&mut **transaction)
+        .await?
+        .into_iter()
+        .map(|row| (row.title.to_lowercase(), row.id))
+        .collect::<HashMap<_, _>>();
+
+        let mut existing_tasks = sqlx::query!(
+            "SELECT id, supertask_id, title FROM tasks WHERE rotation_id = $1 AND deleted_at IS NULL;",
+            rotation_id
+        )
+        .fetch_all(&mut **transaction)
+        .await?
+        .into_iter()
+        .map(|row| ((row.supertask_id, row.title.to_lowercase()), row.id))
+        .collect::<HashMap<_, _>>();
+
+        let mut existing_subtasks = sqlx::query!(
+            "SELECT id, task_id, title FROM subtasks WHERE rotation_id = $1 AND deleted_at IS NULL;",
+            rotation_id
+        )
+        .fetch_all(&mut **transaction)
+        .await?
+        .into_iter()
+        .map(|row| ((row.task_id, row.title.to_lowercase()), row.id))
+        .collect::<HashMap<_, _>>();
+
+        for supertask in self.supertasks.iter() {
+            let title = EntryTitle::parse(supertask.title.clone())?;
+            let supertask_id = match existing_supertasks.get(&title.as_str().to_lowercase()) {
+                Some(id) => *id,
+                None => {
+                    let id = sqlx::query!(
+                        "INSERT INTO supertasks (title, rotation_id) VALUES ($1, $2) RETURNING id;",
+                        title.as_str(),
+                        rotation_id
+                    )
+                    .fetch_one(&mut **transaction)
+                    .await?
+                    .id;
+
+                    existing_supertasks.insert(title.as_str().to_lowercase(), id);
+                    summary.supertasks += 1;
+                    id
+                }
+            };
+
+            for task in supertask.tasks.iter() {
+                let title = EntryTitle::parse(task.title.clone())?;
+                let key = (supertask_id, title.as_str().to_lowercase());
+                let task_id = match existing_tasks.get(&key) {
+                    Some(id) => *id,
+                    None => {
+                        let id = sqlx::query!(
+                            "INSERT INTO tasks (title, rotation_id, supertask_id) VALUES ($1, $2, $3) RETURNING id;",
+                            title.as_str(),
+                            rotation_id,
+                            supertask_id
+                        )
+                        .fetch_one(&mut **transaction)
+                        .await?
+                        .id;
+
+                        existing_tasks.insert(key, id);
+                        summary.tasks += 1;
+                        id
+                    }
+                };
+
+                for subtask in task.subtasks.iter() {
+                    let title = EntryTitle::parse(subtask.title.clone())?;
+                    let key = (task_id, title.as_str().to_lowercase());
+                    if existing_subtasks.contains_key(&key) {
+                        continue;
+                    }
+
+                    let id = sqlx::query!(
+                        "INSERT INTO subtasks (title, rotation_id, task_id) VALUES ($1, $2, $3) RETURNING id;",
+                        title.as_str(),
+                        rotation_id,
+                        task_id
+                    )
+                    .fetch_one(&mut **transaction)
+                    .await?
+                    .id;
+
+                    existing_subtasks.insert(key, id);
+                    summary.subtasks += 1;
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+}
@@ -0,0 +1,601 @@
+use super::{entry_structure::{EntryStructure, Subtask}, prelude::*};
+use std::collections::{HashMap, HashSet};
+
+/// A published version of the curriculum of a rotation.
+///
+/// The live entries of a rotation are its draft, and publishing them takes an immutable snapshot of their structure.
+/// Trainees follow the version they started on until they are migrated to another, or the latest version if they have not started yet.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CurriculumVersion {
+    id: i32,
+    rotation_id: i32,
+    version: i32,
+    notes: String,
+    published_by: Option<i32>,
+    published_at: DateTime<Utc>,
+    trainee_count: i64
+}
+
+/// A published version along with the entries it was published with.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CurriculumSnapshot {
+    #[serde(flatten)]
+    version: CurriculumVersion,
+    entries: EntryStructure
+}
+
+/// The version of a curriculum a trainee follows.
+struct FollowedVersion {
+    id: i32,
+    structure: serde_json::Value,
+    subtask_ids: Vec<i32>
+}
+
+/// An entry that was added to or removed from a curriculum.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffEntry {
+    id: i32,
+    parent_id: Option<i32>,
+    title: String
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamedEntry {
+    id: i32,
+    from: String,
+    to: String
+}
+
+/// An entry that was moved under another parent.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MovedEntry {
+    id: i32,
+    title: String,
+    from_parent_id: i32,
+    to_parent_id: i32
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RequiredCountChange {
+    id: i32,
+    title: String,
+    from: i32,
+    to: i32
+}
+
+/// The changes to a single level of entries between two versions of a curriculum.
+#[derive(Serialize, Debug, Default)]
+pub struct LevelDiff {
+    added: Vec<DiffEntry>,
+    removed: Vec<DiffEntry>,
+    renamed: Vec<RenamedEntry>,
+    moved: Vec<MovedEntry>
+}
+
+/// The changes between two versions of a curriculum, matching entries by their IDs.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CurriculumDiff {
+    from_version: i32,
+
+    /// The version compared against, or `None` for the draft.
+    to_version: Option<i32>,
+    supertasks: LevelDiff,
+    tasks: LevelDiff,
+    subtasks: LevelDiff,
+    required_counts: Vec<RequiredCountChange>
+}
+
+/// An entry of a curriculum without its hierarchy, used to compare versions.
+struct FlatEntry {
+    id: i32,
+    parent_id: Option<i32>,
+    title: String,
+    required_count: i32
+}
+
+/// Maps progress on a subtask of one version onto a subtask of another.
+#[derive(Deserialize, Debug)]
+pub struct SubtaskMapping {
+    from: i32,
+    to: i32
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationSummary {
+    from_version: i32,
+    to_version: i32,
+
+    /// The IDs of the trainees that were moved onto the new version.
+    user_ids: Vec<i32>,
+
+    /// The number of user tasks that were moved onto another subtask by a mapping.
+    remapped_tasks: usize,
+
+    /// The number of user tasks on subtasks that are not in the new version, which are kept but no longer count towards progress.
+    unmapped_tasks: usize,
+    dry_run: bool
+}
+
+/// The outcome of migrating trainees from one version of a curriculum to another.
+pub enum Migration {
+    Applied(MigrationSummary),
+
+    /// Either version does not exist in the rotation.
+    VersionNotFound,
+
+    /// A mapping is not from a subtask of the old version to a subtask of the new version, or maps the same subtask twice.
+    InvalidMapping
+}
+
+/// Flattens the levels of an entry structure, in order.
+fn flatten(structure: &EntryStructure) -> [Vec<FlatEntry>; 3] {
+    let mut levels: [Vec<FlatEntry>; 3] = Default::default();
+
+    for supertask in structure.hierarchies() {
+        levels[0].push(FlatEntry {
+            id: supertask.entry().id(),
+            parent_id: None,
+            title: supertask.entry().title().as_str().to_string(),
+            required_count: 1
+        });
+
+        for task in supertask.children() {
+            levels[1].push(FlatEntry {
+                id: task.entry().id(),
+                parent_id: Some(supertask.entry().id()),
+                title: task.entry().title().as_str().to_string(),
+                required_count: 1
+            });
+
+            for subtask in task.children() {
+                levels[2].push(FlatEntry {
+                    id: subtask.id(),
+                    parent_id: Some(task.entry().id()),
+                    title: subtask.title().as_str().to_string(),
+                    required_count: subtask.required_count()
+                });
+            }
+        }
+    }
+
+    levels
+}
+
+fn diff_level(from: &[FlatEntry], to: &[FlatEntry]) -> LevelDiff {
+    let from_entries = from.iter().map(|entry| (entry.id, entry)).collect::<HashMap<_, _>>();
+    let to_entries = to.iter().map(|entry| (entry.id, entry)).collect::<HashMap<_, _>>();
+
+    let mut diff = LevelDiff::default();
+
+    for entry in from.iter().filter(|entry| !to_entries.contains_key(&entry.id)) {
+        diff.removed.push(DiffEntry { id: entry.id, parent_id: entry.parent_id, title: entry.title.clone() });
+    }
+
+    for entry in to.iter() {
+        let Some(previous) = from_entries.get(&entry.id) else {
+            diff.added.push(DiffEntry { id: entry.id, parent_id: entry.parent_id, title: entry.title.clone() });
+            continue;
+        };
+
+        if previous.title != entry.title {
+            diff.renamed.push(RenamedEntry { id: entry.id, from: previous.title.clone(), to: entry.title.clone() });
+        }
+
+        if let (Some(from_parent_id), Some(to_parent_id)) = (previous.parent_id, entry.parent_id) {
+            if from_parent_id != to_parent_id {
+                diff.moved.push(MovedEntry { id: entry.id, title: entry.title.clone(), from_parent_id, to_parent_id });
+            }
+        }
+    }
+
+    diff
+}
+
+impl CurriculumVersion {
+    /// Publishes the draft of a rotation as its next version.
+    ///
+    /// Trainees who have already made progress on the rotation without following a version start following this one.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `rotation_id` - The ID of the rotation.
+    /// * `published_by` - The ID of the admin publishing the version.
+    /// * `notes` - Notes describing the changes in the version.
+    ///
+    /// # Returns
+    ///
+    /// The new version, or `None` if the rotation does not exist.
+    pub async fn publish(pool: &PgPool, rotation_id: i32, published_by: i32, notes: &str) -> Result<Option<Self>> {
+        let mut transaction = pool.begin().await?;
+
+        // Locking the rotation numbers its versions one at a time.
+        let rotation = sqlx::query!("SELECT id FROM rotations WHERE id = $1 FOR UPDATE;", rotation_id)
+            .fetch_optional(&mut *transaction)
+            .await?;
+
+        if rotation.is_none() {
+            return Ok(None);
+        }
+
+        // The draft is read while the rotation is locked, so that no edit lands between the snapshot and the version.
+        let structure = EntryStructure::fetch_with(&mut transaction, rotation_id).await?;
+        let subtask_ids = flatten(&structure)[2]
+            .iter()
+            .map(|subtask| subtask.id)
+            .collect::<Vec<_>>();
+
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO curriculum_versions (rotation_id, version, notes, structure, subtask_ids, published_by)
+            SELECT $1, COALESCE(MAX(version), 0) + 1, $2, $3, $4, $5 FROM curriculum_versions WHERE rotation_id = $1
+            RETURNING id, version, published_at;
+            "#,
+            rotation_id,
+            notes,
+            serde_json::to_value(&structure)?,
+            &subtask_ids,
+            published_by
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        let enrolled = sqlx::query!(
+            r#"
+            INSERT INTO curriculum_enrollments (user_id, rotation_id, version_id)
+            SELECT DISTINCT user_id, rotation_id, $2::INT FROM user_tasks WHERE rotation_id = $1
+            ON CONFLICT DO NOTHING;
+            "#,
+            rotation_id,
+            row.id
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        // Trainees who have not started yet now see the new version.
+        sqlx::query!("UPDATE rotations SET last_updated = NOW() WHERE id = $1;", rotation_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(Some(Self {
+            id: row.id,
+            rotation_id,
+            version: row.version,
+            notes: notes.to_string(),
+            published_by: Some(published_by),
+            published_at: row.published_at,
+            trainee_count: enrolled.rows_affected() as i64
+        }))
+    }
+
+    /// Fetches every published version of a rotation, newest first.
+    pub async fn fetch_all(pool: &PgPool, rotation_id: i32) -> Result<Box<[Self]>> {
+        let versions = sqlx::query_as!(
+            Self,
+            r#"
+            SELECT
+                id, rotation_id, version, notes, published_by, published_at,
+                (SELECT COUNT(*) FROM curriculum_enrollments WHERE version_id = curriculum_versions.id) AS "trainee_count!"
+            FROM curriculum_versions
+            WHERE rotation_id = $1
+            ORDER BY version DESC;
+            "#,
+            rotation_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(versions.into_boxed_slice())
+    }
+
+    /// Fetches a published version of a rotation along with its entries.
+    ///
+    /// # Returns
+    ///
+    /// The snapshot, or `None` if the version does not exist.
+    pub async fn fetch_snapshot(pool: &PgPool, rotation_id: i32, version: i32) -> Result<Option<CurriculumSnapshot>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                id, notes, structure, published_by, published_at,
+                (SELECT COUNT(*) FROM curriculum_enrollments WHERE version_id = curriculum_versions.id) AS "trainee_count!"
+            FROM curriculum_versions
+            WHERE rotation_id = $1 AND version = $2;
+            "#,
+            rotation_id,
+            version
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        Ok(Some(CurriculumSnapshot {
+            version: Self {
+                id: row.id,
+                rotation_id,
+                version,
+                notes: row.notes,
+                published_by: row.published_by,
+                published_at: row.published_at,
+                trainee_count: row.trainee_count
+            },
+            entries: serde_json::from_value(row.structure)?
+        }))
+    }
+
+    /// Fetches the version a trainee follows, which is the one they are enrolled in or otherwise the latest.
+    async fn fetch_followed(pool: &PgPool, user_id: i32, rotation_id: i32) -> Result<Option<FollowedVersion>> {
+        let version = sqlx::query_as!(
+            FollowedVersion,
+            r#"
+            SELECT id, structure, subtask_ids
+            FROM curriculum_versions
+            WHERE rotation_id = $2 AND id = COALESCE(
+                (SELECT version_id FROM curriculum_enrollments WHERE user_id = $1 AND rotation_id = $2),
+                (SELECT id FROM curriculum_versions WHERE rotation_id = $2 ORDER BY version DESC LIMIT 1)
+            );
+            "#,
+            user_id,
+            rotation_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(version)
+    }
+
+    /// Fetches the entries of the curriculum a trainee follows on a rotation, which is the draft until a version has been published.
+    pub async fn fetch_trainee_structure(pool: &PgPool, user_id: i32, rotation_id: i32) -> Result<EntryStructure> {
+        match Self::fetch_followed_structure(pool, user_id, rotation_id).await? {
+            Some(structure) => Ok(structure),
+            None => EntryStructure::fetch(pool, rotation_id).await
+        }
+    }
+
+    /// Fetches the entries of the version a trainee follows on a rotation, or `None` if no version has been published and they follow the draft.
+    pub async fn fetch_followed_structure(pool: &PgPool, user_id: i32, rotation_id: i32) -> Result<Option<EntryStructure>> {
+        match Self::fetch_followed(pool, user_id, rotation_id).await? {
+            Some(version) => Ok(Some(serde_json::from_value(version.structure)?)),
+            None => Ok(None)
+        }
+    }
+
+    /// Checks that a subtask is in the curriculum a trainee follows on a rotation before they make progress on it,
+    /// enrolling them in the latest version if they have not started on one yet.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the trainee can make progress on the subtask, `false` if it is not in their curriculum.
+    pub async fn enroll_for_subtask(pool: &PgPool, user_id: i32, rotation_id: i32, subtask_id: i32) -> Result<bool> {
+        let Some(version) = Self::fetch_followed(pool, user_id, rotation_id).await? else {
+            return Ok(Subtask::fetch_rotation_id(pool, subtask_id).await? == Some(rotation_id));
+        };
+
+        if !version.subtask_ids.contains(&subtask_id) {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            "INSERT INTO curriculum_enrollments (user_id, rotation_id, version_id) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING;",
+            user_id,
+            rotation_id,
+            version.id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(true)
+    }
+
+    /// Compares two versions of the curriculum of a rotation.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `rotation_id` - The ID of the rotation.
+    /// * `from_version` - The version to compare from.
+    /// * `to_version` - The version to compare to, or `None` to compare to the draft.
+    ///
+    /// # Returns
+    ///
+    /// The changes, or `None` if either version does not exist.
+    pub async fn diff(pool: &PgPool, rotation_id: i32, from_version: i32, to_version: Option<i32>) -> Result<Option<CurriculumDiff>> {
+        let Some(from) = Self::fetch_snapshot(pool, rotation_id, from_version).await? else {
+            return Ok(None);
+        };
+
+        let to = match to_version {
+            Some(to_version) => match Self::fetch_snapshot(pool, rotation_id, to_version).await? {
+                Some(snapshot) => snapshot.entries,
+                None => return Ok(None)
+            },
+            None => EntryStructure::fetch(pool, rotation_id).await?
+        };
+
+        let [from_supertasks, from_tasks, from_subtasks] = flatten(&from.entries);
+        let [to_supertasks, to_tasks, to_subtasks] = flatten(&to);
+
+        let previous_counts = from_subtasks
+            .iter()
+            .map(|subtask| (subtask.id, subtask.required_count))
+            .collect::<HashMap<_, _>>();
+
+        let required_counts = to_subtasks
+            .iter()
+            .filter_map(|subtask| {
+                let from = *previous_counts.get(&subtask.id)?;
+                (from != subtask.required_count).then(|| RequiredCountChange {
+                    id: subtask.id,
+                    title: subtask.title.clone(),
+                    from,
+                    to: subtask.required_count
+                })
+            })
+            .collect();
+
+        Ok(Some(CurriculumDiff {
+            from_version,
+            to_version,
+            supertasks: diff_level(&from_supertasks, &to_supertasks),
+            tasks: diff_level(&from_tasks, &to_tasks),
+            subtasks: diff_level(&from_subtasks, &to_subtasks),
+            required_counts
+        }))
+    }
+
+    /// Moves trainees from one version of the curriculum of a rotation to another, carrying their progress forward.
+    ///
+    /// Progress on subtasks in both versions is kept as it is, and progress on subtasks with a mapping is moved onto the subtask they map to,
+    /// unless the trainee already has progress there. Any other progress is kept but no longer counts towards the new version.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `rotation_id` - The ID of the rotation.
+    /// * `from_version` - The version to migrate trainees from.
+    /// * `to_version` - The version to migrate trainees to.
+    /// * `user_ids` - The trainees to migrate, or `None` to migrate everyone following the old version.
+    /// * `mappings` - The subtasks of the old version whose progress is moved onto subtasks of the new version.
+    /// * `dry_run` - Whether to only report what would be migrated.
+    pub async fn migrate(pool: &PgPool, rotation_id: i32, from_version: i32, to_version: i32, user_ids: Option<&[i32]>, mappings: &[SubtaskMapping], dry_run: bool) -> Result<Migration> {
+        let mut transaction = pool.begin().await?;
+
+        sqlx::query!("SELECT id FROM rotations WHERE id = $1 FOR UPDATE;", rotation_id)
+            .fetch_optional(&mut *transaction)
+            .await?;
+
+        let versions = sqlx::query!(
+            "SELECT id, version, subtask_ids FROM curriculum_versions WHERE rotation_id = $1 AND (version = $2 OR version = $3);",
+            rotation_id,
+            from_version,
+            to_version
+        )
+        .fetch_all(&mut *transaction)
+        .await?;
+
+        let (Some(from), Some(to)) = (
+            versions.iter().find(|version| version.version == from_version),
+            versions.iter().find(|version| version.version == to_version)
+        ) else {
+            return Ok(Migration::VersionNotFound);
+        };
+
+        // Progress can only be moved onto subtasks that are still in the rotation.
+        let to_subtask_ids = sqlx::query!(
+            "SELECT id FROM subtasks WHERE rotation_id = $1 AND id = ANY($2);",
+            rotation_id,
+            &to.subtask_ids
+        )
+        .fetch_all(&mut *transaction)
+        .await?
+        .into_iter()
+        .map(|row| row.id)
+        .collect::<HashSet<_>>();
+
+        let mut targets = HashMap::with_capacity(mappings.len());
+        for mapping in mappings {
+            if !from.subtask_ids.contains(&mapping.from) || !to_subtask_ids.contains(&mapping.to) || targets.insert(mapping.from, mapping.to).is_some() {
+                return Ok(Migration::InvalidMapping);
+            }
+        }
+
+        let user_ids = sqlx::query!(
+            r#"
+            SELECT user_id FROM curriculum_enrollments
+            WHERE version_id = $1 AND ($2::INT[] IS NULL OR user_id = ANY($2))
+            ORDER BY user_id
+            FOR UPDATE;
+            "#,
+            from.id,
+            user_ids as Option<&[i32]>
+        )
+        .fetch_all(&mut *transaction)
+        .await?
+        .into_iter()
+        .map(|row| row.user_id)
+        .collect::<Vec<_>>();
+
+        let user_tasks = sqlx::query!(
+            "SELECT id, user_id, subtask_id FROM user_tasks WHERE rotation_id = $1 AND user_id = ANY($2) ORDER BY id FOR UPDATE;",
+            rotation_id,
+            &user_ids
+        )
+        .fetch_all(&mut *transaction)
+        .await?;
+
+        // The subtasks of the new version each trainee has progress on, starting with the progress that stays where it is.
+        let mut occupied = user_tasks
+            .iter()
+            .filter(|user_task| to.subtask_ids.contains(&user_task.subtask_id) && !targets.contains_key(&user_task.subtask_id))
+            .map(|user_task| (user_task.user_id, user_task.subtask_id))
+            .collect::<HashSet<_>>();
+
+        let mut remapped_ids = Vec::new();
+        let mut remapped_subtask_ids = Vec::new();
+        let mut unmapped_tasks = 0;
+
+        for user_task in user_tasks.iter() {
+            match targets.get(&user_task.subtask_id) {
+                Some(target) if occupied.insert((user_task.user_id, *target)) => {
+                    remapped_ids.push(user_task.id);
+                    remapped_subtask_ids.push(*target);
+                },
+                Some(_) => unmapped_tasks += 1,
+                None if !to.subtask_ids.contains(&user_task.subtask_id) => unmapped_tasks += 1,
+                None => {}
+            }
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE user_tasks SET subtask_id = mapped.subtask_id
+            FROM UNNEST($1::INT[], $2::INT[]) AS mapped(id, subtask_id)
+            WHERE user_tasks.id = mapped.id;
+            "#,
+            &remapped_ids,
+            &remapped_subtask_ids
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query!(
+            "UPDATE curriculum_enrollments SET version_id = $1, enrolled_at = NOW() WHERE rotation_id = $2 AND user_id = ANY($3);",
+            to.id,
+            rotation_id,
+            &user_ids
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query!("UPDATE rotations SET last_updated = NOW() WHERE id = $1;", rotation_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        match dry_run {
+            true => transaction.rollback().await?,
+            false => transaction.commit().await?
+        }
+
+        Ok(Migration::Applied(MigrationSummary {
+            from_version,
+            to_version,
+            user_ids,
+            remapped_tasks: remapped_ids.len(),
+            unmapped_tasks,
+            dry_run
+        }))
+    }
+}
@@ -0,0 +1,163 @@
+use super::{prelude::*, user_task::UserTask};
+use crate::utilities::parsable::Comment;
+use chrono::NaiveDate;
+use sqlx::{Postgres, Transaction};
+
+/// A dated encounter a trainee has logged towards a subtask, such as a single procedure they performed.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Encounter {
+    id: i32,
+    user_task_id: i32,
+    encountered_on: NaiveDate,
+    comment: Comment,
+    created_at: DateTime<Utc>
+}
+
+/// Recalculates the completion of a user task from its logged encounters, if its subtask requires more than one encounter.
+///
+/// # Returns
+///
+/// Whether the user task is now completed.
+async fn sync_completion(transaction: &mut Transaction<'_, Postgres>, user_task_id: i32, user_id: i32) -> Result<bool> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            user_tasks.is_completed, user_tasks.comment, subtasks.required_count,
+            (SELECT COUNT(*) FROM user_task_encounters WHERE user_task_id = user_tasks.id) AS "encounter_count!"
+        FROM user_tasks
+        JOIN subtasks ON subtasks.id = user_tasks.subtask_id
+        WHERE user_tasks.id = $1
+        FOR UPDATE OF user_tasks;
+        "#,
+        user_task_id
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+
+    if row.required_count == 1 {
+        return Ok(row.is_completed);
+    }
+
+    let is_completed = row.encounter_count >= row.required_count as i64;
+    UserTask::set_progress(transaction, user_task_id, is_completed, row.comment.as_str(), Some(user_id)).await?;
+
+    Ok(is_completed)
+}
+
+impl Encounter {
+    /// Logs an encounter against one of a trainee's own user tasks.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `user_id` - The ID of the trainee logging the encounter.
+    /// * `user_task_id` - The ID of the user task to log the encounter against.
+    /// * `encountered_on` - The date of the encounter.
+    /// * `comment` - A comment about the encounter.
+    ///
+    /// # Returns
+    ///
+    /// The logged encounter and whether the user task is now completed, or `None` if the trainee has no such user task.
+    pub async fn insert(pool: &PgPool, user_id: i32, user_task_id: i32, encountered_on: NaiveDate, comment: &Comment) -> Result<Option<(Self, bool)>> {
+        let mut transaction = pool.begin().await?;
+
+        let encounter = sqlx::query_as!(
+            Encounter,
+            r#"
+            INSERT INTO user_task_encounters (user_task_id, encountered_on, comment)
+            SELECT id, $3, $4 FROM user_tasks WHERE id = $1 AND user_id = $2
+            RETURNING id, user_task_id, encountered_on, comment, created_at;
+            "#,
+            user_task_id,
+            user_id,
+            encountered_on,
+            comment.as_str()
+        )
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        let encounter = match encounter {
+            Some(encounter) => encounter,
+            None => return Ok(None)
+        };
+
+        let is_completed = sync_completion(&mut transaction, user_task_id, user_id).await?;
+        transaction.commit().await?;
+
+        Ok(Some((encounter, is_completed)))
+    }
+
+    /// Fetches the encounters logged against a user task, most recent first.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `user_task_id` - The ID of the user task.
+    /// * `user_id` - The ID of the trainee the user task must belong to, or `None` to allow any trainee.
+    ///
+    /// # Returns
+    ///
+    /// The encounters, or `None` if there is no such user task.
+    pub async fn fetch_all(pool: &PgPool, user_task_id: i32, user_id: Option<i32>) -> Result<Option<Box<[Self]>>> {
+        let exists = sqlx::query!(
+            r#"SELECT EXISTS(SELECT 1 FROM user_tasks WHERE id = $1 AND ($2::INT IS NULL OR user_id = $2)) AS "exists!";"#,
+            user_task_id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?
+        .exists;
+
+        if !exists {
+            return Ok(None);
+        }
+
+        let encounters = sqlx::query_as!(
+            Encounter,
+            r#"
+            SELECT id, user_task_id, encountered_on, comment, created_at
+            FROM user_task_encounters
+            WHERE user_task_id = $1
+            ORDER BY encountered_on DESC, id DESC;
+            "#,
+            user_task_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(Some(encounters.into_boxed_slice()))
+    }
+
+    /// Deletes one of a trainee's own encounters.
+    ///
+    /// # Returns
+    ///
+    /// Whether the user task the encounter was logged against is still completed, or `None` if the trainee has no such encounter.
+    pub async fn delete(pool: &PgPool, user_id: i32, id: i32) -> Result<Option<bool>> {
+        let mut transaction = pool.begin().await?;
+
+        let row = sqlx::query!(
+            r#"
+            DELETE FROM user_task_encounters
+            USING user_tasks
+            WHERE user_task_encounters.id = $1 AND user_tasks.id = user_task_encounters.user_task_id AND user_tasks.user_id = $2
+            RETURNING user_task_encounters.user_task_id;
+            "#,
+            id,
+            user_id
+        )
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        let user_task_id = match row {
+            Some(row) => row.user_task_id,
+            None => return Ok(None)
+        };
+
+        let is_completed = sync_completion(&mut transaction, user_task_id, user_id).await?;
+        transaction.commit().await?;
+
+        Ok(Some(is_completed))
+    }
+}
@@ -0,0 +1,54 @@
+use super::{entry_structure::Revised, prelude::*};
+use crate::utilities::{parsable::{EntryTitle, LearningObjective, ReferenceUrl}, rich_text::RichText};
+
+/// The maximum number of learning objectives an entry can have.
+const MAX_LEARNING_OBJECTIVES: usize = 25;
+
+/// The maximum number of reference links an entry can have.
+const MAX_REFERENCE_LINKS: usize = 25;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReferenceLink {
+    pub title: EntryTitle,
+    pub url: ReferenceUrl
+}
+
+/// The rich content of a supertask, task or subtask.
+///
+/// Content is kept out of the entry structure and fetched separately for a single entry, since it can be much larger than the entry itself.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryContent {
+    #[serde(default)]
+    pub description: RichText,
+
+    #[serde(default)]
+    pub learning_objectives: Vec<LearningObjective>,
+
+    #[serde(default)]
+    pub reference_links: Vec<ReferenceLink>
+}
+
+/// The content of an entry, along with the revision of the entry it belongs to.
+#[derive(Serialize, Debug)]
+pub struct EntryContentRevision {
+    #[serde(skip)]
+    pub revision: i32,
+
+    #[serde(flatten)]
+    pub content: EntryContent
+}
+
+impl Revised for EntryContentRevision {
+    #[inline(always)]
+    fn revision(&self) -> i32 {
+        self.revision
+    }
+}
+
+impl EntryContent {
+    /// Checks that the content does not have too many learning objectives or reference links.
+    pub fn is_within_limits(&self) -> bool {
+        self.learning_objectives.len() <= MAX_LEARNING_OBJECTIVES && self.reference_links.len() <= MAX_REFERENCE_LINKS
+    }
+}
@@ -1,31 +1,47 @@
-use super::prelude::*;
-use crate::utilities::parsable::EntryTitle;
+use super::{curriculum::CurriculumSupertask, entry_content::{EntryContent, EntryContentRevision}, prelude::*, user_task::UserTask};
+use crate::utilities::{parsable::{EntryTitle, LearningObjective}, rich_text::RichText};
 use std::collections::HashMap;
+use sqlx::{PgConnection, PgExecutor, Postgres, Transaction};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Supertask {
     id: i32,
     title: EntryTitle,
     rotation_id: i32,
+    position: i32,
+
+    /// Incremented whenever the entry is changed, and used as its entity tag.
+    /// Snapshots of published curricula from before entries had revisions do not include one.
+    #[serde(default)]
+    revision: i32
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Task {
     id: i32,
     supertask_id: i32,
     title: EntryTitle,
-    rotation_id: i32
+    rotation_id: i32,
+    position: i32,
+
+    #[serde(default)]
+    revision: i32
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Subtask {
     id: i32,
     task_id: i32,
     title: EntryTitle,
-    rotation_id: i32
+    rotation_id: i32,
+    position: i32,
+    required_count: i32,
+
+    #[serde(default)]
+    revision: i32
 }
 
 /// Represents a level of entries, with an entry and its children.
@@ -34,81 +50,500 @@ pub struct Subtask {
 /// 
 /// * `T` - The type of the entry.
 /// * `U` - The type of the children.
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct EntryLevel<T: Sized, U: Sized> {
     entry: T,
     children: Box<[U]>
 }
 
-type EntryHierarchy = EntryLevel<Supertask, EntryLevel<Task, Subtask>>;
+impl<T, U> EntryLevel<T, U> {
+    #[inline(always)]
+    pub fn entry(&self) -> &T {
+        &self.entry
+    }
+
+    #[inline(always)]
+    pub fn children(&self) -> &[U] {
+        &self.children
+    }
+}
+
+pub type EntryHierarchy = EntryLevel<Supertask, EntryLevel<Task, Subtask>>;
 
 /// Represents the hierarchal structure of entries, with supertasks at the top level, tasks at the second level, and subtasks at the third level.
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct EntryStructure(Vec<EntryHierarchy>);
 
+/// Maps the IDs of copied entries to the IDs of their copies, for each level of the entry structure.
+#[derive(Serialize, Debug, Default)]
+pub struct EntryIdMapping {
+    pub supertasks: HashMap<i32, i32>,
+    pub tasks: HashMap<i32, i32>,
+    pub subtasks: HashMap<i32, i32>
+}
+
+/// The levels of the entry structure.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum EntryKind {
+    Supertask,
+    Task,
+    Subtask
+}
+
+impl From<String> for EntryKind {
+    fn from(value: String) -> Self {
+        // Kinds are only ever produced by queries, so anything else is unreachable.
+        match value.as_str() {
+            "supertask" => Self::Supertask,
+            "task" => Self::Task,
+            _ => Self::Subtask
+        }
+    }
+}
+
+/// A deleted entry in the trash of a rotation, which is the top of the subtree that was deleted with it.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedEntry {
+    kind: EntryKind,
+    id: i32,
+    parent_id: Option<i32>,
+    title: EntryTitle,
+    deleted_at: DateTime<Utc>,
+
+    /// The number of subtasks that were deleted along with the entry, including itself if it is a subtask.
+    subtask_count: i64,
+
+    /// The number of user tasks on those subtasks, which come back when the entry is restored.
+    user_task_count: i64
+}
+
+/// The outcome of restoring a deleted entry.
+pub enum Restoration {
+    Restored,
+
+    /// The entry does not exist or has not been deleted.
+    NotFound,
+
+    /// The parent of the entry is still deleted, so it has to be restored first.
+    ParentDeleted
+}
+
+/// Something that is at a revision, which changes whenever it does.
+pub trait Revised {
+    fn revision(&self) -> i32;
+}
+
+/// The outcome of changing an entry on the condition that it is still at one of the revisions the change was made against.
+///
+/// # Type Parameters
+///
+/// * `T` - The state of the entry that was changed.
+pub enum Conditional<T> {
+    /// The change was made, holding the new state of the entry.
+    Applied(T),
+
+    /// The entry does not exist or has been deleted.
+    NotFound,
+
+    /// The entry has been changed since, holding its current state.
+    Modified(T),
+
+    /// The change cannot be made to the entry in its current state.
+    Conflict
+}
+
+/// Checks whether any of the given subtasks is in a published curriculum version of a rotation.
+/// The rotation is held until the transaction ends, so that no version can be published in the meantime.
+async fn is_published(transaction: &mut Transaction<'_, Postgres>, rotation_id: i32, subtask_ids: &[i32]) -> Result<bool> {
+    sqlx::query!("SELECT id FROM rotations WHERE id = $1 FOR SHARE;", rotation_id)
+        .fetch_optional(&mut **transaction)
+        .await?;
+
+    let row = sqlx::query!(
+        r#"SELECT EXISTS (SELECT 1 FROM curriculum_versions WHERE rotation_id = $1 AND subtask_ids && $2) AS "is_published!";"#,
+        rotation_id,
+        subtask_ids
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+
+    Ok(row.is_published)
+}
+
+/// Deletes every live task and subtask of a rotation whose parent was deleted in the current transaction, so that they share its deletion time.
+async fn delete_descendants(transaction: &mut Transaction<'_, Postgres>, rotation_id: i32) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE tasks SET deleted_at = NOW()
+        WHERE rotation_id = $1 AND deleted_at IS NULL AND supertask_id IN (SELECT id FROM supertasks WHERE rotation_id = $1 AND deleted_at = NOW());
+        "#,
+        rotation_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE subtasks SET deleted_at = NOW()
+        WHERE rotation_id = $1 AND deleted_at IS NULL AND task_id IN (SELECT id FROM tasks WHERE rotation_id = $1 AND deleted_at = NOW());
+        "#,
+        rotation_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    Ok(())
+}
+
+/// Restores every task and subtask of a rotation that was deleted at the same time as a parent that is live again.
+async fn restore_descendants(transaction: &mut Transaction<'_, Postgres>, rotation_id: i32, deleted_at: DateTime<Utc>) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE tasks SET deleted_at = NULL
+        WHERE rotation_id = $1 AND deleted_at = $2 AND supertask_id IN (SELECT id FROM supertasks WHERE rotation_id = $1 AND deleted_at IS NULL);
+        "#,
+        rotation_id,
+        deleted_at
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE subtasks SET deleted_at = NULL
+        WHERE rotation_id = $1 AND deleted_at = $2 AND task_id IN (SELECT id FROM tasks WHERE rotation_id = $1 AND deleted_at IS NULL);
+        "#,
+        rotation_id,
+        deleted_at
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    Ok(())
+}
+
 macro_rules! entity_operations {
-    ($struct_name:ident, $table_name:literal) => {
-        pub async fn fetch_all(pool: &PgPool, rotation_id: i32) -> Result<Box<[Self]>> {
+    ($struct_name:ident, $table_name:literal, $parent_column:literal, $columns:literal) => {
+        #[inline(always)]
+        pub fn id(&self) -> i32 {
+            self.id
+        }
+
+        #[inline(always)]
+        pub fn title(&self) -> &EntryTitle {
+            &self.title
+        }
+
+        pub async fn fetch_all(executor: impl PgExecutor<'_>, rotation_id: i32) -> Result<Box<[Self]>> {
             let records = sqlx::query_as!(
                 $struct_name,
-                "SELECT * FROM " + $table_name + " WHERE rotation_id = $1 ORDER BY id;",
+                "SELECT " + $columns + " FROM " + $table_name + " WHERE rotation_id = $1 AND deleted_at IS NULL ORDER BY position, id;",
                 rotation_id
             )
-            .fetch_all(pool)
+            .fetch_all(executor)
             .await?;
 
             Ok(records.into_boxed_slice())
         }
 
-        pub async fn update_title(pool: &PgPool, id: i32, title: &str) -> Result<()> {
+        /// Fetches the ID of the rotation an entry belongs to.
+        ///
+        /// # Returns
+        ///
+        /// The ID of the rotation, or `None` if the entry does not exist or has been deleted.
+        pub async fn fetch_rotation_id(pool: &PgPool, id: i32) -> Result<Option<i32>> {
+            let row = sqlx::query!(
+                "SELECT rotation_id FROM " + $table_name + " WHERE id = $1 AND deleted_at IS NULL;",
+                id
+            )
+            .fetch_optional(pool)
+            .await?;
+
+            Ok(row.map(|row| row.rotation_id))
+        }
+
+        /// Fetches a live entry.
+        ///
+        /// # Returns
+        ///
+        /// The entry, or `None` if it does not exist or has been deleted.
+        pub async fn fetch_by_id(pool: &PgPool, id: i32) -> Result<Option<Self>> {
+            let entry = sqlx::query_as!(
+                $struct_name,
+                "SELECT " + $columns + " FROM " + $table_name + " WHERE id = $1 AND deleted_at IS NULL;",
+                id
+            )
+            .fetch_optional(pool)
+            .await?;
+
+            Ok(entry)
+        }
+
+        /// Finds out why a change made on the condition of the revision of an entry was not applied.
+        async fn unapplied(pool: &PgPool, id: i32) -> Result<Conditional<Self>> {
+            Ok(match Self::fetch_by_id(pool, id).await? {
+                Some(entry) => Conditional::Modified(entry),
+                None => Conditional::NotFound
+            })
+        }
+
+        /// Renames an entry if it is still at one of the revisions the change was made against.
+        ///
+        /// # Parameters
+        ///
+        /// * `pool` - The Postgres connection pool to use for the operation.
+        /// * `id` - The ID of the entry.
+        /// * `title` - The new title of the entry.
+        /// * `expected_revisions` - The revisions the entry may be at, or `None` if it may be at any revision.
+        pub async fn update_title(pool: &PgPool, id: i32, title: &str, expected_revisions: Option<&[i32]>) -> Result<Conditional<Self>> {
             let mut transaction = pool.begin().await?;
 
-            sqlx::query!(
-                "UPDATE " + $table_name + " SET title = $1 WHERE id = $2;",
+            let entry = sqlx::query_as!(
+                $struct_name,
+                "UPDATE " + $table_name + " SET title = $1, revision = revision + 1 WHERE id = $2 AND deleted_at IS NULL AND ($3::INT[] IS NULL OR revision = ANY($3)) RETURNING " + $columns + ";",
                 title,
+                id,
+                expected_revisions
+            )
+            .fetch_optional(&mut *transaction)
+            .await?;
+
+            let Some(entry) = entry else {
+                return Self::unapplied(pool, id).await;
+            };
+
+            sqlx::query!(
+                "UPDATE rotations SET last_updated = NOW() WHERE id = $1;",
+                entry.rotation_id
+            )
+            .execute(&mut *transaction)
+            .await?;
+
+            transaction.commit().await?;
+
+            Ok(Conditional::Applied(entry))
+        }
+
+        /// Fetches the rich content of an entry.
+        ///
+        /// # Returns
+        ///
+        /// The content of the entry, or `None` if the entry does not exist or has been deleted.
+        pub async fn fetch_content(pool: &PgPool, id: i32) -> Result<Option<EntryContentRevision>> {
+            let row = sqlx::query!(
+                "SELECT description, learning_objectives, reference_links, revision FROM " + $table_name + " WHERE id = $1 AND deleted_at IS NULL;",
                 id
             )
+            .fetch_optional(pool)
+            .await?;
+
+            let row = match row {
+                Some(row) => row,
+                None => return Ok(None)
+            };
+
+            Ok(Some(EntryContentRevision {
+                revision: row.revision,
+                content: EntryContent {
+                    description: RichText::from(row.description),
+                    learning_objectives: row.learning_objectives.into_iter().map(LearningObjective::from).collect(),
+                    reference_links: serde_json::from_value(row.reference_links)?
+                }
+            }))
+        }
+
+        /// Replaces the rich content of an entry if it is still at one of the revisions the change was made against.
+        ///
+        /// # Parameters
+        ///
+        /// * `pool` - The Postgres connection pool to use for the operation.
+        /// * `id` - The ID of the entry.
+        /// * `content` - The new content of the entry.
+        /// * `expected_revisions` - The revisions the entry may be at, or `None` if it may be at any revision.
+        pub async fn update_content(pool: &PgPool, id: i32, content: &EntryContent, expected_revisions: Option<&[i32]>) -> Result<Conditional<EntryContentRevision>> {
+            let learning_objectives = content.learning_objectives
+                .iter()
+                .map(|objective| objective.as_str().to_string())
+                .collect::<Vec<_>>();
+
+            let mut transaction = pool.begin().await?;
+
+            let row = sqlx::query!(
+                "UPDATE " + $table_name + " SET description = $1, learning_objectives = $2, reference_links = $3, revision = revision + 1 WHERE id = $4 AND deleted_at IS NULL AND ($5::INT[] IS NULL OR revision = ANY($5)) RETURNING revision, rotation_id;",
+                content.description.as_str(),
+                &learning_objectives,
+                serde_json::to_value(&content.reference_links)?,
+                id,
+                expected_revisions
+            )
+            .fetch_optional(&mut *transaction)
+            .await?;
+
+            let Some(row) = row else {
+                if Self::fetch_by_id(pool, id).await?.is_none() {
+                    return Ok(Conditional::NotFound);
+                }
+
+                return Ok(match Self::fetch_content(pool, id).await? {
+                    Some(current) => Conditional::Modified(current),
+                    None => Conditional::NotFound
+                });
+            };
+
+            sqlx::query!(
+                "UPDATE rotations SET last_updated = NOW() WHERE id = $1;",
+                row.rotation_id
+            )
+            .execute(&mut *transaction)
+            .await?;
+
+            transaction.commit().await?;
+
+            Ok(Conditional::Applied(EntryContentRevision {
+                revision: row.revision,
+                content: content.clone()
+            }))
+        }
+
+        /// Reorders an entry and all of its siblings, giving each of them a new revision.
+        ///
+        /// # Parameters
+        ///
+        /// * `pool` - The Postgres connection pool to use for the operation.
+        /// * `ordered_ids` - The IDs of every entry sharing the same parent, in their new order.
+        ///
+        /// # Returns
+        ///
+        /// `true` if the entries were reordered, `false` if the IDs are not exactly the siblings of a single parent.
+        pub async fn reorder(pool: &PgPool, ordered_ids: &[i32]) -> Result<bool> {
+            let first_id = match ordered_ids.first() {
+                Some(id) => *id,
+                None => return Ok(false)
+            };
+
+            let mut transaction = pool.begin().await?;
+
+            let sibling_ids = sqlx::query!(
+                "SELECT id FROM " + $table_name + " WHERE " + $parent_column + " = (SELECT " + $parent_column + " FROM " + $table_name + " WHERE id = $1) AND deleted_at IS NULL ORDER BY id FOR UPDATE;",
+                first_id
+            )
+            .fetch_all(&mut *transaction)
+            .await?
+            .into_iter()
+            .map(|row| row.id)
+            .collect::<Vec<_>>();
+
+            let mut sorted_ids = ordered_ids.to_vec();
+            sorted_ids.sort_unstable();
+
+            if sorted_ids != sibling_ids {
+                return Ok(false);
+            }
+
+            sqlx::query!(
+                "UPDATE " + $table_name + " SET position = ordered.position::INT, revision = revision + 1 FROM UNNEST($1::INT[]) WITH ORDINALITY AS ordered(id, position) WHERE " + $table_name + ".id = ordered.id;",
+                ordered_ids
+            )
             .execute(&mut *transaction)
             .await?;
 
             sqlx::query!(
                 "UPDATE rotations SET last_updated = NOW() WHERE id = (SELECT rotation_id FROM " + $table_name + " WHERE id = $1);",
-                id
+                first_id
             )
             .execute(&mut *transaction)
             .await?;
 
             transaction.commit().await?;
 
-            Ok(())
+            Ok(true)
         }
 
-        pub async fn delete(pool: &PgPool, id: i32) -> Result<()> {
+        /// Moves an entry and its live descendants into the trash of its rotation.
+        ///
+        /// Every trainee's progress on them is kept, so that it comes back if the entry is restored.
+        /// The entry is only deleted if it is still at one of the `expected_revisions`, or at any revision if they are `None`.
+        pub async fn delete(pool: &PgPool, id: i32, expected_revisions: Option<&[i32]>) -> Result<Conditional<Self>> {
             let mut transaction = pool.begin().await?;
 
+            let entry = sqlx::query_as!(
+                $struct_name,
+                "UPDATE " + $table_name + " SET deleted_at = NOW(), revision = revision + 1 WHERE id = $1 AND deleted_at IS NULL AND ($2::INT[] IS NULL OR revision = ANY($2)) RETURNING " + $columns + ";",
+                id,
+                expected_revisions
+            )
+            .fetch_optional(&mut *transaction)
+            .await?;
+
+            let Some(entry) = entry else {
+                return Self::unapplied(pool, id).await;
+            };
+
+            delete_descendants(&mut transaction, entry.rotation_id).await?;
+
             sqlx::query!(
-                "DELETE FROM " + $table_name + " WHERE id = $1;",
-                id
+                "UPDATE rotations SET last_updated = NOW() WHERE id = $1;",
+                entry.rotation_id
             )
             .execute(&mut *transaction)
             .await?;
 
+            transaction.commit().await?;
+
+            Ok(Conditional::Applied(entry))
+        }
+
+        /// Restores a deleted entry from the trash, along with the descendants that were deleted with it and the progress on them.
+        pub async fn restore(pool: &PgPool, id: i32) -> Result<Restoration> {
+            let mut transaction = pool.begin().await?;
+
+            let row = sqlx::query!(
+                "SELECT rotation_id, deleted_at FROM " + $table_name + " WHERE id = $1 FOR UPDATE;",
+                id
+            )
+            .fetch_optional(&mut *transaction)
+            .await?;
+
+            let Some((rotation_id, Some(deleted_at))) = row.map(|row| (row.rotation_id, row.deleted_at)) else {
+                return Ok(Restoration::NotFound);
+            };
+
+            if Self::is_parent_deleted(&mut transaction, id).await? {
+                return Ok(Restoration::ParentDeleted);
+            }
+
             sqlx::query!(
-                "UPDATE rotations SET last_updated = NOW() WHERE id = (SELECT rotation_id FROM " + $table_name + " WHERE id = $1);",
+                "UPDATE " + $table_name + " SET deleted_at = NULL, revision = revision + 1 WHERE id = $1;",
                 id
             )
             .execute(&mut *transaction)
             .await?;
 
+            restore_descendants(&mut transaction, rotation_id, deleted_at).await?;
+
+            sqlx::query!(
+                "UPDATE rotations SET last_updated = NOW() WHERE id = $1;",
+                rotation_id
+            )
+            .execute(&mut *transaction)
+            .await?;
+
             transaction.commit().await?;
 
-            Ok(())
+            Ok(Restoration::Restored)
         }
     };
 }
 
 impl Supertask {
-    entity_operations!(Supertask, "supertasks");
+    entity_operations!(Supertask, "supertasks", "rotation_id", "id, title, rotation_id, position, revision");
+
+    /// Supertasks belong directly to their rotation, so there is no parent to be deleted.
+    async fn is_parent_deleted(_transaction: &mut Transaction<'_, Postgres>, _id: i32) -> Result<bool> {
+        Ok(false)
+    }
 
     pub async fn insert_from(pool: &PgPool, title: &str, rotation_id: i32) -> Result<i32> {
         let mut transaction = pool.begin().await?;
@@ -139,7 +574,18 @@ impl Supertask {
 }
 
 impl Task {
-    entity_operations!(Task, "tasks");
+    entity_operations!(Task, "tasks", "supertask_id", "id, supertask_id, title, rotation_id, position, revision");
+
+    async fn is_parent_deleted(transaction: &mut Transaction<'_, Postgres>, id: i32) -> Result<bool> {
+        let row = sqlx::query!(
+            r#"SELECT supertasks.deleted_at IS NOT NULL AS "is_deleted!" FROM tasks JOIN supertasks ON supertasks.id = tasks.supertask_id WHERE tasks.id = $1;"#,
+            id
+        )
+        .fetch_one(&mut **transaction)
+        .await?;
+
+        Ok(row.is_deleted)
+    }
 
     pub async fn insert_from(pool: &PgPool, title: &str, rotation_id: i32, supertask_id: i32) -> Result<i32> {
         let mut transaction = pool.begin().await?;
@@ -170,10 +616,107 @@ impl Task {
 
         Ok(row.id)
     }
+
+    /// Moves a task and its subtasks under another supertask, which may be in a different rotation.
+    /// Every user's progress on the subtasks is kept and moved along with them.
+    /// A task cannot leave its rotation once any of its subtasks has been published, since trainees following that version still make progress on them there.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `id` - The ID of the task to move.
+    /// * `supertask_id` - The ID of the supertask to move the task under.
+    /// * `expected_revisions` - The revisions the task may be at, or `None` if it may be at any revision.
+    ///
+    /// # Returns
+    ///
+    /// The moved task, which is `NotFound` if either the task or the supertask does not exist, or `Conflict` if it cannot leave its rotation.
+    pub async fn move_to(pool: &PgPool, id: i32, supertask_id: i32, expected_revisions: Option<&[i32]>) -> Result<Conditional<Self>> {
+        let mut transaction = pool.begin().await?;
+
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                (SELECT rotation_id FROM tasks WHERE id = $1 AND deleted_at IS NULL AND ($3::INT[] IS NULL OR revision = ANY($3)) FOR UPDATE) AS from_rotation_id,
+                (SELECT rotation_id FROM supertasks WHERE id = $2 AND deleted_at IS NULL FOR SHARE) AS to_rotation_id;
+            "#,
+            id,
+            supertask_id,
+            expected_revisions
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        let Some(from_rotation_id) = row.from_rotation_id else {
+            return Self::unapplied(pool, id).await;
+        };
+
+        let Some(to_rotation_id) = row.to_rotation_id else {
+            return Ok(Conditional::NotFound);
+        };
+
+        if from_rotation_id != to_rotation_id {
+            let subtask_ids = sqlx::query!("SELECT id FROM subtasks WHERE task_id = $1;", id)
+                .fetch_all(&mut *transaction)
+                .await?
+                .into_iter()
+                .map(|row| row.id)
+                .collect::<Vec<_>>();
+
+            if is_published(&mut transaction, from_rotation_id, &subtask_ids).await? {
+                return Ok(Conditional::Conflict);
+            }
+        }
+
+        // A task moved to another supertask is placed after its new siblings.
+        // The new rotation cascades to its subtasks and their user tasks through their foreign keys.
+        let entry = sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE tasks
+            SET supertask_id = $2, rotation_id = $3, position = CASE WHEN supertask_id = $2 THEN position ELSE nextval('tasks_position_seq')::INT END, revision = revision + 1
+            WHERE id = $1
+            RETURNING id, supertask_id, title, rotation_id, position, revision;
+            "#,
+            id,
+            supertask_id,
+            to_rotation_id
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        sqlx::query!(
+            "UPDATE rotations SET last_updated = NOW() WHERE id = $1 OR id = $2;",
+            from_rotation_id,
+            to_rotation_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(Conditional::Applied(entry))
+    }
 }
 
 impl Subtask {
-    entity_operations!(Subtask, "subtasks");
+    entity_operations!(Subtask, "subtasks", "task_id", "id, task_id, title, rotation_id, position, required_count, revision");
+
+    async fn is_parent_deleted(transaction: &mut Transaction<'_, Postgres>, id: i32) -> Result<bool> {
+        let row = sqlx::query!(
+            r#"SELECT tasks.deleted_at IS NOT NULL AS "is_deleted!" FROM subtasks JOIN tasks ON tasks.id = subtasks.task_id WHERE subtasks.id = $1;"#,
+            id
+        )
+        .fetch_one(&mut **transaction)
+        .await?;
+
+        Ok(row.is_deleted)
+    }
+
+    #[inline(always)]
+    pub fn required_count(&self) -> i32 {
+        self.required_count
+    }
 
     pub async fn insert_from(pool: &PgPool, title: &str, rotation_id: i32, task_id: i32) -> Result<i32> {
         let mut transaction = pool.begin().await?;
@@ -205,21 +748,165 @@ impl Subtask {
         Ok(row.id)
     }
 
-    pub async fn exists(pool: &PgPool, id: i32) -> Result<bool> {
-        let exists = sqlx::query!(
-            "SELECT EXISTS(SELECT 1 FROM subtasks WHERE id = $1) AS exists;",
-            id
+    /// Sets how many encounters a trainee must log to complete a subtask.
+    /// The completion of every trainee's progress on the subtask is recalculated when more than one encounter is required.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `id` - The ID of the subtask.
+    /// * `required_count` - The number of encounters required, where `1` leaves completion to the trainee.
+    /// * `expected_revisions` - The revisions the subtask may be at, or `None` if it may be at any revision.
+    pub async fn update_required_count(pool: &PgPool, id: i32, required_count: i32, expected_revisions: Option<&[i32]>) -> Result<Conditional<Self>> {
+        let mut transaction = pool.begin().await?;
+
+        let entry = sqlx::query_as!(
+            Self,
+            "UPDATE subtasks SET required_count = $1, revision = revision + 1 WHERE id = $2 AND deleted_at IS NULL AND ($3::INT[] IS NULL OR revision = ANY($3)) RETURNING id, task_id, title, rotation_id, position, required_count, revision;",
+            required_count,
+            id,
+            expected_revisions
         )
-        .fetch_one(pool)
-        .await?
-        .exists;
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        let Some(entry) = entry else {
+            return Self::unapplied(pool, id).await;
+        };
+
+        if required_count > 1 {
+            let rows = sqlx::query!(
+                r#"
+                SELECT id, comment, (SELECT COUNT(*) FROM user_task_encounters WHERE user_task_id = user_tasks.id) >= $1 AS "is_completed!"
+                FROM user_tasks
+                WHERE subtask_id = $2
+                FOR UPDATE;
+                "#,
+                required_count as i64,
+                id
+            )
+            .fetch_all(&mut *transaction)
+            .await?;
+
+            for row in rows {
+                UserTask::set_progress(&mut transaction, row.id, row.is_completed, row.comment.as_str(), None).await?;
+            }
+        }
+
+        sqlx::query!(
+            "UPDATE rotations SET last_updated = NOW() WHERE id = $1;",
+            entry.rotation_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
 
-        Ok(exists.unwrap_or(false))
+        Ok(Conditional::Applied(entry))
+    }
+
+    /// Moves a subtask under another task, which may be in a different rotation.
+    /// Every user's progress on the subtask is kept and moved along with it.
+    /// A subtask cannot leave its rotation once it has been published, since trainees following that version still make progress on it there.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `id` - The ID of the subtask to move.
+    /// * `task_id` - The ID of the task to move the subtask under.
+    /// * `expected_revisions` - The revisions the subtask may be at, or `None` if it may be at any revision.
+    ///
+    /// # Returns
+    ///
+    /// The moved subtask, which is `NotFound` if either the subtask or the task does not exist, or `Conflict` if it cannot leave its rotation.
+    pub async fn move_to(pool: &PgPool, id: i32, task_id: i32, expected_revisions: Option<&[i32]>) -> Result<Conditional<Self>> {
+        let mut transaction = pool.begin().await?;
+
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                (SELECT rotation_id FROM subtasks WHERE id = $1 AND deleted_at IS NULL AND ($3::INT[] IS NULL OR revision = ANY($3)) FOR UPDATE) AS from_rotation_id,
+                (SELECT rotation_id FROM tasks WHERE id = $2 AND deleted_at IS NULL FOR SHARE) AS to_rotation_id;
+            "#,
+            id,
+            task_id,
+            expected_revisions
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        let Some(from_rotation_id) = row.from_rotation_id else {
+            return Self::unapplied(pool, id).await;
+        };
+
+        let Some(to_rotation_id) = row.to_rotation_id else {
+            return Ok(Conditional::NotFound);
+        };
+
+        if from_rotation_id != to_rotation_id && is_published(&mut transaction, from_rotation_id, &[id]).await? {
+            return Ok(Conditional::Conflict);
+        }
+
+        // A subtask moved to another task is placed after its new siblings.
+        // The new rotation cascades to its user tasks through their foreign key.
+        let entry = sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE subtasks
+            SET task_id = $2, rotation_id = $3, position = CASE WHEN task_id = $2 THEN position ELSE nextval('subtasks_position_seq')::INT END, revision = revision + 1
+            WHERE id = $1
+            RETURNING id, task_id, title, rotation_id, position, required_count, revision;
+            "#,
+            id,
+            task_id,
+            to_rotation_id
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        sqlx::query!(
+            "UPDATE rotations SET last_updated = NOW() WHERE id = $1 OR id = $2;",
+            from_rotation_id,
+            to_rotation_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(Conditional::Applied(entry))
+    }
+}
+
+impl Revised for Supertask {
+    #[inline(always)]
+    fn revision(&self) -> i32 {
+        self.revision
+    }
+}
+
+impl Revised for Task {
+    #[inline(always)]
+    fn revision(&self) -> i32 {
+        self.revision
+    }
+}
+
+impl Revised for Subtask {
+    #[inline(always)]
+    fn revision(&self) -> i32 {
+        self.revision
     }
 }
 
 impl EntryStructure {
+    #[inline(always)]
+    pub fn hierarchies(&self) -> &[EntryHierarchy] {
+        &self.0
+    }
+
     /// Builds an entry structure from an unordered collection of supertasks, tasks, and subtasks.
+    /// Siblings are ordered by their position, falling back to their creation order.
     /// 
     /// # Parameters
     /// 
@@ -231,39 +918,49 @@ impl EntryStructure {
     /// 
     /// The entry structure.
     pub fn build(supertasks: &[Supertask], tasks: &[Task], subtasks: &[Subtask]) -> Result<Self> {
+        let mut supertasks = supertasks.to_vec();
+        supertasks.sort_by_key(|supertask| (supertask.position, supertask.id));
+
+        let mut tasks = tasks.to_vec();
+        tasks.sort_by_key(|task| (task.position, task.id));
+
+        let mut subtasks = subtasks.to_vec();
+        subtasks.sort_by_key(|subtask| (subtask.position, subtask.id));
+
         let mut task_map: HashMap<i32, Vec<Subtask>> = HashMap::with_capacity(subtasks.len());
-        for subtask in subtasks.iter() {
+        for subtask in subtasks.into_iter() {
             task_map
                 .entry(subtask.task_id)
                 .or_default()
-                .push(subtask.to_owned());
+                .push(subtask);
         }
 
         let mut supertask_map: HashMap<i32, Vec<EntryLevel<Task, Subtask>>> = HashMap::with_capacity(tasks.len());
-        for task in tasks.iter() {
+        for task in tasks.into_iter() {
             let subtasks = task_map
                 .remove(&task.id)
                 .unwrap_or_default();
 
+            let supertask_id = task.supertask_id;
             let entry_level = EntryLevel {
-                entry: task.to_owned(),
+                entry: task,
                 children: subtasks.into_boxed_slice()
             };
 
             supertask_map
-                .entry(task.supertask_id)
+                .entry(supertask_id)
                 .or_default()
                 .push(entry_level);
         }
 
         let mut entry_structure = Vec::with_capacity(supertasks.len());
-        for supertask in supertasks.iter() {
+        for supertask in supertasks.into_iter() {
             let entry_levels = supertask_map
                 .remove(&supertask.id)
                 .unwrap_or_default();
 
             let entry_hierarchy = EntryHierarchy {
-                entry: supertask.to_owned(),
+                entry: supertask,
                 children: entry_levels.into_boxed_slice()
             };
 
@@ -274,11 +971,283 @@ impl EntryStructure {
     }
 
     pub async fn fetch(pool: &PgPool, rotation_id: i32) -> Result<Self> {
-        let supertasks = Supertask::fetch_all(pool, rotation_id).await?;
-        let tasks = Task::fetch_all(pool, rotation_id).await?;
-        let subtasks = Subtask::fetch_all(pool, rotation_id).await?;
+        Self::fetch_with(&mut *pool.acquire().await?, rotation_id).await
+    }
+
+    /// Fetches the entry structure of a rotation on a connection, such as one in a transaction that has to see the same entries.
+    pub async fn fetch_with(connection: &mut PgConnection, rotation_id: i32) -> Result<Self> {
+        let supertasks = Supertask::fetch_all(&mut *connection, rotation_id).await?;
+        let tasks = Task::fetch_all(&mut *connection, rotation_id).await?;
+        let subtasks = Subtask::fetch_all(&mut *connection, rotation_id).await?;
 
         let structure = Self::build(&supertasks, &tasks, &subtasks)?;
         Ok(structure)
     }
+
+    /// Fetches the trash of a rotation, with the most recently deleted entries first.
+    ///
+    /// Only the top of each deleted subtree is listed, since restoring it brings back the rest.
+    pub async fn fetch_trash(pool: &PgPool, rotation_id: i32) -> Result<Box<[TrashedEntry]>> {
+        let entries = sqlx::query_as!(
+            TrashedEntry,
+            r#"
+            SELECT
+                'supertask' AS "kind!", supertasks.id AS "id!", NULL::INT AS parent_id, supertasks.title AS "title!", supertasks.deleted_at AS "deleted_at!",
+                (SELECT COUNT(*) FROM subtasks JOIN tasks ON tasks.id = subtasks.task_id WHERE tasks.supertask_id = supertasks.id AND subtasks.deleted_at = supertasks.deleted_at) AS "subtask_count!",
+                (
+                    SELECT COUNT(*) FROM user_tasks JOIN subtasks ON subtasks.id = user_tasks.subtask_id JOIN tasks ON tasks.id = subtasks.task_id
+                    WHERE tasks.supertask_id = supertasks.id AND subtasks.deleted_at = supertasks.deleted_at
+                ) AS "user_task_count!"
+            FROM supertasks
+            WHERE supertasks.rotation_id = $1 AND supertasks.deleted_at IS NOT NULL
+            UNION ALL
+            SELECT
+                'task', tasks.id, tasks.supertask_id, tasks.title, tasks.deleted_at,
+                (SELECT COUNT(*) FROM subtasks WHERE subtasks.task_id = tasks.id AND subtasks.deleted_at = tasks.deleted_at),
+                (
+                    SELECT COUNT(*) FROM user_tasks JOIN subtasks ON subtasks.id = user_tasks.subtask_id
+                    WHERE subtasks.task_id = tasks.id AND subtasks.deleted_at = tasks.deleted_at
+                )
+            FROM tasks
+            JOIN supertasks ON supertasks.id = tasks.supertask_id
+            WHERE tasks.rotation_id = $1 AND tasks.deleted_at IS NOT NULL AND supertasks.deleted_at IS DISTINCT FROM tasks.deleted_at
+            UNION ALL
+            SELECT
+                'subtask', subtasks.id, subtasks.task_id, subtasks.title, subtasks.deleted_at, 1,
+                (SELECT COUNT(*) FROM user_tasks WHERE user_tasks.subtask_id = subtasks.id)
+            FROM subtasks
+            JOIN tasks ON tasks.id = subtasks.task_id
+            WHERE subtasks.rotation_id = $1 AND subtasks.deleted_at IS NOT NULL AND tasks.deleted_at IS DISTINCT FROM subtasks.deleted_at
+            ORDER BY 5 DESC, 2;
+            "#,
+            rotation_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(entries.into_boxed_slice())
+    }
+
+    /// Permanently deletes entries that have been in the trash since before a point in time, along with the progress on them.
+    ///
+    /// Subtasks included in a published version of a curriculum are kept for the trainees following it, and so are their ancestors.
+    ///
+    /// # Returns
+    ///
+    /// The number of entries that were deleted.
+    pub async fn purge_deleted(pool: &PgPool, deleted_before: DateTime<Utc>) -> Result<u64> {
+        let mut transaction = pool.begin().await?;
+
+        let subtasks = sqlx::query!(
+            r#"
+            DELETE FROM subtasks
+            WHERE deleted_at < $1 AND NOT EXISTS (SELECT 1 FROM curriculum_versions WHERE subtasks.id = ANY(curriculum_versions.subtask_ids));
+            "#,
+            deleted_before
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        let tasks = sqlx::query!(
+            "DELETE FROM tasks WHERE deleted_at < $1 AND NOT EXISTS (SELECT 1 FROM subtasks WHERE subtasks.task_id = tasks.id);",
+            deleted_before
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        let supertasks = sqlx::query!(
+            "DELETE FROM supertasks WHERE deleted_at < $1 AND NOT EXISTS (SELECT 1 FROM tasks WHERE tasks.supertask_id = supertasks.id);",
+            deleted_before
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(subtasks.rows_affected() + tasks.rows_affected() + supertasks.rows_affected())
+    }
+
+    /// Creates a tree of new entries in a rotation in a single transaction, in the order they are given.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `rotation_id` - The ID of the rotation to create the entries in.
+    /// * `supertasks` - The entries to create, whose titles must have been validated beforehand.
+    ///
+    /// # Returns
+    ///
+    /// The entry structure of only the entries that were created.
+    pub async fn insert_tree(pool: &PgPool, rotation_id: i32, supertasks: &[CurriculumSupertask]) -> Result<Self> {
+        let mut transaction = pool.begin().await?;
+
+        sqlx::query!(
+            "UPDATE rotations SET last_updated = NOW() WHERE id = $1;",
+            rotation_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        let mut entry_structure = Vec::with_capacity(supertasks.len());
+        for supertask in supertasks.iter() {
+            let supertask_entry = sqlx::query_as!(
+                Supertask,
+                "INSERT INTO supertasks (title, rotation_id) VALUES ($1, $2) RETURNING id, title, rotation_id, position, revision;",
+                supertask.title.as_str(),
+                rotation_id
+            )
+            .fetch_one(&mut *transaction)
+            .await?;
+
+            let mut entry_levels = Vec::with_capacity(supertask.tasks.len());
+            for task in supertask.tasks.iter() {
+                let task_entry = sqlx::query_as!(
+                    Task,
+                    "INSERT INTO tasks (title, rotation_id, supertask_id) VALUES ($1, $2, $3) RETURNING id, supertask_id, title, rotation_id, position, revision;",
+                    task.title.as_str(),
+                    rotation_id,
+                    supertask_entry.id
+                )
+                .fetch_one(&mut *transaction)
+                .await?;
+
+                let mut subtasks = Vec::with_capacity(task.subtasks.len());
+                for subtask in task.subtasks.iter() {
+                    let subtask_entry = sqlx::query_as!(
+                        Subtask,
+                        "INSERT INTO subtasks (title, rotation_id, task_id) VALUES ($1, $2, $3) RETURNING id, task_id, title, rotation_id, position, required_count, revision;",
+                        subtask.title.as_str(),
+                        rotation_id,
+                        task_entry.id
+                    )
+                    .fetch_one(&mut *transaction)
+                    .await?;
+
+                    subtasks.push(subtask_entry);
+                }
+
+                entry_levels.push(EntryLevel {
+                    entry: task_entry,
+                    children: subtasks.into_boxed_slice()
+                });
+            }
+
+            entry_structure.push(EntryHierarchy {
+                entry: supertask_entry,
+                children: entry_levels.into_boxed_slice()
+            });
+        }
+
+        transaction.commit().await?;
+
+        Ok(Self(entry_structure))
+    }
+
+    /// Copies every entry of a rotation into another rotation, preserving the hierarchy and the competencies subtasks are tagged with.
+    ///
+    /// # Parameters
+    ///
+    /// * `transaction` - The transaction to perform the copy in.
+    /// * `from_rotation_id` - The ID of the rotation to copy entries from.
+    /// * `to_rotation_id` - The ID of the rotation to copy entries into.
+    ///
+    /// # Returns
+    ///
+    /// The mapping from the IDs of the original entries to the IDs of their copies.
+    pub async fn copy(transaction: &mut Transaction<'_, Postgres>, from_rotation_id: i32, to_rotation_id: i32) -> Result<EntryIdMapping> {
+        let supertasks = sqlx::query!(
+            r#"
+            WITH mapping AS (
+                SELECT id AS old_id, nextval(pg_get_serial_sequence('supertasks', 'id'))::INT AS new_id, title, position, description, learning_objectives, reference_links
+                FROM supertasks
+                WHERE rotation_id = $1 AND deleted_at IS NULL
+            ), inserted AS (
+                INSERT INTO supertasks (id, title, rotation_id, position, description, learning_objectives, reference_links)
+                SELECT new_id, title, $2, position, description, learning_objectives, reference_links FROM mapping
+            )
+            SELECT old_id, new_id AS "new_id!" FROM mapping;
+            "#,
+            from_rotation_id,
+            to_rotation_id
+        )
+        .fetch_all(&mut **transaction)
+        .await?
+        .into_iter()
+        .map(|row| (row.old_id, row.new_id))
+        .collect::<HashMap<_, _>>();
+
+        let (old_supertask_ids, new_supertask_ids): (Vec<_>, Vec<_>) = supertasks.iter().unzip();
+        let tasks = sqlx::query!(
+            r#"
+            WITH parents AS (
+                SELECT * FROM UNNEST($3::INT[], $4::INT[]) AS parents(old_id, new_id)
+            ), mapping AS (
+                SELECT tasks.id AS old_id, nextval(pg_get_serial_sequence('tasks', 'id'))::INT AS new_id, parents.new_id AS parent_id, tasks.title, tasks.position, tasks.description, tasks.learning_objectives, tasks.reference_links
+                FROM tasks
+                JOIN parents ON parents.old_id = tasks.supertask_id
+                WHERE tasks.rotation_id = $1 AND tasks.deleted_at IS NULL
+            ), inserted AS (
+                INSERT INTO tasks (id, supertask_id, title, rotation_id, position, description, learning_objectives, reference_links)
+                SELECT new_id, parent_id, title, $2, position, description, learning_objectives, reference_links FROM mapping
+            )
+            SELECT old_id, new_id AS "new_id!" FROM mapping;
+            "#,
+            from_rotation_id,
+            to_rotation_id,
+            &old_supertask_ids as &[i32],
+            &new_supertask_ids as &[i32]
+        )
+        .fetch_all(&mut **transaction)
+        .await?
+        .into_iter()
+        .map(|row| (row.old_id, row.new_id))
+        .collect::<HashMap<_, _>>();
+
+        let (old_task_ids, new_task_ids): (Vec<_>, Vec<_>) = tasks.iter().unzip();
+        let subtasks = sqlx::query!(
+            r#"
+            WITH parents AS (
+                SELECT * FROM UNNEST($3::INT[], $4::INT[]) AS parents(old_id, new_id)
+            ), mapping AS (
+                SELECT subtasks.id AS old_id, nextval(pg_get_serial_sequence('subtasks', 'id'))::INT AS new_id, parents.new_id AS parent_id, subtasks.title, subtasks.position, subtasks.description, subtasks.learning_objectives, subtasks.reference_links, subtasks.required_count
+                FROM subtasks
+                JOIN parents ON parents.old_id = subtasks.task_id
+                WHERE subtasks.rotation_id = $1 AND subtasks.deleted_at IS NULL
+            ), inserted AS (
+                INSERT INTO subtasks (id, task_id, title, rotation_id, position, description, learning_objectives, reference_links, required_count)
+                SELECT new_id, parent_id, title, $2, position, description, learning_objectives, reference_links, required_count FROM mapping
+            )
+            SELECT old_id, new_id AS "new_id!" FROM mapping;
+            "#,
+            from_rotation_id,
+            to_rotation_id,
+            &old_task_ids as &[i32],
+            &new_task_ids as &[i32]
+        )
+        .fetch_all(&mut **transaction)
+        .await?
+        .into_iter()
+        .map(|row| (row.old_id, row.new_id))
+        .collect::<HashMap<_, _>>();
+
+        let (old_subtask_ids, new_subtask_ids): (Vec<_>, Vec<_>) = subtasks.iter().unzip();
+        sqlx::query!(
+            r#"
+            INSERT INTO subtask_competencies (subtask_id, competency_id)
+            SELECT copies.new_id, subtask_competencies.competency_id
+            FROM UNNEST($1::INT[], $2::INT[]) AS copies(old_id, new_id)
+            JOIN subtask_competencies ON subtask_competencies.subtask_id = copies.old_id;
+            "#,
+            &old_subtask_ids as &[i32],
+            &new_subtask_ids as &[i32]
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(EntryIdMapping {
+            supertasks,
+            tasks,
+            subtasks
+        })
+    }
 }
\ No newline at end of file
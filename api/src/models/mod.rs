@@ -2,5 +2,16 @@ mod prelude;
 pub mod user;
 pub mod client_user;
 pub mod entry_structure;
+pub mod entry_content;
+pub mod curriculum;
+pub mod competency;
 pub mod rotation;
-pub mod user_task;
\ No newline at end of file
+pub mod user_task;
+pub mod encounter;
+pub mod verification;
+pub mod supervisor;
+pub mod progress;
+pub mod case_log;
+pub mod progress_report;
+pub mod progress_export;
+pub mod curriculum_version;
\ No newline at end of file
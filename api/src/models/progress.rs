@@ -0,0 +1,359 @@
+use super::{curriculum_version::CurriculumVersion, entry_structure::{EntryStructure, Subtask, Supertask}, prelude::*, user_task::UserTask};
+use crate::utilities::parsable::{Name, Username};
+use std::collections::HashMap;
+
+/// The progress of a trainee on a single subtask.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SubtaskProgress {
+    subtask_id: i32,
+    is_completed: bool,
+    encounter_count: i64,
+    required_count: i32
+}
+
+/// The progress of a trainee on a task, as the percentage of its subtasks they have completed.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskProgress {
+    task_id: i32,
+    progress: f64,
+    completed_subtasks: usize,
+    total_subtasks: usize,
+    subtasks: Box<[SubtaskProgress]>
+}
+
+/// The progress of a trainee on a supertask, as the mean progress of its tasks.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SupertaskProgress {
+    supertask_id: i32,
+    progress: f64,
+    completed_subtasks: usize,
+    total_subtasks: usize,
+    tasks: Box<[TaskProgress]>
+}
+
+/// The progress of a trainee on a rotation, as the mean progress of its supertasks.
+///
+/// Percentages are between `0` and `100`, and any level without children is at `0`.
+/// This is the single definition of progress, so anything reporting it should compute it from here.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RotationProgress {
+    user_id: i32,
+    rotation_id: i32,
+    progress: f64,
+    completed_subtasks: usize,
+    total_subtasks: usize,
+    supertasks: Box<[SupertaskProgress]>
+}
+
+/// What to sort the rows of a cohort progress matrix by.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CohortSort {
+    #[default]
+    Name,
+    Progress
+}
+
+/// How to filter and sort the rows of a cohort progress matrix.
+///
+/// When a supertask is given, rows are filtered and sorted by the progress on that supertask rather than on the whole rotation.
+#[derive(Debug)]
+pub struct CohortFilter {
+    pub supertask_id: Option<i32>,
+    pub min_progress: f64,
+    pub max_progress: f64,
+    pub sort: CohortSort,
+    pub descending: bool
+}
+
+/// The progress of a trainee on a rotation and each of its supertasks, as a row of a cohort progress matrix.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CohortProgressRow {
+    user_id: i32,
+    username: Username,
+    name: Name,
+    progress: f64,
+    supertask_progress: Vec<f64>
+}
+
+/// The progress of every trainee on a rotation, with a column for each of its supertasks.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CohortProgress {
+    rotation_id: i32,
+    supertasks: Box<[Supertask]>,
+    rows: Box<[CohortProgressRow]>
+}
+
+/// The mean of a collection of percentages, which is `0` if it is empty.
+fn mean(percentages: impl ExactSizeIterator<Item = f64>) -> f64 {
+    let count = percentages.len();
+
+    match count {
+        0 => 0.0,
+        _ => percentages.sum::<f64>() / count as f64
+    }
+}
+
+impl TaskProgress {
+    #[inline(always)]
+    pub fn progress(&self) -> f64 {
+        self.progress
+    }
+}
+
+impl SupertaskProgress {
+    #[inline(always)]
+    pub fn progress(&self) -> f64 {
+        self.progress
+    }
+
+    /// The progress on each task of the supertask, in the same order as its tasks.
+    #[inline(always)]
+    pub fn tasks(&self) -> &[TaskProgress] {
+        &self.tasks
+    }
+}
+
+impl SubtaskProgress {
+    fn compute(subtask: &Subtask, user_tasks: &HashMap<i32, UserTask<Synced>>) -> Self {
+        let user_task = user_tasks.get(&subtask.id());
+
+        Self {
+            subtask_id: subtask.id(),
+            is_completed: user_task.map(|user_task| user_task.is_completed()).unwrap_or(false),
+            encounter_count: user_task.map(|user_task| user_task.encounter_count()).unwrap_or(0),
+            required_count: subtask.required_count()
+        }
+    }
+}
+
+impl RotationProgress {
+    #[inline(always)]
+    pub fn progress(&self) -> f64 {
+        self.progress
+    }
+
+    #[inline(always)]
+    pub fn completed_subtasks(&self) -> usize {
+        self.completed_subtasks
+    }
+
+    #[inline(always)]
+    pub fn total_subtasks(&self) -> usize {
+        self.total_subtasks
+    }
+
+    /// The progress on each supertask of the rotation, in the same order as its supertasks.
+    #[inline(always)]
+    pub fn supertasks(&self) -> &[SupertaskProgress] {
+        &self.supertasks
+    }
+
+    /// Computes the progress of a trainee on a rotation.
+    ///
+    /// # Parameters
+    ///
+    /// * `user_id` - The ID of the trainee.
+    /// * `rotation_id` - The ID of the rotation.
+    /// * `entries` - The entry structure of the rotation.
+    /// * `user_tasks` - The trainee's user tasks in the rotation, keyed by subtask ID.
+    pub fn compute(user_id: i32, rotation_id: i32, entries: &EntryStructure, user_tasks: &HashMap<i32, UserTask<Synced>>) -> Self {
+        let supertasks = entries
+            .hierarchies()
+            .iter()
+            .map(|supertask| {
+                let tasks = supertask
+                    .children()
+                    .iter()
+                    .map(|task| {
+                        let subtasks = task
+                            .children()
+                            .iter()
+                            .map(|subtask| SubtaskProgress::compute(subtask, user_tasks))
+                            .collect::<Box<[_]>>();
+
+                        let completed_subtasks = subtasks.iter().filter(|subtask| subtask.is_completed).count();
+                        let progress = match subtasks.len() {
+                            0 => 0.0,
+                            total => completed_subtasks as f64 / total as f64 * 100.0
+                        };
+
+                        TaskProgress {
+                            task_id: task.entry().id(),
+                            progress,
+                            completed_subtasks,
+                            total_subtasks: subtasks.len(),
+                            subtasks
+                        }
+                    })
+                    .collect::<Box<[_]>>();
+
+                SupertaskProgress {
+                    supertask_id: supertask.entry().id(),
+                    progress: mean(tasks.iter().map(|task| task.progress)),
+                    completed_subtasks: tasks.iter().map(|task| task.completed_subtasks).sum(),
+                    total_subtasks: tasks.iter().map(|task| task.total_subtasks).sum(),
+                    tasks
+                }
+            })
+            .collect::<Box<[_]>>();
+
+        Self {
+            user_id,
+            rotation_id,
+            progress: mean(supertasks.iter().map(|supertask| supertask.progress)),
+            completed_subtasks: supertasks.iter().map(|supertask| supertask.completed_subtasks).sum(),
+            total_subtasks: supertasks.iter().map(|supertask| supertask.total_subtasks).sum(),
+            supertasks
+        }
+    }
+
+    /// Fetches the entries of the curriculum a trainee follows on a rotation and their user tasks in it, and computes their progress.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `user_id` - The ID of the trainee.
+    /// * `rotation_id` - The ID of the rotation.
+    pub async fn fetch(pool: &PgPool, user_id: i32, rotation_id: i32) -> Result<Self> {
+        let entries = CurriculumVersion::fetch_trainee_structure(pool, user_id, rotation_id).await?;
+        let user_tasks = UserTask::fetch_as_map(pool, user_id, rotation_id).await?;
+
+        Ok(Self::compute(user_id, rotation_id, &entries, &user_tasks))
+    }
+}
+
+impl CohortProgress {
+    /// Computes the progress of every trainee on a rotation in a single query, using the same definition and floating point arithmetic as `RotationProgress`.
+    /// Each trainee's progress is measured against the curriculum they follow, which is the version they are enrolled in, otherwise the latest version, otherwise the draft.
+    /// A trainee has no progress on a supertask of the draft that is not in the version they follow.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `rotation_id` - The ID of the rotation.
+    /// * `filter` - How to filter and sort the trainees.
+    ///
+    /// # Returns
+    ///
+    /// The matrix, whose progress on each supertask is in the same order as the supertasks of the draft, or `None` if the filter names a supertask outside the rotation.
+    pub async fn fetch(pool: &PgPool, rotation_id: i32, filter: &CohortFilter) -> Result<Option<Self>> {
+        let supertasks = Supertask::fetch_all(pool, rotation_id).await?;
+
+        if let Some(supertask_id) = filter.supertask_id {
+            if !supertasks.iter().any(|supertask| supertask.id() == supertask_id) {
+                return Ok(None);
+            }
+        }
+
+        let rows = sqlx::query_as!(
+            CohortProgressRow,
+            r#"
+            WITH trainees AS (
+                SELECT id, username, name, COALESCE(
+                    (SELECT version_id FROM curriculum_enrollments WHERE user_id = users.id AND rotation_id = $1),
+                    (SELECT id FROM curriculum_versions WHERE rotation_id = $1 ORDER BY version DESC LIMIT 1)
+                ) AS version_id
+                FROM users
+                WHERE NOT is_admin
+            ),
+            curriculum_supertasks AS (
+                SELECT NULL::INT AS version_id, id AS supertask_id
+                FROM supertasks
+                WHERE rotation_id = $1 AND deleted_at IS NULL
+                UNION ALL
+                SELECT curriculum_versions.id, (supertask.value->'entry'->>'id')::INT
+                FROM curriculum_versions
+                CROSS JOIN jsonb_array_elements(curriculum_versions.structure) AS supertask
+                WHERE curriculum_versions.rotation_id = $1
+            ),
+            curriculum_subtasks AS (
+                SELECT NULL::INT AS version_id, tasks.supertask_id, tasks.id AS task_id, subtasks.id AS subtask_id
+                FROM tasks
+                LEFT JOIN subtasks ON subtasks.task_id = tasks.id AND subtasks.deleted_at IS NULL
+                WHERE tasks.rotation_id = $1 AND tasks.deleted_at IS NULL
+                UNION ALL
+                SELECT curriculum_versions.id, (supertask.value->'entry'->>'id')::INT, (task.value->'entry'->>'id')::INT, (subtask.value->>'id')::INT
+                FROM curriculum_versions
+                CROSS JOIN jsonb_array_elements(curriculum_versions.structure) AS supertask
+                CROSS JOIN jsonb_array_elements(supertask.value->'children') AS task
+                LEFT JOIN LATERAL jsonb_array_elements(task.value->'children') AS subtask ON TRUE
+                WHERE curriculum_versions.rotation_id = $1
+            ),
+            completed AS (
+                SELECT user_id, subtask_id FROM user_tasks WHERE rotation_id = $1 AND is_completed
+            ),
+            task_progress AS (
+                SELECT
+                    trainees.id AS user_id, curriculum_subtasks.supertask_id,
+                    COALESCE(COUNT(completed.subtask_id)::FLOAT8 / NULLIF(COUNT(curriculum_subtasks.subtask_id), 0) * 100.0, 0) AS progress
+                FROM trainees
+                JOIN curriculum_subtasks ON curriculum_subtasks.version_id IS NOT DISTINCT FROM trainees.version_id
+                LEFT JOIN completed ON completed.subtask_id = curriculum_subtasks.subtask_id AND completed.user_id = trainees.id
+                GROUP BY trainees.id, curriculum_subtasks.supertask_id, curriculum_subtasks.task_id
+            ),
+            supertask_progress AS (
+                SELECT
+                    trainees.id AS user_id, curriculum_supertasks.supertask_id,
+                    COALESCE(AVG(task_progress.progress), 0) AS progress
+                FROM trainees
+                JOIN curriculum_supertasks ON curriculum_supertasks.version_id IS NOT DISTINCT FROM trainees.version_id
+                LEFT JOIN task_progress ON task_progress.user_id = trainees.id AND task_progress.supertask_id = curriculum_supertasks.supertask_id
+                GROUP BY trainees.id, curriculum_supertasks.supertask_id
+            ),
+            draft_supertasks AS (
+                SELECT id, position FROM supertasks WHERE rotation_id = $1 AND deleted_at IS NULL
+            ),
+            matrix AS (
+                SELECT
+                    trainees.id AS user_id, trainees.username, trainees.name,
+                    COALESCE(AVG(supertask_progress.progress), 0) AS progress,
+                    ARRAY(
+                        SELECT COALESCE(column_progress.progress, 0)
+                        FROM draft_supertasks
+                        LEFT JOIN supertask_progress AS column_progress
+                            ON column_progress.supertask_id = draft_supertasks.id AND column_progress.user_id = trainees.id
+                        ORDER BY draft_supertasks.position, draft_supertasks.id
+                    ) AS supertask_progress,
+                    COALESCE(MAX(supertask_progress.progress) FILTER (WHERE supertask_progress.supertask_id = $2), 0) AS selected_progress
+                FROM trainees
+                LEFT JOIN supertask_progress ON supertask_progress.user_id = trainees.id
+                GROUP BY trainees.id, trainees.username, trainees.name
+            ),
+            sorted AS (
+                SELECT *, CASE WHEN $2::INT IS NULL THEN progress ELSE selected_progress END AS sort_progress
+                FROM matrix
+            )
+            SELECT user_id AS "user_id!", username AS "username!", name AS "name!", progress AS "progress!", supertask_progress AS "supertask_progress!"
+            FROM sorted
+            WHERE sort_progress BETWEEN $3 AND $4
+            ORDER BY
+                CASE WHEN $5 AND NOT $6 THEN sort_progress END ASC,
+                CASE WHEN $5 AND $6 THEN sort_progress END DESC,
+                CASE WHEN NOT $6 THEN name END ASC,
+                CASE WHEN $6 THEN name END DESC,
+                user_id;
+            "#,
+            rotation_id,
+            filter.supertask_id,
+            filter.min_progress,
+            filter.max_progress,
+            filter.sort == CohortSort::Progress,
+            filter.descending
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(Some(Self {
+            rotation_id,
+            supertasks,
+            rows: rows.into_boxed_slice()
+        }))
+    }
+}
@@ -0,0 +1,287 @@
+use super::prelude::*;
+use crate::utilities::spreadsheet::{Cell, SpreadsheetFormat, SpreadsheetWriter, CHUNK_SIZE};
+use futures_util::TryStreamExt;
+use std::{collections::HashMap, io::Read};
+use tokio::sync::mpsc::Sender;
+
+/// How the rows of a progress export are laid out.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportLayout {
+    /// A row for each trainee and subtask.
+    #[default]
+    Rows,
+
+    /// A row for each trainee, with a column for the completion of each subtask.
+    Matrix
+}
+
+/// The progress of a trainee on a subtask, which is empty if they have not started it.
+struct ProgressExportRow {
+    rotation_name: String,
+    supertask_title: String,
+    task_title: String,
+    subtask_id: i32,
+    subtask_title: String,
+    required_count: i32,
+    user_id: i32,
+    username: String,
+    name: String,
+    is_completed: bool,
+    status: Option<String>,
+    encounter_count: i64,
+    comment: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+    completed_at: Option<DateTime<Utc>>
+}
+
+/// A subtask with a column in a matrix export.
+struct ExportColumn {
+    subtask_id: i32,
+    rotation_name: String,
+    supertask_title: String,
+    task_title: String,
+    subtask_title: String
+}
+
+/// A trainee's row in a matrix export, which counts completed subtasks across their whole curriculum rather than only the columns.
+struct MatrixRow {
+    user_id: i32,
+    username: String,
+    name: String,
+    completed_subtasks: usize,
+    completions: Vec<Option<bool>>
+}
+
+/// An export of the progress of every trainee on a rotation, or on every active rotation of the program.
+///
+/// Like the cohort progress matrix, each trainee's rows cover the subtasks of the curriculum they follow on each rotation.
+/// The columns of a matrix are the subtasks of the draft, so a trainee's cell is empty for a subtask that is not in the version they follow.
+pub struct ProgressExport {
+    rotation_id: Option<i32>,
+    layout: ExportLayout,
+    columns: Box<[ExportColumn]>
+}
+
+impl ProgressExport {
+    /// Prepares an export, fetching the subtask columns up front when it is a matrix.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `rotation_id` - The ID of the rotation to export, or `None` to export every rotation that has not been archived.
+    /// * `layout` - How to lay out the rows of the export.
+    pub async fn prepare(pool: &PgPool, rotation_id: Option<i32>, layout: ExportLayout) -> Result<Self> {
+        let columns = match layout {
+            ExportLayout::Rows => Vec::new(),
+            ExportLayout::Matrix => sqlx::query_as!(
+                ExportColumn,
+                r#"
+                SELECT
+                    subtasks.id AS subtask_id, rotations.name AS rotation_name,
+                    supertasks.title AS supertask_title, tasks.title AS task_title, subtasks.title AS subtask_title
+                FROM subtasks
+                JOIN tasks ON tasks.id = subtasks.task_id
+                JOIN supertasks ON supertasks.id = tasks.supertask_id
+                JOIN rotations ON rotations.id = subtasks.rotation_id
+                WHERE subtasks.deleted_at IS NULL AND (rotations.id = $1 OR ($1 IS NULL AND rotations.archived_at IS NULL))
+                ORDER BY rotations.name, rotations.id, supertasks.position, supertasks.id, tasks.position, tasks.id, subtasks.position, subtasks.id;
+                "#,
+                rotation_id
+            )
+            .fetch_all(pool)
+            .await?
+        };
+
+        Ok(Self {
+            rotation_id,
+            layout,
+            columns: columns.into_boxed_slice()
+        })
+    }
+
+    /// The number of columns the export will have.
+    pub fn column_count(&self) -> usize {
+        self.headers().len()
+    }
+
+    fn headers(&self) -> Vec<String> {
+        let headers = match self.layout {
+            ExportLayout::Rows => vec![
+                "Rotation", "Supertask", "Task", "Subtask", "Username", "Name", "Completed", "Status",
+                "Encounters", "Required Encounters", "Comment", "Created At", "Updated At", "Completed At"
+            ],
+            ExportLayout::Matrix => vec!["Username", "Name", "Completed Subtasks"]
+        };
+
+        let columns = self.columns.iter().map(|column| {
+            match self.rotation_id {
+                Some(_) => format!("{} / {} / {}", column.supertask_title, column.task_title, column.subtask_title),
+                None => format!("{} / {} / {} / {}", column.rotation_name, column.supertask_title, column.task_title, column.subtask_title)
+            }
+        });
+
+        headers.into_iter().map(String::from).chain(columns).collect()
+    }
+
+    /// Writes the export in the given format and sends it through a channel in chunks as it is written.
+    ///
+    /// Rows are streamed from the database, so at most a single trainee is held in memory at a time.
+    /// Writing stops early if the receiver is dropped, and any error is sent as the last item.
+    pub async fn stream(self, pool: PgPool, format: SpreadsheetFormat, sender: Sender<Result<Vec<u8>>>) {
+        if let Err(error) = self.write(&pool, format, &sender).await {
+            let _ = sender.send(Err(error)).await;
+        }
+    }
+
+    async fn write(&self, pool: &PgPool, format: SpreadsheetFormat, sender: &Sender<Result<Vec<u8>>>) -> Result<()> {
+        let mut writer = SpreadsheetWriter::new(format);
+        writer.write_header(&self.headers())?;
+
+        let column_indices = self.columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| (column.subtask_id, index))
+            .collect::<HashMap<_, _>>();
+
+        // The trainee whose matrix row is being filled in, along with the completion of each column that is in their curriculum.
+        let mut current: Option<MatrixRow> = None;
+
+        let mut rows = sqlx::query_as!(
+            ProgressExportRow,
+            r#"
+            WITH scoped_rotations AS (
+                SELECT id, name FROM rotations WHERE id = $1 OR ($1 IS NULL AND archived_at IS NULL)
+            ),
+            curricula AS (
+                SELECT
+                    NULL::INT AS version_id, subtasks.rotation_id,
+                    supertasks.title AS supertask_title, tasks.title AS task_title,
+                    subtasks.id AS subtask_id, subtasks.title AS subtask_title, subtasks.required_count,
+                    ARRAY[supertasks.position, supertasks.id, tasks.position, tasks.id, subtasks.position, subtasks.id]::BIGINT[] AS sort_key
+                FROM subtasks
+                JOIN tasks ON tasks.id = subtasks.task_id
+                JOIN supertasks ON supertasks.id = tasks.supertask_id
+                WHERE subtasks.deleted_at IS NULL AND subtasks.rotation_id IN (SELECT id FROM scoped_rotations)
+                UNION ALL
+                SELECT
+                    curriculum_versions.id, curriculum_versions.rotation_id,
+                    supertask.value->'entry'->>'title', task.value->'entry'->>'title',
+                    (subtask.value->>'id')::INT, subtask.value->>'title', (subtask.value->>'requiredCount')::INT,
+                    ARRAY[supertask.position, 0, task.position, 0, subtask.position, 0]::BIGINT[]
+                FROM curriculum_versions
+                CROSS JOIN jsonb_array_elements(curriculum_versions.structure) WITH ORDINALITY AS supertask(value, position)
+                CROSS JOIN jsonb_array_elements(supertask.value->'children') WITH ORDINALITY AS task(value, position)
+                CROSS JOIN jsonb_array_elements(task.value->'children') WITH ORDINALITY AS subtask(value, position)
+                WHERE curriculum_versions.rotation_id IN (SELECT id FROM scoped_rotations)
+            ),
+            followed AS (
+                SELECT
+                    users.id AS user_id, users.username, users.name,
+                    scoped_rotations.id AS rotation_id, scoped_rotations.name AS rotation_name,
+                    COALESCE(
+                        (SELECT version_id FROM curriculum_enrollments WHERE user_id = users.id AND rotation_id = scoped_rotations.id),
+                        (SELECT id FROM curriculum_versions WHERE rotation_id = scoped_rotations.id ORDER BY version DESC LIMIT 1)
+                    ) AS version_id
+                FROM users
+                CROSS JOIN scoped_rotations
+                WHERE NOT users.is_admin
+            )
+            SELECT
+                followed.rotation_name AS "rotation_name!", curricula.supertask_title AS "supertask_title!", curricula.task_title AS "task_title!",
+                curricula.subtask_id AS "subtask_id!", curricula.subtask_title AS "subtask_title!", curricula.required_count AS "required_count!",
+                followed.user_id AS "user_id!", followed.username AS "username!", followed.name AS "name!",
+                COALESCE(user_tasks.is_completed, FALSE) AS "is_completed!",
+                user_tasks.status AS "status?",
+                (SELECT COUNT(*) FROM user_task_encounters WHERE user_task_id = user_tasks.id) AS "encounter_count!",
+                user_tasks.comment AS "comment?",
+                user_tasks.created_at AS "created_at?",
+                user_tasks.updated_at AS "updated_at?",
+                user_tasks.completed_at AS "completed_at?"
+            FROM followed
+            JOIN curricula ON curricula.rotation_id = followed.rotation_id AND curricula.version_id IS NOT DISTINCT FROM followed.version_id
+            LEFT JOIN user_tasks ON user_tasks.user_id = followed.user_id AND user_tasks.subtask_id = curricula.subtask_id
+            ORDER BY followed.name, followed.user_id, followed.rotation_name, followed.rotation_id, curricula.sort_key;
+            "#,
+            self.rotation_id
+        )
+        .fetch(pool);
+
+        while let Some(row) = rows.try_next().await? {
+            match self.layout {
+                ExportLayout::Rows => writer.write_row(&[
+                    row.rotation_name.into(),
+                    row.supertask_title.into(),
+                    row.task_title.into(),
+                    row.subtask_title.into(),
+                    row.username.into(),
+                    row.name.into(),
+                    row.is_completed.into(),
+                    row.status.into(),
+                    Cell::Number(row.encounter_count as f64),
+                    Cell::Number(row.required_count as f64),
+                    row.comment.into(),
+                    row.created_at.into(),
+                    row.updated_at.into(),
+                    row.completed_at.into()
+                ])?,
+                ExportLayout::Matrix => {
+                    if current.as_ref().is_some_and(|trainee| trainee.user_id != row.user_id) {
+                        if let Some(trainee) = current.take() {
+                            Self::write_matrix_row(&mut writer, trainee)?;
+                        }
+                    }
+
+                    let trainee = current.get_or_insert_with(|| MatrixRow {
+                        user_id: row.user_id,
+                        username: row.username,
+                        name: row.name,
+                        completed_subtasks: 0,
+                        completions: vec![None; self.columns.len()]
+                    });
+
+                    trainee.completed_subtasks += row.is_completed as usize;
+
+                    if let Some(index) = column_indices.get(&row.subtask_id) {
+                        trainee.completions[*index] = Some(row.is_completed);
+                    }
+                }
+            }
+
+            if let Some(chunk) = writer.take_chunk()? {
+                if sender.send(Ok(chunk)).await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+
+        if let Some(trainee) = current.take() {
+            Self::write_matrix_row(&mut writer, trainee)?;
+        }
+
+        // Finishing an XLSX workbook compresses every row, so it is done off of the async runtime.
+        let sender = sender.clone();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut reader = writer.finish()?;
+            let mut buffer = vec![0; CHUNK_SIZE];
+
+            loop {
+                let length = reader.read(&mut buffer)?;
+                if length == 0 || sender.blocking_send(Ok(buffer[..length].to_vec())).is_err() {
+                    return Ok(());
+                }
+            }
+        })
+        .await?
+    }
+
+    fn write_matrix_row(writer: &mut SpreadsheetWriter, trainee: MatrixRow) -> Result<()> {
+        let cells = [Cell::Text(trainee.username), Cell::Text(trainee.name), Cell::Number(trainee.completed_subtasks as f64)]
+            .into_iter()
+            .chain(trainee.completions.into_iter().map(|is_completed| is_completed.map_or(Cell::Empty, Cell::Boolean)))
+            .collect::<Vec<_>>();
+
+        writer.write_row(&cells)
+    }
+}
@@ -0,0 +1,96 @@
+use super::{curriculum_version::CurriculumVersion, prelude::*, progress::RotationProgress, rotation::Rotation, user_task::UserTask, verification::VerificationStatus};
+use crate::utilities::pdf::PdfReport;
+
+/// The people who sign off a progress report, in the order their signature fields appear.
+const SIGNATORIES: [&str; 2] = ["Trainee", "Program director"];
+
+/// Formats a percentage for a report, dropping the decimal when it is a whole number.
+fn format_progress(progress: f64) -> String {
+    match progress.fract() == 0.0 {
+        true => format!("{progress:.0}%"),
+        false => format!("{progress:.1}%")
+    }
+}
+
+fn format_status(status: VerificationStatus) -> &'static str {
+    match status {
+        VerificationStatus::Draft => "Not submitted",
+        VerificationStatus::Submitted => "Awaiting verification",
+        VerificationStatus::Verified => "Verified",
+        VerificationStatus::Returned => "Returned"
+    }
+}
+
+/// Renders the progress of a trainee on a rotation as a printable PDF report, ending with fields for the trainee and program director to sign.
+///
+/// # Parameters
+///
+/// * `pool` - The Postgres connection pool to use for the operation.
+/// * `user_id` - The ID of the trainee.
+/// * `rotation_id` - The ID of the rotation.
+///
+/// # Returns
+///
+/// The PDF document, or `None` if the trainee or rotation does not exist.
+pub async fn render_progress_report(pool: &PgPool, user_id: i32, rotation_id: i32) -> Result<Option<Vec<u8>>> {
+    let Some(trainee) = sqlx::query!("SELECT name, username FROM users WHERE id = $1 AND NOT is_admin;", user_id)
+        .fetch_optional(pool)
+        .await? else {
+        return Ok(None);
+    };
+
+    let Some(rotation) = Rotation::fetch_by_id(pool, rotation_id).await? else {
+        return Ok(None);
+    };
+
+    let entries = CurriculumVersion::fetch_trainee_structure(pool, user_id, rotation_id).await?;
+    let user_tasks = UserTask::fetch_as_map(pool, user_id, rotation_id).await?;
+    let progress = RotationProgress::compute(user_id, rotation_id, &entries, &user_tasks);
+
+    let mut report = PdfReport::new(
+        &format!("Progress Report: {}", rotation.name().as_str()),
+        &format!("Trainee: {} ({})", trainee.name, trainee.username),
+        &format!("Generated {} UTC", Utc::now().format("%-d %B %Y %H:%M"))
+    )?;
+
+    report.row("Overall progress", &format_progress(progress.progress()), 12.0, 0.0, true);
+    report.text(&format!("{} of {} subtasks completed", progress.completed_subtasks(), progress.total_subtasks()), 9.0, 0.0, false);
+
+    for (supertask, supertask_progress) in entries.hierarchies().iter().zip(progress.supertasks()) {
+        report.space(6.0);
+        report.row(supertask.entry().title().as_str(), &format_progress(supertask_progress.progress()), 12.0, 0.0, true);
+
+        for (task, task_progress) in supertask.children().iter().zip(supertask_progress.tasks()) {
+            report.space(2.0);
+            report.row(task.entry().title().as_str(), &format_progress(task_progress.progress()), 10.0, 5.0, true);
+
+            for subtask in task.children() {
+                let user_task = user_tasks.get(&subtask.id());
+                let is_completed = user_task.is_some_and(|user_task| user_task.is_completed());
+                report.row(subtask.title().as_str(), if is_completed { "Complete" } else { "Incomplete" }, 9.0, 10.0, false);
+
+                let Some(user_task) = user_task else {
+                    continue;
+                };
+
+                let mut details = vec![format_status(user_task.status()).to_string()];
+                if subtask.required_count() > 1 {
+                    details.push(format!("{} of {} encounters", user_task.encounter_count(), subtask.required_count()));
+                }
+                if let Some(completed_at) = user_task.completed_at() {
+                    details.push(format!("Completed {}", completed_at.format("%-d %B %Y")));
+                }
+
+                report.text(&details.join(" | "), 8.0, 14.0, false);
+                if !user_task.comment().as_str().is_empty() {
+                    report.text(&format!("Comment: {}", user_task.comment().as_str()), 8.0, 14.0, false);
+                }
+            }
+        }
+    }
+
+    report.space(10.0);
+    report.signature_block(&SIGNATORIES);
+
+    Ok(Some(report.finish()?))
+}
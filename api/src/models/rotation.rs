@@ -1,13 +1,65 @@
-use super::prelude::*;
-use crate::utilities::parsable::Name;
+use super::{curriculum::{CurriculumDocument, CurriculumImportSummary}, entry_structure::{EntryIdMapping, EntryStructure}, prelude::*};
+use crate::utilities::parsable::{ConfirmationToken, Description, Location, Name};
 use std::{collections::HashMap, marker::PhantomData};
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use anyhow::anyhow;
+use sqlx::{error::BoxDynError, postgres::{PgTypeInfo, PgValueRef}, Decode, Postgres};
+
+const DELETION_TOKEN_LENGTH: usize = 8;
+
+/// The kind of rotation within the residency program.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum RotationCategory {
+    Core,
+    Elective
+}
+
+impl RotationCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Core => "core",
+            Self::Elective => "elective"
+        }
+    }
+}
+
+impl TryFrom<String> for RotationCategory {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        match value.as_str() {
+            "core" => Ok(Self::Core),
+            "elective" => Ok(Self::Elective),
+            _ => Err(anyhow!("Unknown rotation category: {}", value))
+        }
+    }
+}
+
+impl sqlx::Type<Postgres> for RotationCategory {
+    fn type_info() -> PgTypeInfo {
+        <String as sqlx::Type<Postgres>>::type_info()
+    }
+}
+
+/// Decodes a stored category, failing on anything unknown rather than treating it as some other category.
+impl<'r> Decode<'r, Postgres> for RotationCategory {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(Self::try_from(<String as Decode<Postgres>>::decode(value)?)?)
+    }
+}
 
 #[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 struct RotationModel {
     id: i32,
     name: Name,
-    last_updated: DateTime<Utc>
+    description: Description,
+    location: Location,
+    duration_weeks: Option<i32>,
+    category: RotationCategory,
+    last_updated: DateTime<Utc>,
+    archived_at: Option<DateTime<Utc>>
 }
 
 #[derive(Serialize, Clone)]
@@ -25,6 +77,11 @@ impl<S> Rotation<S> {
         self.model.id
     }
 
+    #[inline(always)]
+    pub fn name(&self) -> &Name {
+        &self.model.name
+    }
+
     #[inline(always)]
     pub fn last_updated(&self) -> DateTime<Utc> {
         self.model.last_updated
@@ -37,32 +94,35 @@ impl Rotation<Unsynced> {
             model: RotationModel {
                 id: 0,
                 name,
-                last_updated: Utc::now()
+                description: Description::from(String::new()),
+                location: Location::from(String::new()),
+                duration_weeks: None,
+                category: RotationCategory::Core,
+                last_updated: Utc::now(),
+                archived_at: None
             },
             _marker: PhantomData
         }
     }
 
     pub async fn insert(self, pool: &PgPool) -> Result<Rotation<Synced>> {
-        let row = sqlx::query!(
+        let model = sqlx::query_as!(
+            RotationModel,
             r#"
-            INSERT INTO rotations (name)
-            VALUES ($1)
-            RETURNING id, last_updated;
+            INSERT INTO rotations (name, description, location, duration_weeks, category)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, name, description, location, duration_weeks, category AS "category: RotationCategory", last_updated, archived_at;
             "#,
-            self.model.name.as_str()
+            self.model.name.as_str(),
+            self.model.description.as_str(),
+            self.model.location.as_str(),
+            self.model.duration_weeks,
+            self.model.category.as_str()
         )
         .fetch_one(pool)
         .await?;
 
-        Ok(Rotation {
-            model: RotationModel {
-                id: row.id,
-                name: self.model.name,
-                last_updated: row.last_updated
-            },
-            _marker: PhantomData
-        })
+        Ok(Rotation::<Synced>::from(model))
     }
 }
 
@@ -75,55 +135,342 @@ impl Rotation<Synced> {
         }
     }
 
-    pub async fn delete(pool: &PgPool, rotation_id: i32) -> Result<()> {
-        sqlx::query!(
+    /// Fetches a rotation by its ID, whether or not it has been archived.
+    pub async fn fetch_by_id(pool: &PgPool, rotation_id: i32) -> Result<Option<Self>> {
+        let model = sqlx::query_as!(
+            RotationModel,
             r#"
-            DELETE FROM rotations WHERE id = $1;
+            SELECT id, name, description, location, duration_weeks, category AS "category: RotationCategory", last_updated, archived_at
+            FROM rotations
+            WHERE id = $1;
             "#,
             rotation_id
         )
-        .execute(pool)
+        .fetch_optional(pool)
         .await?;
 
-        Ok(())
+        Ok(model.map(Self::from))
     }
 
-    /// Checks if a cache is valid by comparing the cache timestamp with the last updated timestamp of the rotation.
+    /// Updates the name and metadata of a rotation, keeping whatever is `None`.
     ///
     /// # Arguments
     ///
     /// * `pool` - A reference to the database connection pool.
     /// * `rotation_id` - The ID of the rotation.
-    /// * `cache_timestamp` - The timestamp of the cache to be checked.
+    /// * `name` - The new name of the rotation.
+    /// * `description` - The new description of the rotation.
+    /// * `location` - The site or location the rotation takes place at.
+    /// * `duration_weeks` - The length of the rotation in weeks, which is `Some(None)` to clear it.
+    /// * `category` - The category of the rotation.
+    ///
+    /// # Returns
+    ///
+    /// The updated rotation, or `None` if the rotation does not exist or has been archived.
+    pub async fn update(pool: &PgPool, rotation_id: i32, name: Option<&Name>, description: Option<&Description>, location: Option<&Location>, duration_weeks: Option<Option<i32>>, category: Option<RotationCategory>) -> Result<Option<Self>> {
+        let model = sqlx::query_as!(
+            RotationModel,
+            r#"
+            UPDATE rotations
+            SET
+                name = COALESCE($1, name),
+                description = COALESCE($2, description),
+                location = COALESCE($3, location),
+                duration_weeks = CASE WHEN $4 THEN $5 ELSE duration_weeks END,
+                category = COALESCE($6, category),
+                last_updated = NOW()
+            WHERE id = $7 AND archived_at IS NULL
+            RETURNING id, name, description, location, duration_weeks, category AS "category: RotationCategory", last_updated, archived_at;
+            "#,
+            name.map(Name::as_str),
+            description.map(Description::as_str),
+            location.map(Location::as_str),
+            duration_weeks.is_some(),
+            duration_weeks.flatten(),
+            category.map(|category| category.as_str()),
+            rotation_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(model.map(Self::from))
+    }
+
+    /// Creates a copy of a rotation along with its entire entry structure in a single transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A reference to the database connection pool.
+    /// * `rotation_id` - The ID of the rotation to copy.
+    /// * `name` - The name of the copy, or `None` to keep the name of the original rotation.
+    ///
+    /// # Returns
+    ///
+    /// The new rotation and the mapping from the original entry IDs to the copied entry IDs, or `None` if the rotation does not exist.
+    pub async fn clone_with_entries(pool: &PgPool, rotation_id: i32, name: Option<&Name>) -> Result<Option<(Self, EntryIdMapping)>> {
+        let mut transaction = pool.begin().await?;
+
+        let model = sqlx::query_as!(
+            RotationModel,
+            r#"
+            INSERT INTO rotations (name, description, location, duration_weeks, category)
+            SELECT COALESCE($2, name), description, location, duration_weeks, category
+            FROM rotations
+            WHERE id = $1
+            RETURNING id, name, description, location, duration_weeks, category AS "category: RotationCategory", last_updated, archived_at;
+            "#,
+            rotation_id,
+            name.map(|name| name.as_str())
+        )
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        let model = match model {
+            Some(model) => model,
+            None => return Ok(None)
+        };
+
+        let mapping = EntryStructure::copy(&mut transaction, rotation_id, model.id).await?;
+        transaction.commit().await?;
+
+        Ok(Some((Self::from(model), mapping)))
+    }
+
+    /// Imports a curriculum document in a single transaction, either into a new rotation or merged into an existing one.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A reference to the database connection pool.
+    /// * `document` - The curriculum to import, which must have been validated beforehand.
+    /// * `rotation_id` - The ID of the rotation to merge into, or `None` to create a new rotation named after the document.
+    /// * `dry_run` - Whether to roll the import back once it has been performed, leaving the database unchanged.
     ///
     /// # Returns
     ///
-    /// A boolean wrapped in a Result indicating whether the cache is valid. There will be an error if a database error occurs.
-    pub async fn is_cache_valid(pool: &PgPool, rotation_id: i32, cache_timestamp: Option<DateTime<Utc>>) -> Result<bool> {
-        let cache_timestamp = match cache_timestamp {
-            Some(cache_timestamp) => cache_timestamp,
-            None => return Ok(false)
+    /// The rotation imported into and the number of entries created, or `None` if the rotation to merge into does not exist or has been archived.
+    pub async fn import_curriculum(pool: &PgPool, document: &CurriculumDocument, rotation_id: Option<i32>, dry_run: bool) -> Result<Option<(Self, CurriculumImportSummary)>> {
+        let name = Name::parse(document.name.clone())?;
+        let mut transaction = pool.begin().await?;
+
+        let model = match rotation_id {
+            Some(rotation_id) => sqlx::query_as!(
+                RotationModel,
+                r#"
+                UPDATE rotations
+                SET last_updated = NOW()
+                WHERE id = $1 AND archived_at IS NULL
+                RETURNING id, name, description, location, duration_weeks, category AS "category: RotationCategory", last_updated, archived_at;
+                "#,
+                rotation_id
+            )
+            .fetch_optional(&mut *transaction)
+            .await?,
+            None => Some(sqlx::query_as!(
+                RotationModel,
+                r#"
+                INSERT INTO rotations (name)
+                VALUES ($1)
+                RETURNING id, name, description, location, duration_weeks, category AS "category: RotationCategory", last_updated, archived_at;
+                "#,
+                name.as_str()
+            )
+            .fetch_one(&mut *transaction)
+            .await?)
         };
 
-        let last_updated = sqlx::query!(
+        let model = match model {
+            Some(model) => model,
+            None => return Ok(None)
+        };
+
+        let summary = document.merge_into(&mut transaction, model.id).await?;
+
+        match dry_run {
+            true => transaction.rollback().await?,
+            false => transaction.commit().await?
+        }
+
+        Ok(Some((Self::from(model), summary)))
+    }
+
+    /// Archives a rotation, hiding it from user sessions while keeping its entries and every user's progress.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A reference to the database connection pool.
+    /// * `rotation_id` - The ID of the rotation.
+    ///
+    /// # Returns
+    ///
+    /// The archived rotation, or `None` if the rotation does not exist.
+    pub async fn archive(pool: &PgPool, rotation_id: i32) -> Result<Option<Self>> {
+        let model = sqlx::query_as!(
+            RotationModel,
             r#"
-            SELECT last_updated FROM rotations WHERE id = $1;
+            UPDATE rotations
+            SET archived_at = COALESCE(archived_at, NOW())
+            WHERE id = $1
+            RETURNING id, name, description, location, duration_weeks, category AS "category: RotationCategory", last_updated, archived_at;
             "#,
             rotation_id
         )
-        .fetch_one(pool)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(model.map(Self::from))
+    }
+
+    /// Restores an archived rotation so that it is visible in user sessions again.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A reference to the database connection pool.
+    /// * `rotation_id` - The ID of the rotation.
+    ///
+    /// # Returns
+    ///
+    /// The restored rotation, or `None` if the rotation does not exist.
+    pub async fn unarchive(pool: &PgPool, rotation_id: i32) -> Result<Option<Self>> {
+        let model = sqlx::query_as!(
+            RotationModel,
+            r#"
+            UPDATE rotations
+            SET archived_at = NULL, deletion_token = NULL, deletion_token_expiry = NULL
+            WHERE id = $1
+            RETURNING id, name, description, location, duration_weeks, category AS "category: RotationCategory", last_updated, archived_at;
+            "#,
+            rotation_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(model.map(Self::from))
+    }
+
+    /// Generates a random token used to confirm the permanent deletion of a rotation.
+    #[inline(always)]
+    fn generate_deletion_token() -> ConfirmationToken {
+        let token = thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(DELETION_TOKEN_LENGTH)
+            .map(char::from)
+            .collect();
+
+        ConfirmationToken::parse(token).unwrap()
+    }
+
+    /// Begins the permanent deletion of an archived rotation by issuing a confirmation token.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A reference to the database connection pool.
+    /// * `rotation_id` - The ID of the rotation.
+    /// * `expiration_minutes` - The number of minutes the confirmation token will be valid for.
+    ///
+    /// # Returns
+    ///
+    /// The expiration time and the confirmation token, or `None` if the rotation does not exist or is not archived.
+    pub async fn request_deletion(pool: &PgPool, rotation_id: i32, expiration_minutes: i32) -> Result<Option<(DateTime<Utc>, ConfirmationToken)>> {
+        let token = Self::generate_deletion_token();
+        let row = sqlx::query!(
+            r#"
+            UPDATE rotations
+            SET deletion_token = $1, deletion_token_expiry = NOW() + make_interval(mins => $2)
+            WHERE id = $3 AND archived_at IS NOT NULL
+            RETURNING deletion_token_expiry AS "deletion_token_expiry!";
+            "#,
+            token.as_str(),
+            expiration_minutes,
+            rotation_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.map(|row| (row.deletion_token_expiry, token)))
+    }
+
+    /// Permanently deletes an archived rotation along with its entries and every user's progress on it.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A reference to the database connection pool.
+    /// * `rotation_id` - The ID of the rotation.
+    /// * `deletion_token` - The confirmation token issued by `request_deletion`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the rotation was deleted, `false` if it is not archived or the token is invalid or expired.
+    pub async fn delete(pool: &PgPool, rotation_id: i32, deletion_token: &str) -> Result<bool> {
+        let rows_affected = sqlx::query!(
+            r#"
+            DELETE FROM rotations
+            WHERE id = $1
+            AND archived_at IS NOT NULL
+            AND deletion_token = $2
+            AND deletion_token_expiry > NOW();
+            "#,
+            rotation_id,
+            deletion_token
+        )
+        .execute(pool)
         .await?
-        .last_updated;
+        .rows_affected();
+
+        Ok(rows_affected > 0)
+    }
+
+    /// Fetches when the entries of a rotation were last changed, which is what cached entries are validated against.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A reference to the database connection pool.
+    /// * `rotation_id` - The ID of the rotation.
+    ///
+    /// # Returns
+    ///
+    /// The last updated timestamp of the rotation, or `None` if the rotation does not exist.
+    pub async fn fetch_last_updated(pool: &PgPool, rotation_id: i32) -> Result<Option<DateTime<Utc>>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT last_updated FROM rotations WHERE id = $1;
+            "#,
+            rotation_id
+        )
+        .fetch_optional(pool)
+        .await?;
 
-        // If the cache timestamp is greater than or equal to the last updated timestamp, then the cache is valid.
-        Ok(cache_timestamp >= last_updated)
+        Ok(row.map(|row| row.last_updated))
     }
 
+    /// Fetches every rotation that has not been archived.
     pub async fn fetch_all_as_map(pool: &PgPool) -> Result<HashMap<i32, Self>> {
         let rotations = sqlx::query_as!(
             RotationModel,
             r#"
-            SELECT * FROM rotations;
+            SELECT id, name, description, location, duration_weeks, category AS "category: RotationCategory", last_updated, archived_at
+            FROM rotations
+            WHERE archived_at IS NULL;
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let map = rotations
+            .into_iter()
+            .map(|rotation| { (rotation.id, Self::from(rotation)) })
+            .collect::<HashMap<_, _>>();
+
+        Ok(map)
+    }
+
+    /// Fetches every archived rotation.
+    pub async fn fetch_archived_as_map(pool: &PgPool) -> Result<HashMap<i32, Self>> {
+        let rotations = sqlx::query_as!(
+            RotationModel,
+            r#"
+            SELECT id, name, description, location, duration_weeks, category AS "category: RotationCategory", last_updated, archived_at
+            FROM rotations
+            WHERE archived_at IS NOT NULL;
             "#
         )
         .fetch_all(pool)
@@ -0,0 +1,79 @@
+use super::prelude::*;
+use crate::utilities::parsable::{Name, Username};
+
+/// A regular user assigned to supervise a trainee, allowing them to verify the trainee's tasks.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SupervisorAssignment {
+    supervisor_id: i32,
+    supervisor_name: Name,
+    supervisor_username: Username,
+    trainee_id: i32,
+    trainee_name: Name,
+    trainee_username: Username
+}
+
+impl SupervisorAssignment {
+    /// Assigns a supervisor to a trainee. Both must be regular users and cannot be the same user.
+    ///
+    /// # Returns
+    ///
+    /// Whether the assignment was made, which is also `true` if it already existed.
+    pub async fn insert(pool: &PgPool, supervisor_id: i32, trainee_id: i32) -> Result<bool> {
+        let row = sqlx::query!(
+            r#"
+            WITH inserted AS (
+                INSERT INTO supervisors (supervisor_id, trainee_id)
+                SELECT $1::INT, $2::INT
+                WHERE $1 <> $2 AND (SELECT COUNT(*) FROM users WHERE id IN ($1, $2) AND NOT is_admin) = 2
+                ON CONFLICT DO NOTHING
+                RETURNING 1
+            )
+            SELECT EXISTS(SELECT 1 FROM inserted) OR EXISTS(SELECT 1 FROM supervisors WHERE supervisor_id = $1 AND trainee_id = $2) AS "assigned!";
+            "#,
+            supervisor_id,
+            trainee_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(row.assigned)
+    }
+
+    /// Removes a supervisor from a trainee.
+    ///
+    /// # Returns
+    ///
+    /// Whether the assignment existed.
+    pub async fn delete(pool: &PgPool, supervisor_id: i32, trainee_id: i32) -> Result<bool> {
+        let result = sqlx::query!(
+            "DELETE FROM supervisors WHERE supervisor_id = $1 AND trainee_id = $2;",
+            supervisor_id,
+            trainee_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Fetches every supervisor assignment, ordered by supervisor.
+    pub async fn fetch_all(pool: &PgPool) -> Result<Box<[Self]>> {
+        let assignments = sqlx::query_as!(
+            SupervisorAssignment,
+            r#"
+            SELECT
+                supervisor.id AS supervisor_id, supervisor.name AS supervisor_name, supervisor.username AS supervisor_username,
+                trainee.id AS trainee_id, trainee.name AS trainee_name, trainee.username AS trainee_username
+            FROM supervisors
+            JOIN users supervisor ON supervisor.id = supervisors.supervisor_id
+            JOIN users trainee ON trainee.id = supervisors.trainee_id
+            ORDER BY supervisor.name, trainee.name;
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(assignments.into_boxed_slice())
+    }
+}
@@ -1,6 +1,7 @@
-use super::prelude::*;
+use super::{prelude::*, verification::VerificationStatus};
 use crate::utilities::parsable::Comment;
 use std::collections::HashMap;
+use sqlx::{Postgres, Transaction};
 use anyhow::anyhow;
 
 #[derive(Serialize)]
@@ -11,7 +12,12 @@ struct UserTaskModel {
     subtask_id: i32,
     rotation_id: i32,
     is_completed: bool,
-    comment: Comment
+    comment: Comment,
+    status: VerificationStatus,
+    encounter_count: i64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>
 }
 
 #[derive(Serialize)]
@@ -23,6 +29,18 @@ pub struct UserTask<S> {
     _marker: PhantomData<S>
 }
 
+/// A change to the completion or comment of a user task, holding the state of the task after the change.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTaskChange {
+    id: i32,
+    user_task_id: i32,
+    is_completed: bool,
+    comment: Comment,
+    changed_by: Option<i32>,
+    changed_at: DateTime<Utc>
+}
+
 impl<S> UserTask<S> {
     #[inline(always)]
     pub fn id(&self) -> i32 {
@@ -38,10 +56,37 @@ impl<S> UserTask<S> {
     pub fn rotation_id(&self) -> i32 {
         self.model.rotation_id
     }
+
+    #[inline(always)]
+    pub fn is_completed(&self) -> bool {
+        self.model.is_completed
+    }
+
+    #[inline(always)]
+    pub fn encounter_count(&self) -> i64 {
+        self.model.encounter_count
+    }
+
+    #[inline(always)]
+    pub fn comment(&self) -> &Comment {
+        &self.model.comment
+    }
+
+    #[inline(always)]
+    pub fn status(&self) -> VerificationStatus {
+        self.model.status
+    }
+
+    #[inline(always)]
+    pub fn completed_at(&self) -> Option<DateTime<Utc>> {
+        self.model.completed_at
+    }
 }
 
 impl UserTask<Unsynced> {
     pub fn new(user_id: i32, subtask_id: i32, rotation_id: i32, is_completed: bool, comment: Comment) -> Self {
+        let now = Utc::now();
+
         Self {
             model: UserTaskModel {
                 id: -1,
@@ -49,18 +94,29 @@ impl UserTask<Unsynced> {
                 subtask_id,
                 rotation_id,
                 is_completed,
-                comment
+                comment,
+                status: VerificationStatus::Draft,
+                encounter_count: 0,
+                created_at: now,
+                updated_at: now,
+                completed_at: None
             },
             _marker: PhantomData
         }
     }
 
+    /// Inserts the user task and starts its history. Subtasks that require more than one encounter always start out incomplete.
     pub async fn insert(self, pool: &PgPool) -> Result<UserTask<Synced>> {
+        let mut transaction = pool.begin().await?;
+
         let row = sqlx::query!(
             r#"
-            INSERT INTO user_tasks (user_id, subtask_id, rotation_id, is_completed, comment)
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING id;
+            WITH completion AS (
+                SELECT $4 AND required_count = 1 AS is_completed FROM subtasks WHERE id = $2
+            )
+            INSERT INTO user_tasks (user_id, subtask_id, rotation_id, is_completed, comment, completed_at)
+            SELECT $1, $2, $3, is_completed, $5, CASE WHEN is_completed THEN NOW() END FROM completion
+            RETURNING id, is_completed, created_at, updated_at, completed_at;
             "#,
             self.model.user_id,
             self.model.subtask_id,
@@ -68,17 +124,34 @@ impl UserTask<Unsynced> {
             self.model.is_completed,
             self.model.comment.as_str()
         )
-        .fetch_one(pool)
+        .fetch_one(&mut *transaction)
         .await?;
 
+        sqlx::query!(
+            "INSERT INTO user_task_changes (user_task_id, is_completed, comment, changed_by) VALUES ($1, $2, $3, $4);",
+            row.id,
+            row.is_completed,
+            self.model.comment.as_str(),
+            self.model.user_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
         Ok(UserTask {
             model: UserTaskModel {
                 id: row.id,
                 user_id: self.model.user_id,
                 subtask_id: self.model.subtask_id,
                 rotation_id: self.model.rotation_id,
-                is_completed: self.model.is_completed,
-                comment: self.model.comment
+                is_completed: row.is_completed,
+                comment: self.model.comment,
+                status: VerificationStatus::Draft,
+                encounter_count: 0,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+                completed_at: row.completed_at
             },
             _marker: PhantomData
         })
@@ -113,11 +186,82 @@ impl UserTask<Synced> {
         }
     }
 
-    pub async fn fetch_as_map(pool: &PgPool, user_id: i32, rotation_id: i32) -> Result<HashMap<i32, Self>> {        
+    /// Sets the completion and comment of a user task and records the change in its history, if either differs from the current ones.
+    ///
+    /// Completing the task sets its completion time, and any change returns it to a draft since a submission or verification only applies to what was submitted.
+    ///
+    /// # Parameters
+    ///
+    /// * `transaction` - The transaction to use for the operation.
+    /// * `id` - The ID of the user task.
+    /// * `is_completed` - The new completion of the task.
+    /// * `comment` - The new comment of the task.
+    /// * `changed_by` - The ID of the user making the change, or `None` if it is derived from other data.
+    pub(super) async fn set_progress(transaction: &mut Transaction<'_, Postgres>, id: i32, is_completed: bool, comment: &str, changed_by: Option<i32>) -> Result<()> {
+        sqlx::query!(
+            r#"
+            WITH updated AS (
+                UPDATE user_tasks
+                SET
+                    is_completed = $2,
+                    comment = $3,
+                    status = 'draft',
+                    completed_at = CASE WHEN NOT $2 THEN NULL WHEN is_completed THEN completed_at ELSE NOW() END,
+                    updated_at = NOW()
+                WHERE id = $1 AND ($2 <> is_completed OR $3 <> comment)
+                RETURNING id
+            )
+            INSERT INTO user_task_changes (user_task_id, is_completed, comment, changed_by)
+            SELECT id, $2, $3, $4 FROM updated;
+            "#,
+            id,
+            is_completed,
+            comment,
+            changed_by
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Checks whether a user may see a user task, which they can if they own it, supervise its trainee or are a director.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `id` - The ID of the user task.
+    /// * `viewer_id` - The ID of the user viewing the task.
+    /// * `is_director` - Whether the viewer may see any task.
+    pub async fn is_visible_to(pool: &PgPool, id: i32, viewer_id: i32, is_director: bool) -> Result<bool> {
+        let record = sqlx::query!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM user_tasks
+                WHERE id = $1 AND (
+                    $3 OR user_id = $2
+                    OR EXISTS(SELECT 1 FROM supervisors WHERE supervisor_id = $2 AND trainee_id = user_tasks.user_id)
+                )
+            ) AS "exists!";
+            "#,
+            id,
+            viewer_id,
+            is_director
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(record.exists)
+    }
+
+    pub async fn fetch_as_map(pool: &PgPool, user_id: i32, rotation_id: i32) -> Result<HashMap<i32, Self>> {
         let user_tasks = sqlx::query_as!(
             UserTaskModel,
             r#"
-            SELECT * 
+            SELECT
+                id, user_id, subtask_id, rotation_id, is_completed, comment, status,
+                (SELECT COUNT(*) FROM user_task_encounters WHERE user_task_id = user_tasks.id) AS "encounter_count!",
+                created_at, updated_at, completed_at
             FROM user_tasks
             WHERE user_id = $1 AND rotation_id = $2;
             "#,
@@ -135,25 +279,67 @@ impl UserTask<Synced> {
         Ok(map)
     }
 
+    /// Updates the completion and comment of a user task.
+    /// The completion of subtasks that require more than one encounter is derived from the logged encounters, so it is left unchanged.
     pub async fn update(pool: &PgPool, id: i32, user_id: i32, is_completed: bool, comment: &str) -> Result<()> {
-        let update_query = sqlx::query!(
+        let mut transaction = pool.begin().await?;
+
+        let row = sqlx::query!(
             r#"
-            UPDATE user_tasks
-            SET is_completed = $1, comment = $2
-            WHERE id = $3 AND user_id = $4;
+            SELECT user_tasks.is_completed, subtasks.required_count
+            FROM user_tasks
+            JOIN subtasks ON subtasks.id = user_tasks.subtask_id
+            WHERE user_tasks.id = $1 AND user_tasks.user_id = $2
+            FOR UPDATE OF user_tasks;
             "#,
-            is_completed,
-            comment,
             id,
             user_id
         )
-        .execute(pool)
-        .await?;
+        .fetch_optional(&mut *transaction)
+        .await?
+        .ok_or_else(|| anyhow!("User task does not exist."))?;
 
-        if update_query.rows_affected() == 0 {
-            return Err(anyhow!("User task does not exist."));
-        }
+        let is_completed = match row.required_count {
+            1 => is_completed,
+            _ => row.is_completed
+        };
+
+        Self::set_progress(&mut transaction, id, is_completed, comment, Some(user_id)).await?;
+        transaction.commit().await?;
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Fetches the changes made to a user task, oldest first.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `id` - The ID of the user task.
+    /// * `viewer_id` - The ID of the user viewing the history.
+    /// * `is_director` - Whether the viewer may view the history of any task.
+    ///
+    /// # Returns
+    ///
+    /// The changes, or `None` if there is no such user task the viewer may see.
+    pub async fn fetch_history(pool: &PgPool, id: i32, viewer_id: i32, is_director: bool) -> Result<Option<Box<[UserTaskChange]>>> {
+        if !Self::is_visible_to(pool, id, viewer_id, is_director).await? {
+            return Ok(None);
+        }
+
+        let changes = sqlx::query_as!(
+            UserTaskChange,
+            r#"
+            SELECT id, user_task_id, is_completed, comment, changed_by, changed_at
+            FROM user_task_changes
+            WHERE user_task_id = $1
+            ORDER BY changed_at, id;
+            "#,
+            id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(Some(changes.into_boxed_slice()))
+    }
+}
@@ -0,0 +1,261 @@
+use super::{prelude::*, user_task::UserTask};
+use crate::utilities::parsable::{Comment, EntryTitle, Name, Username};
+use sqlx::{Postgres, Transaction};
+
+/// Where a user task is in the verification workflow.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum VerificationStatus {
+    Draft,
+    Submitted,
+    Verified,
+    Returned
+}
+
+impl VerificationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Submitted => "submitted",
+            Self::Verified => "verified",
+            Self::Returned => "returned"
+        }
+    }
+
+    /// Checks whether a user task can move from this status to another.
+    ///
+    /// Trainees submit drafts and returned tasks, and supervisors verify or return submitted tasks.
+    /// Tasks only go back to being drafts when their completion changes.
+    fn can_transition_to(&self, next: Self) -> bool {
+        matches!(
+            (self, next),
+            (Self::Draft | Self::Returned, Self::Submitted) | (Self::Submitted, Self::Verified | Self::Returned)
+        )
+    }
+}
+
+impl From<String> for VerificationStatus {
+    fn from(value: String) -> Self {
+        // The database constrains the status, so anything else is unreachable.
+        match value.as_str() {
+            "submitted" => Self::Submitted,
+            "verified" => Self::Verified,
+            "returned" => Self::Returned,
+            _ => Self::Draft
+        }
+    }
+}
+
+/// A decision made on a user task, which together with the others on the task makes up its history.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationDecision {
+    id: i32,
+    user_task_id: i32,
+    status: VerificationStatus,
+    reason: String,
+    decided_by: Option<i32>,
+    decided_at: DateTime<Utc>
+}
+
+/// A submitted user task waiting for a supervisor or director to verify it.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingVerification {
+    user_task_id: i32,
+    user_id: i32,
+    username: Username,
+    name: Name,
+    rotation_id: i32,
+    subtask_id: i32,
+    subtask_title: EntryTitle,
+    comment: Comment,
+    submitted_at: DateTime<Utc>
+}
+
+/// The outcome of attempting to move a user task to another status.
+pub enum Transition {
+    /// The task moved to the new status and the decision was recorded.
+    Applied(VerificationDecision),
+
+    /// The task does not exist or the user is not allowed to make decisions on it.
+    NotFound,
+
+    /// The task cannot move to the new status from its current one.
+    Invalid
+}
+
+impl VerificationDecision {
+    /// Moves a user task to another status and records the decision.
+    ///
+    /// The user task must already be locked by the transaction and the transition checked.
+    async fn apply(transaction: &mut Transaction<'_, Postgres>, user_task_id: i32, status: VerificationStatus, reason: &str, decided_by: i32) -> Result<Self> {
+        sqlx::query!(
+            "UPDATE user_tasks SET status = $1 WHERE id = $2;",
+            status.as_str(),
+            user_task_id
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        let decision = sqlx::query_as!(
+            VerificationDecision,
+            r#"
+            INSERT INTO user_task_verifications (user_task_id, status, reason, decided_by)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, user_task_id, status, reason, decided_by, decided_at;
+            "#,
+            user_task_id,
+            status.as_str(),
+            reason,
+            decided_by
+        )
+        .fetch_one(&mut **transaction)
+        .await?;
+
+        Ok(decision)
+    }
+
+    /// Submits one of a trainee's own completed user tasks for verification.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `user_id` - The ID of the trainee submitting the task.
+    /// * `user_task_id` - The ID of the user task to submit.
+    pub async fn submit(pool: &PgPool, user_id: i32, user_task_id: i32) -> Result<Transition> {
+        let mut transaction = pool.begin().await?;
+
+        let row = sqlx::query!(
+            "SELECT status, is_completed FROM user_tasks WHERE id = $1 AND user_id = $2 FOR UPDATE;",
+            user_task_id,
+            user_id
+        )
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        let row = match row {
+            Some(row) => row,
+            None => return Ok(Transition::NotFound)
+        };
+
+        if !row.is_completed || !VerificationStatus::from(row.status).can_transition_to(VerificationStatus::Submitted) {
+            return Ok(Transition::Invalid);
+        }
+
+        let decision = Self::apply(&mut transaction, user_task_id, VerificationStatus::Submitted, "", user_id).await?;
+        transaction.commit().await?;
+
+        Ok(Transition::Applied(decision))
+    }
+
+    /// Verifies or returns a submitted user task.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `reviewer_id` - The ID of the supervisor or director making the decision.
+    /// * `is_director` - Whether the reviewer may decide on the tasks of any trainee, rather than only those they supervise.
+    /// * `user_task_id` - The ID of the user task to decide on.
+    /// * `status` - Either `Verified` or `Returned`.
+    /// * `reason` - Why the task was returned, which is empty for verified tasks.
+    pub async fn decide(pool: &PgPool, reviewer_id: i32, is_director: bool, user_task_id: i32, status: VerificationStatus, reason: &str) -> Result<Transition> {
+        let mut transaction = pool.begin().await?;
+
+        let row = sqlx::query!(
+            r#"
+            SELECT status FROM user_tasks
+            WHERE id = $1 AND ($3 OR EXISTS(SELECT 1 FROM supervisors WHERE supervisor_id = $2 AND trainee_id = user_tasks.user_id))
+            FOR UPDATE;
+            "#,
+            user_task_id,
+            reviewer_id,
+            is_director
+        )
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        let row = match row {
+            Some(row) => row,
+            None => return Ok(Transition::NotFound)
+        };
+
+        if status == VerificationStatus::Submitted || !VerificationStatus::from(row.status).can_transition_to(status) {
+            return Ok(Transition::Invalid);
+        }
+
+        let decision = Self::apply(&mut transaction, user_task_id, status, reason, reviewer_id).await?;
+        transaction.commit().await?;
+
+        Ok(Transition::Applied(decision))
+    }
+
+    /// Fetches the decisions made on a user task, oldest first.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `user_task_id` - The ID of the user task.
+    /// * `viewer_id` - The ID of the user viewing the history, who must own the task or supervise its trainee unless they are a director.
+    /// * `is_director` - Whether the viewer may view the history of any task.
+    ///
+    /// # Returns
+    ///
+    /// The decisions, or `None` if there is no such user task the viewer may see.
+    pub async fn fetch_history(pool: &PgPool, user_task_id: i32, viewer_id: i32, is_director: bool) -> Result<Option<Box<[Self]>>> {
+        if !UserTask::is_visible_to(pool, user_task_id, viewer_id, is_director).await? {
+            return Ok(None);
+        }
+
+        let decisions = sqlx::query_as!(
+            VerificationDecision,
+            r#"
+            SELECT id, user_task_id, status, reason, decided_by, decided_at
+            FROM user_task_verifications
+            WHERE user_task_id = $1
+            ORDER BY decided_at, id;
+            "#,
+            user_task_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(Some(decisions.into_boxed_slice()))
+    }
+}
+
+impl PendingVerification {
+    /// Fetches the submitted user tasks a reviewer can decide on, oldest submission first.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool` - The Postgres connection pool to use for the operation.
+    /// * `reviewer_id` - The ID of the supervisor or director.
+    /// * `is_director` - Whether to include the tasks of every trainee, rather than only those the reviewer supervises.
+    pub async fn fetch_inbox(pool: &PgPool, reviewer_id: i32, is_director: bool) -> Result<Box<[Self]>> {
+        let pending = sqlx::query_as!(
+            PendingVerification,
+            r#"
+            SELECT
+                user_tasks.id AS user_task_id, users.id AS user_id, users.username, users.name,
+                user_tasks.rotation_id, subtasks.id AS subtask_id, subtasks.title AS subtask_title, user_tasks.comment,
+                (
+                    SELECT MAX(decided_at) FROM user_task_verifications
+                    WHERE user_task_id = user_tasks.id AND status = 'submitted'
+                ) AS "submitted_at!"
+            FROM user_tasks
+            JOIN users ON users.id = user_tasks.user_id
+            JOIN subtasks ON subtasks.id = user_tasks.subtask_id
+            WHERE user_tasks.status = 'submitted'
+            AND ($2 OR EXISTS(SELECT 1 FROM supervisors WHERE supervisor_id = $1 AND trainee_id = user_tasks.user_id))
+            ORDER BY 9, user_tasks.id;
+            "#,
+            reviewer_id,
+            is_director
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(pending.into_boxed_slice())
+    }
+}
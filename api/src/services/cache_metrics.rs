@@ -0,0 +1,11 @@
+use crate::services::prelude::*;
+
+/// Reports how the memory cache of this instance has been used, so that its time to live and capacity can be tuned.
+#[actix_web::get("/cache/metrics")]
+pub(super) async fn get_cache_metrics(claim: JwtUserClaim, memory_cache: Data<MemoryCache>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    HttpResponse::Ok().json(memory_cache.metrics())
+}
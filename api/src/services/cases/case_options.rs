@@ -0,0 +1,50 @@
+use crate::models::case_log::{CaseLogField, CaseLogOption};
+use crate::utilities::parsable::EntryTitle;
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateCaseOptionQuery {
+    field: CaseLogField,
+    value: EntryTitle
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeleteCaseOptionQuery {
+    option_id: i32
+}
+
+#[actix_web::get("/options")]
+pub(super) async fn get_case_options(_claim: JwtUserClaim, pool: Data<PgPool>) -> impl Responder {
+    match CaseLogOption::fetch_all(&pool).await {
+        Ok(options) => HttpResponse::Ok().json(options),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::post("/options/create")]
+pub(super) async fn create_case_option(claim: JwtUserClaim, pool: Data<PgPool>, create_case_option_query: Json<CreateCaseOptionQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match CaseLogOption::insert(&pool, create_case_option_query.field, &create_case_option_query.value).await {
+        Ok(Some(option)) => HttpResponse::Created().json(option),
+        Ok(None) => HttpResponse::Conflict().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::delete("/options/delete")]
+pub(super) async fn delete_case_option(claim: JwtUserClaim, pool: Data<PgPool>, delete_case_option_query: Json<DeleteCaseOptionQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match CaseLogOption::delete(&pool, delete_case_option_query.option_id).await {
+        Ok(true) => HttpResponse::Ok().finish(),
+        Ok(false) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
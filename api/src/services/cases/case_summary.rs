@@ -0,0 +1,26 @@
+use crate::models::case_log::CaseLogSummary;
+use crate::services::prelude::*;
+use chrono::NaiveDate;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CaseSummaryQuery {
+    user_id: Option<i32>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>
+}
+
+/// Counts logged cases by each enumerated field.
+/// Admins can count every trainee's cases or filter by user, while trainees only count their own.
+#[actix_web::get("/summary")]
+pub(super) async fn get_case_summary(claim: JwtUserClaim, pool: Data<PgPool>, query: Query<CaseSummaryQuery>) -> impl Responder {
+    let user_id = match claim.sub.is_admin {
+        true => query.user_id,
+        false => Some(claim.sub.id)
+    };
+
+    match CaseLogSummary::fetch(&pool, user_id, query.from, query.to).await {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
@@ -0,0 +1,26 @@
+use crate::models::case_log::CaseLogEntry;
+use crate::services::prelude::*;
+
+#[actix_web::get("")]
+pub(super) async fn get_own_cases(claim: JwtUserClaim, pool: Data<PgPool>) -> impl Responder {
+    if claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match CaseLogEntry::fetch_all(&pool, claim.sub.id).await {
+        Ok(entries) => HttpResponse::Ok().json(entries),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::get("/users/{user_id}")]
+pub(super) async fn get_user_cases(claim: JwtUserClaim, pool: Data<PgPool>, user_id: Path<i32>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match CaseLogEntry::fetch_all(&pool, *user_id).await {
+        Ok(entries) => HttpResponse::Ok().json(entries),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
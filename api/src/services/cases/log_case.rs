@@ -0,0 +1,75 @@
+use crate::models::case_log::{CaseLogEntry, CaseLogError, CaseLogInput};
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateCaseQuery {
+    case_id: i32,
+
+    #[serde(flatten)]
+    case: CaseLogInput
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeleteCaseQuery {
+    case_id: i32
+}
+
+#[derive(Serialize)]
+struct CaseLogErrorsResponse {
+    errors: Vec<CaseLogError>
+}
+
+macro_rules! validate_case {
+    ($pool:ident, $case:expr) => {
+        match $case.validate(&$pool).await {
+            Ok(errors) => if !errors.is_empty() {
+                return HttpResponse::BadRequest().json(CaseLogErrorsResponse { errors });
+            },
+            Err(_) => return HttpResponse::InternalServerError().finish()
+        }
+    };
+}
+
+#[actix_web::post("/create")]
+pub(super) async fn log_case(claim: JwtUserClaim, pool: Data<PgPool>, case: Json<CaseLogInput>) -> impl Responder {
+    if claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    validate_case!(pool, case);
+
+    match CaseLogEntry::insert(&pool, claim.sub.id, &case).await {
+        Ok(entry) => HttpResponse::Created().json(entry),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::patch("/update")]
+pub(super) async fn update_case(claim: JwtUserClaim, pool: Data<PgPool>, update_case_query: Json<UpdateCaseQuery>) -> impl Responder {
+    if claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    validate_case!(pool, update_case_query.case);
+
+    match CaseLogEntry::update(&pool, claim.sub.id, update_case_query.case_id, &update_case_query.case).await {
+        Ok(Some(entry)) => HttpResponse::Ok().json(entry),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::delete("/delete")]
+pub(super) async fn delete_case(claim: JwtUserClaim, pool: Data<PgPool>, delete_case_query: Json<DeleteCaseQuery>) -> impl Responder {
+    if claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match CaseLogEntry::delete(&pool, claim.sub.id, delete_case_query.case_id).await {
+        Ok(true) => HttpResponse::Ok().finish(),
+        Ok(false) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
@@ -0,0 +1,4 @@
+pub(super) mod log_case;
+pub(super) mod get_cases;
+pub(super) mod case_options;
+pub(super) mod case_summary;
\ No newline at end of file
@@ -0,0 +1,24 @@
+use crate::models::competency::{is_duplicate, Competency};
+use crate::utilities::parsable::{Description, EntryTitle, Name};
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateCompetencyQuery {
+    framework: Name,
+    title: EntryTitle,
+    description: Description
+}
+
+#[actix_web::post("/create")]
+pub(super) async fn create_competency(claim: JwtUserClaim, pool: Data<PgPool>, create_competency_query: Json<CreateCompetencyQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match Competency::insert(&pool, &create_competency_query.framework, &create_competency_query.title, &create_competency_query.description).await {
+        Ok(competency) => HttpResponse::Created().json(competency),
+        Err(error) if is_duplicate(&error) => HttpResponse::Conflict().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
@@ -0,0 +1,21 @@
+use crate::models::competency::Competency;
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeleteCompetencyQuery {
+    competency_id: i32
+}
+
+#[actix_web::delete("/delete")]
+pub(super) async fn delete_competency(claim: JwtUserClaim, pool: Data<PgPool>, delete_competency_query: Json<DeleteCompetencyQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match Competency::delete(&pool, delete_competency_query.competency_id).await {
+        Ok(true) => HttpResponse::Ok().finish(),
+        Ok(false) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
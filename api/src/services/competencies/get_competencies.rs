@@ -0,0 +1,19 @@
+use crate::models::competency::Competency;
+use crate::services::prelude::*;
+
+#[actix_web::get("")]
+pub(super) async fn get_competencies(_claim: JwtUserClaim, pool: Data<PgPool>) -> impl Responder {
+    match Competency::fetch_all(&pool).await {
+        Ok(competencies) => HttpResponse::Ok().json(competencies),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+/// Gets the competencies each subtask of a rotation is tagged with, keyed by subtask ID.
+#[actix_web::get("/subtasks/{rotation_id}")]
+pub(super) async fn get_subtask_competencies(_claim: JwtUserClaim, pool: Data<PgPool>, rotation_id: Path<i32>) -> impl Responder {
+    match Competency::fetch_subtask_tags(&pool, *rotation_id).await {
+        Ok(tags) => HttpResponse::Ok().json(tags),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
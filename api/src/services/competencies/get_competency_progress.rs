@@ -0,0 +1,34 @@
+use crate::models::competency::{Competency, CompetencyProgress};
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GetCompetencyProgressQuery {
+    user_id: Option<i32>
+}
+
+#[derive(Serialize)]
+struct GetCompetencyProgressResponse {
+    competencies: Box<[Competency]>,
+    progress: Box<[CompetencyProgress]>
+}
+
+/// Gets each trainee's completion of every competency across all rotations.
+/// Admins can get every trainee's progress or filter by user, while trainees only get their own.
+#[actix_web::get("/progress")]
+pub(super) async fn get_competency_progress(claim: JwtUserClaim, pool: Data<PgPool>, query: Query<GetCompetencyProgressQuery>) -> impl Responder {
+    let user_id = match claim.sub.is_admin {
+        true => query.user_id,
+        false => Some(claim.sub.id)
+    };
+
+    let competencies = match Competency::fetch_all(&pool).await {
+        Ok(competencies) => competencies,
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    };
+
+    match Competency::fetch_progress(&pool, user_id).await {
+        Ok(progress) => HttpResponse::Ok().json(GetCompetencyProgressResponse { competencies, progress }),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
@@ -0,0 +1,6 @@
+pub(super) mod create_competency;
+pub(super) mod update_competency;
+pub(super) mod delete_competency;
+pub(super) mod get_competencies;
+pub(super) mod tag_subtask;
+pub(super) mod get_competency_progress;
\ No newline at end of file
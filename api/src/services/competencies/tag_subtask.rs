@@ -0,0 +1,29 @@
+use crate::models::{competency::Competency, entry_structure::Subtask};
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TagSubtaskQuery {
+    subtask_id: i32,
+    competency_ids: Box<[i32]>
+}
+
+/// Replaces the competencies a subtask is tagged with.
+#[actix_web::patch("/subtasks")]
+pub(super) async fn tag_subtask(claim: JwtUserClaim, pool: Data<PgPool>, tag_subtask_query: Json<TagSubtaskQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match Subtask::fetch_rotation_id(&pool, tag_subtask_query.subtask_id).await {
+        Ok(Some(_)) => {},
+        Ok(None) => return HttpResponse::NotFound().finish(),
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    }
+
+    match Competency::tag_subtask(&pool, tag_subtask_query.subtask_id, &tag_subtask_query.competency_ids).await {
+        Ok(true) => HttpResponse::Ok().finish(),
+        Ok(false) => HttpResponse::BadRequest().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
@@ -0,0 +1,32 @@
+use crate::models::competency::{is_duplicate, Competency};
+use crate::utilities::parsable::{Description, EntryTitle, Name};
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateCompetencyQuery {
+    competency_id: i32,
+    framework: Name,
+    title: EntryTitle,
+    description: Description
+}
+
+#[actix_web::patch("/update")]
+pub(super) async fn update_competency(claim: JwtUserClaim, pool: Data<PgPool>, update_competency_query: Json<UpdateCompetencyQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match Competency::update(
+        &pool,
+        update_competency_query.competency_id,
+        &update_competency_query.framework,
+        &update_competency_query.title,
+        &update_competency_query.description
+    ).await {
+        Ok(Some(competency)) => HttpResponse::Ok().json(competency),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(error) if is_duplicate(&error) => HttpResponse::Conflict().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
@@ -1,24 +1,83 @@
 use super::*;
-use actix_web::web::{scope, ServiceConfig};
-use actix_governor::{governor::{clock::QuantaInstant, middleware::NoOpMiddleware}, Governor, GovernorConfigBuilder, PeerIpKeyExtractor};
+use crate::middlewares::rate_limiter::{RateLimit, RateLimiter, RateLimiterBackend};
+use crate::utilities::trusted_proxies::TrustedProxies;
+use std::{env::var, sync::Arc};
+use actix_web::web::{get, patch, post, resource, scope, ServiceConfig};
+use sqlx::PgPool;
 
 #[cfg(feature = "monolith")]
 use actix_files::Files;
 
-fn governor(per_second: u64, burst_size: u32) -> Governor<PeerIpKeyExtractor, NoOpMiddleware<QuantaInstant>> {
-    let cfg = match cfg!(feature = "production") {
-        true => GovernorConfigBuilder::default()
-            .per_second(per_second)
-            .burst_size(burst_size)
-            .finish()
-            .unwrap(),
-        false => GovernorConfigBuilder::default()
-            .permissive(true)
-            .finish()
-            .unwrap()
-    };
-    
-    Governor::new(&cfg)
+/// The rate limiters applied to sensitive routes.
+///
+/// These are built once and shared by every worker so that limits are counted per instance rather than per worker.
+#[derive(Clone)]
+pub struct RateLimiters {
+    register: RateLimiter,
+    login: RateLimiter,
+    reset_password: RateLimiter,
+    tasks: RateLimiter,
+    entries: RateLimiter
+}
+
+impl RateLimiters {
+    /// Builds the rate limiters from the environment.
+    ///
+    /// * `TRUSTED_PROXIES` - Comma separated addresses or CIDR ranges whose `X-Forwarded-For` header is trusted.
+    /// * `RATE_LIMIT_BACKEND` - Either `memory` (default) or `postgres` to share limits between instances.
+    /// * `RATE_LIMIT_<ROUTE>` - A limit in the form `<period seconds>:<burst size>` overriding the default for a route.
+    ///
+    /// Rate limiting is only enforced when the production feature is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A connection pool to the database, used by the `postgres` backend.
+    pub fn from_env(pool: &PgPool) -> Self {
+        if !cfg!(feature = "production") {
+            return Self {
+                register: RateLimiter::permissive(),
+                login: RateLimiter::permissive(),
+                reset_password: RateLimiter::permissive(),
+                tasks: RateLimiter::permissive(),
+                entries: RateLimiter::permissive()
+            };
+        }
+
+        let trusted_proxies = Arc::new(
+            TrustedProxies::parse(var("TRUSTED_PROXIES").unwrap_or_default().as_str())
+                .expect("Expected trusted proxies to be a comma separated list of addresses or CIDR ranges.")
+        );
+
+        let backend = match var("RATE_LIMIT_BACKEND").as_deref() {
+            Ok("postgres") => RateLimiterBackend::Postgres(pool.clone()),
+            Ok("memory") | Err(_) => RateLimiterBackend::Memory,
+            Ok(_) => panic!("Expected rate limit backend to be either memory or postgres.")
+        };
+
+        let limiter = |name: &'static str, env_key: &str, default: RateLimit| {
+            let limit = match var(env_key) {
+                Ok(limit) => limit.parse().expect("Expected rate limit in the form <period seconds>:<burst size>."),
+                Err(_) => default
+            };
+
+            RateLimiter::new(name, limit, backend.clone(), trusted_proxies.clone())
+        };
+
+        Self {
+            register: limiter("register", "RATE_LIMIT_REGISTER", RateLimit::new(4, 2)),
+            login: limiter("login", "RATE_LIMIT_LOGIN", RateLimit::new(4, 3)),
+            reset_password: limiter("reset_password", "RATE_LIMIT_RESET_PASSWORD", RateLimit::new(3, 2)),
+            tasks: limiter("tasks", "RATE_LIMIT_TASKS", RateLimit::new(1, 15)),
+            entries: limiter("entries", "RATE_LIMIT_ENTRIES", RateLimit::new(1, 15))
+        }
+    }
+
+    /// Drops stale rate limiting state from every limiter.
+    pub async fn purge_expired(&self) {
+        for limiter in [&self.register, &self.login, &self.reset_password, &self.tasks, &self.entries] {
+            let _ = limiter.purge_expired().await;
+        }
+    }
 }
 
 /// Configures the services for the application.
@@ -26,58 +85,171 @@ fn governor(per_second: u64, burst_size: u32) -> Governor<PeerIpKeyExtractor, No
 /// # Arguments
 /// 
 /// * `cfg` - The service configuration to configure.
-pub fn config(cfg: &mut ServiceConfig) {
+/// * `rate_limiters` - The rate limiters to apply to sensitive routes.
+pub fn config(cfg: &mut ServiceConfig, rate_limiters: &RateLimiters) {
     cfg.service(
         scope("/api")
             .service(healthcheck::healthcheck)
+            .service(cache_metrics::get_cache_metrics)
             .service(
                 scope("/users")
-                    .service(users::register_user::register_user)
-                        .wrap(governor(4, 2))
-                    .service(users::login_user::login_user)
-                        .wrap(governor(4, 3))       
-                    .service(users::reset_user_password::reset_password)
-                        .wrap(governor(3, 2))
+                    // Each limiter only wraps its own resource, so that other routes such as the session do not draw from its budget.
+                    .service(
+                        resource("/register")
+                            .wrap(rate_limiters.register.clone())
+                            .route(post().to(users::register_user::register_user))
+                    )
+                    .service(
+                        resource("/login")
+                            .wrap(rate_limiters.login.clone())
+                            .route(post().to(users::login_user::login_user))
+                    )
+                    .service(
+                        resource("/reset-password")
+                            .wrap(rate_limiters.reset_password.clone())
+                            .route(post().to(users::reset_user_password::reset_password))
+                    )
                     .service(users::reset_user_password::allow_reset_password)
                     .service(users::delete_user::delete_other_user)
                     .service(users::delete_user::delete_self)
                     .service(users::get_user_session::get_user_session)
                     .service(users::search_users::search_users)
+                    .service(users::supervisors::get_supervisors)
+                    .service(users::supervisors::assign_supervisor)
+                    .service(users::supervisors::unassign_supervisor)
             )
             .service(
                 scope("/tasks")
-                    .service(tasks::create_user_task::create_user_task)
-                    .service(tasks::update_user_task::update_user_task)
-                    .service(tasks::get_user_tasks::get_user_tasks)
-                    .service(tasks::get_user_tasks::get_own_tasks)
-                       .wrap(governor(1, 15))
+                    .service(
+                        scope("/encounters")
+                            .service(tasks::encounters::log_encounter)
+                            .service(tasks::encounters::delete_encounter)
+                            .service(tasks::encounters::get_encounters)
+                    )
+                    .service(
+                        scope("/verifications")
+                            .service(tasks::verifications::submit_user_task)
+                            .service(tasks::verifications::verify_user_task)
+                            .service(tasks::verifications::return_user_task)
+                            .service(tasks::verifications::get_verification_history)
+                            .service(tasks::verifications::get_verification_inbox)
+                    )
+                    // Only the routes for making and fetching progress share the tasks budget, so that encounters and verifications do not draw from it.
+                    // Resources with a parameter in their path are registered after those whose path it would also match.
+                    .service(
+                        resource("/create")
+                            .wrap(rate_limiters.tasks.clone())
+                            .route(post().to(tasks::create_user_task::create_user_task))
+                    )
+                    .service(
+                        resource("/update")
+                            .wrap(rate_limiters.tasks.clone())
+                            .route(patch().to(tasks::update_user_task::update_user_task))
+                    )
+                    .service(tasks::user_task_history::get_user_task_history)
+                    .service(
+                        resource("/{user_id}/{rotation_id}")
+                            .wrap(rate_limiters.tasks.clone())
+                            .route(get().to(tasks::get_user_tasks::get_user_tasks))
+                    )
+                    .service(
+                        resource("/{rotation_id}")
+                            .wrap(rate_limiters.tasks.clone())
+                            .route(get().to(tasks::get_user_tasks::get_own_tasks))
+                    )
             )
             .service(
                 scope("/entries")
-                    .service(entries::get_entries::get_entries)
-                        .wrap(governor(1, 15))
+                    .service(entries::bulk_create_entries::bulk_create_entries)
+                    .service(entries::trash::get_trash)
+                    // Only fetching the entries draws from the entries budget, so that editing a curriculum does not use it up.
+                    // It is registered after the routes whose path it would also match.
+                    .service(
+                        resource("/{rotation_id}")
+                            .wrap(rate_limiters.entries.clone())
+                            .route(get().to(entries::get_entries::get_entries))
+                    )
                     .service(
                         scope("/supertasks")
                             .service(entries::create_entries::create_supertask)
                             .service(entries::update_entries::update_supertask)
                             .service(entries::delete_entries::delete_supertask)
+                            .service(entries::trash::restore_supertask)
+                            .service(entries::reorder_entries::reorder_supertasks)
+                            .service(entries::entry_content::get_supertask_content)
+                            .service(entries::entry_content::update_supertask_content)
                     )
                     .service(
                         scope("/tasks")
                             .service(entries::create_entries::create_task)
                             .service(entries::update_entries::update_task)
                             .service(entries::delete_entries::delete_task)
+                            .service(entries::trash::restore_task)
+                            .service(entries::reorder_entries::reorder_tasks)
+                            .service(entries::move_entries::move_task)
+                            .service(entries::entry_content::get_task_content)
+                            .service(entries::entry_content::update_task_content)
                     )
                     .service(
                         scope("/subtasks")
                             .service(entries::create_entries::create_subtask)
                             .service(entries::update_entries::update_subtask)
+                            .service(entries::update_entries::update_subtask_required_count)
                             .service(entries::delete_entries::delete_subtask)
+                            .service(entries::trash::restore_subtask)
+                            .service(entries::reorder_entries::reorder_subtasks)
+                            .service(entries::move_entries::move_subtask)
+                            .service(entries::entry_content::get_subtask_content)
+                            .service(entries::entry_content::update_subtask_content)
                     )
             )
+            .service(
+                scope("/competencies")
+                    .service(competencies::get_competencies::get_competencies)
+                    .service(competencies::get_competencies::get_subtask_competencies)
+                    .service(competencies::get_competency_progress::get_competency_progress)
+                    .service(competencies::create_competency::create_competency)
+                    .service(competencies::update_competency::update_competency)
+                    .service(competencies::delete_competency::delete_competency)
+                    .service(competencies::tag_subtask::tag_subtask)
+            )
+            .service(
+                scope("/cases")
+                    .service(cases::get_cases::get_own_cases)
+                    .service(cases::get_cases::get_user_cases)
+                    .service(cases::case_summary::get_case_summary)
+                    .service(cases::case_options::get_case_options)
+                    .service(cases::case_options::create_case_option)
+                    .service(cases::case_options::delete_case_option)
+                    .service(cases::log_case::log_case)
+                    .service(cases::log_case::update_case)
+                    .service(cases::log_case::delete_case)
+            )
+            .service(
+                scope("/progress")
+                    .service(progress::get_cohort_progress::get_cohort_progress)
+                    .service(progress::export_progress::export_progress)
+                    .service(progress::get_progress_report::get_user_progress_report)
+                    .service(progress::get_progress_report::get_own_progress_report)
+                    .service(progress::get_progress::get_user_progress)
+                    .service(progress::get_progress::get_own_progress)
+            )
             .service(
                 scope("/rotations")
                     .service(rotations::create_rotation::create_rotation)
+                    .service(rotations::update_rotation::update_rotation)
+                    .service(rotations::clone_rotation::clone_rotation)
+                    .service(rotations::curriculum::export_curriculum)
+                    .service(rotations::curriculum::import_curriculum)
+                    .service(rotations::curriculum_versions::get_curriculum_versions)
+                    .service(rotations::curriculum_versions::publish_curriculum_version)
+                    .service(rotations::curriculum_versions::diff_curriculum_versions)
+                    .service(rotations::curriculum_versions::migrate_curriculum_version)
+                    .service(rotations::curriculum_versions::get_curriculum_version)
+                    .service(rotations::archive_rotation::archive_rotation)
+                    .service(rotations::archive_rotation::unarchive_rotation)
+                    .service(rotations::archive_rotation::get_archived_rotations)
+                    .service(rotations::delete_rotation::request_delete_rotation)
                     .service(rotations::delete_rotation::delete_rotation)
             )
     );
@@ -0,0 +1,54 @@
+use crate::models::{entry_structure::EntryStructure, rotation::Rotation};
+use crate::utilities::outline::{parse_csv, parse_outline, OutlineError};
+use crate::services::prelude::*;
+use actix_web::{http::header::CONTENT_TYPE, web::Bytes, HttpRequest};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BulkCreateEntriesQuery {
+    rotation_id: i32
+}
+
+#[derive(Serialize)]
+struct BulkCreateEntriesErrorResponse {
+    errors: Vec<OutlineError>
+}
+
+/// Creates a whole tree of entries from an indented outline, or from a CSV file when sent as `text/csv`.
+#[actix_web::post("/bulk")]
+pub(super) async fn bulk_create_entries(claim: JwtUserClaim, pool: Data<PgPool>, req: HttpRequest, query: Query<BulkCreateEntriesQuery>, body: Bytes) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match Rotation::exists(&pool, query.rotation_id).await {
+        Ok(exists) => if !exists {
+            return HttpResponse::BadRequest().finish();
+        },
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    }
+
+    let is_csv = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/csv"));
+
+    let supertasks = match is_csv {
+        true => parse_csv(&body),
+        false => match std::str::from_utf8(&body) {
+            Ok(text) => parse_outline(text),
+            Err(_) => return HttpResponse::BadRequest().finish()
+        }
+    };
+
+    let supertasks = match supertasks {
+        Ok(supertasks) => supertasks,
+        Err(errors) => return HttpResponse::BadRequest().json(BulkCreateEntriesErrorResponse { errors })
+    };
+
+    match EntryStructure::insert_tree(&pool, query.rotation_id, &supertasks).await {
+        Ok(entry_structure) => HttpResponse::Created().json(entry_structure),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
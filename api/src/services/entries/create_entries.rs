@@ -39,6 +39,19 @@ macro_rules! create_entry_wrapper {
     };
 }
 
+/// Checks that the parent of a new entry exists and is in the same rotation as the entry.
+macro_rules! check_parent {
+    ($parent:ident, $pool:ident, $create_entry_query:ident) => {
+        match $parent::fetch_rotation_id(&$pool, $create_entry_query.parent_id).await {
+            Ok(Some(rotation_id)) => if rotation_id != $create_entry_query.rotation_id {
+                return HttpResponse::BadRequest().finish();
+            },
+            Ok(None) => return HttpResponse::NotFound().finish(),
+            Err(_) => return HttpResponse::InternalServerError().finish()
+        }
+    };
+}
+
 #[actix_web::post("/create")]
 pub(super) async fn create_supertask(claim: JwtUserClaim, pool: Data<PgPool>, create_entry_query: Json<CreateSupertaskEntryQuery>) -> impl Responder {
     create_entry_wrapper! {claim, pool, create_entry_query.rotation_id, {
@@ -52,6 +65,8 @@ pub(super) async fn create_supertask(claim: JwtUserClaim, pool: Data<PgPool>, cr
 #[actix_web::post("/create")]
 pub(super) async fn create_task(claim: JwtUserClaim, pool: Data<PgPool>, create_entry_query: Json<CreateLowerEntryQuery>) -> impl Responder {
     create_entry_wrapper! {claim, pool, create_entry_query.rotation_id, {
+        check_parent!(Supertask, pool, create_entry_query);
+
         match Task::insert_from(&pool, create_entry_query.title.as_str(), create_entry_query.rotation_id, create_entry_query.parent_id).await {
             Ok(entry_id) => HttpResponse::Created().json(CreateEntryResponse { entry_id }),
             Err(_) => HttpResponse::InternalServerError().finish()
@@ -62,6 +77,8 @@ pub(super) async fn create_task(claim: JwtUserClaim, pool: Data<PgPool>, create_
 #[actix_web::post("/create")]
 pub(super) async fn create_subtask(claim: JwtUserClaim, pool: Data<PgPool>, create_entry_query: Json<CreateLowerEntryQuery>) -> impl Responder {
     create_entry_wrapper! {claim, pool, create_entry_query.rotation_id, {
+        check_parent!(Task, pool, create_entry_query);
+
         match Subtask::insert_from(&pool, create_entry_query.title.as_str(), create_entry_query.rotation_id, create_entry_query.parent_id).await {
             Ok(entry_id) => HttpResponse::Created().json(CreateEntryResponse { entry_id }),
             Err(_) => HttpResponse::InternalServerError().finish()
@@ -1,4 +1,4 @@
-use crate::models::entry_structure::{Supertask, Task, Subtask};
+use crate::{middlewares::if_match_extractor::IfMatchRevisions, models::entry_structure::{Supertask, Task, Subtask}};
 use crate::entry_wrapper;
 use crate::services::prelude::*;
 
@@ -9,28 +9,16 @@ struct DeleteEntryQuery {
 }
 
 #[actix_web::delete("/delete")]
-pub(super) async fn delete_supertask(claim: JwtUserClaim, pool: Data<PgPool>, delete_entry_query: Json<DeleteEntryQuery>) -> impl Responder {
-    entry_wrapper! {pool, claim, {
-        if Supertask::delete(&pool, delete_entry_query.entry_id).await.is_err() {
-            return HttpResponse::InternalServerError().finish();
-        }
-    }}
+pub(super) async fn delete_supertask(claim: JwtUserClaim, pool: Data<PgPool>, if_match: IfMatchRevisions, delete_entry_query: Json<DeleteEntryQuery>) -> impl Responder {
+    entry_wrapper! {claim, Supertask::delete(&pool, delete_entry_query.entry_id, if_match.revisions())}
 }
 
 #[actix_web::delete("/delete")]
-pub(super) async fn delete_task(claim: JwtUserClaim, pool: Data<PgPool>, delete_entry_query: Json<DeleteEntryQuery>) -> impl Responder {
-    entry_wrapper! {pool, claim, {
-        if Task::delete(&pool, delete_entry_query.entry_id).await.is_err() {
-            return HttpResponse::InternalServerError().finish();
-        }
-    }}
+pub(super) async fn delete_task(claim: JwtUserClaim, pool: Data<PgPool>, if_match: IfMatchRevisions, delete_entry_query: Json<DeleteEntryQuery>) -> impl Responder {
+    entry_wrapper! {claim, Task::delete(&pool, delete_entry_query.entry_id, if_match.revisions())}
 }
 
 #[actix_web::delete("/delete")]
-pub(super) async fn delete_subtask(claim: JwtUserClaim, pool: Data<PgPool>, delete_entry_query: Json<DeleteEntryQuery>) -> impl Responder {
-    entry_wrapper! {pool, claim, {
-        if Subtask::delete(&pool, delete_entry_query.entry_id).await.is_err() {
-            return HttpResponse::InternalServerError().finish();
-        }
-    }}
+pub(super) async fn delete_subtask(claim: JwtUserClaim, pool: Data<PgPool>, if_match: IfMatchRevisions, delete_entry_query: Json<DeleteEntryQuery>) -> impl Responder {
+    entry_wrapper! {claim, Subtask::delete(&pool, delete_entry_query.entry_id, if_match.revisions())}
 }
\ No newline at end of file
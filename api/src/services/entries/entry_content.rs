@@ -0,0 +1,69 @@
+use crate::{middlewares::if_match_extractor::{revision_tag, IfMatchRevisions}, models::{entry_content::EntryContent, entry_structure::{Supertask, Task, Subtask}}};
+use actix_web::http::header::ETag;
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateEntryContentQuery {
+    pub entry_id: i32,
+
+    #[serde(flatten)]
+    pub content: EntryContent
+}
+
+macro_rules! get_entry_content_wrapper {
+    ($fetch:expr) => {
+        match $fetch.await {
+            Ok(Some(content)) => HttpResponse::Ok()
+                .insert_header(ETag(revision_tag(content.revision)))
+                .json(content),
+            Ok(None) => HttpResponse::NotFound().finish(),
+            Err(_) => HttpResponse::InternalServerError().finish()
+        }
+    };
+}
+
+macro_rules! update_entry_content_wrapper {
+    ($claim:ident, $pool:ident, $if_match:ident, $update_entry_content_query:ident, $entry:ident) => {
+        if !$claim.sub.is_admin {
+            return HttpResponse::Unauthorized().finish();
+        }
+
+        if !$update_entry_content_query.content.is_within_limits() {
+            return HttpResponse::BadRequest().finish();
+        }
+
+        // The sanitized content is returned, since it may differ from what was sent.
+        super::conditional_response($entry::update_content(&$pool, $update_entry_content_query.entry_id, &$update_entry_content_query.content, $if_match.revisions()).await)
+    };
+}
+
+#[actix_web::get("/{entry_id}/content")]
+pub(super) async fn get_supertask_content(_claim: JwtUserClaim, pool: Data<PgPool>, entry_id: Path<i32>) -> impl Responder {
+    get_entry_content_wrapper! {Supertask::fetch_content(&pool, *entry_id)}
+}
+
+#[actix_web::get("/{entry_id}/content")]
+pub(super) async fn get_task_content(_claim: JwtUserClaim, pool: Data<PgPool>, entry_id: Path<i32>) -> impl Responder {
+    get_entry_content_wrapper! {Task::fetch_content(&pool, *entry_id)}
+}
+
+#[actix_web::get("/{entry_id}/content")]
+pub(super) async fn get_subtask_content(_claim: JwtUserClaim, pool: Data<PgPool>, entry_id: Path<i32>) -> impl Responder {
+    get_entry_content_wrapper! {Subtask::fetch_content(&pool, *entry_id)}
+}
+
+#[actix_web::patch("/content")]
+pub(super) async fn update_supertask_content(claim: JwtUserClaim, pool: Data<PgPool>, if_match: IfMatchRevisions, update_entry_content_query: Json<UpdateEntryContentQuery>) -> impl Responder {
+    update_entry_content_wrapper! {claim, pool, if_match, update_entry_content_query, Supertask}
+}
+
+#[actix_web::patch("/content")]
+pub(super) async fn update_task_content(claim: JwtUserClaim, pool: Data<PgPool>, if_match: IfMatchRevisions, update_entry_content_query: Json<UpdateEntryContentQuery>) -> impl Responder {
+    update_entry_content_wrapper! {claim, pool, if_match, update_entry_content_query, Task}
+}
+
+#[actix_web::patch("/content")]
+pub(super) async fn update_subtask_content(claim: JwtUserClaim, pool: Data<PgPool>, if_match: IfMatchRevisions, update_entry_content_query: Json<UpdateEntryContentQuery>) -> impl Responder {
+    update_entry_content_wrapper! {claim, pool, if_match, update_entry_content_query, Subtask}
+}
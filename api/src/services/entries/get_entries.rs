@@ -1,23 +1,63 @@
-use crate::models::{rotation::Rotation, entry_structure::EntryStructure};
+use crate::models::{rotation::Rotation, entry_structure::EntryStructure, curriculum_version::CurriculumVersion};
+use crate::utilities::{http_cache::Validators, memory_cache::CachedEntries};
 use crate::services::prelude::*;
+use actix_web::{http::{header::{ContentType, EntityTag}, StatusCode}, web::Bytes, HttpRequest};
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct GetEntriesQuery {
+pub(crate) struct GetEntriesQuery {
+    /// When the client cached the entries, which is still accepted from clients that do not send `If-None-Match` or `If-Modified-Since`.
     pub entries_cache_timestamp: Option<DateTime<Utc>>
 }
 
-#[actix_web::get("/{rotation_id}")]
-pub(super) async fn get_entries(_claim: JwtUserClaim, pool: Data<PgPool>, rotation_id: Path<i32>, query: Query<GetEntriesQuery>) -> impl Responder {
-    match Rotation::is_cache_valid(&pool, *rotation_id, query.entries_cache_timestamp).await {
-        Err(_) => return HttpResponse::InternalServerError().finish(),
-        Ok(true) => return HttpResponse::NotModified().finish(),
-        _ => {}
+pub(crate) async fn get_entries(claim: JwtUserClaim, pool: Data<PgPool>, memory_cache: Data<MemoryCache>, rotation_id: Path<i32>, query: Query<GetEntriesQuery>, req: HttpRequest) -> impl Responder {
+    let last_updated = match Rotation::fetch_last_updated(&pool, *rotation_id).await {
+        Ok(Some(last_updated)) => last_updated,
+        Ok(None) => return HttpResponse::NotFound().finish(),
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    };
+
+    // Every change to the entries of a rotation, or to the versions of its curriculum, updates when it was last updated.
+    // Admins and trainees are sent different entries, so they have different entity tags.
+    let view = if claim.sub.is_admin { "draft" } else { "published" };
+    let validators = Validators::new(EntityTag::new_strong(format!("{}-{}", view, last_updated.timestamp_micros())), Some(last_updated));
+
+    if let Some(response) = validators.not_modified(&req) {
+        return response;
+    }
+
+    if query.entries_cache_timestamp.is_some_and(|cache_timestamp| cache_timestamp >= last_updated) {
+        return validators.response(StatusCode::NOT_MODIFIED).finish();
     }
-    
-    // If the cache is not valid, then fetch the entry structure from the database.
-    match EntryStructure::fetch(&pool, *rotation_id).await {
-        Ok(entry_structure) => HttpResponse::Ok().json(entry_structure),
+
+    // Admins edit the draft, while trainees see the version of the curriculum they follow.
+    // The draft is cached until the rotation is updated, since it is the same for everyone who sees it.
+    let entries = match claim.sub.is_admin {
+        true => fetch_draft(&pool, &memory_cache, *rotation_id, last_updated).await,
+        false => match CurriculumVersion::fetch_followed_structure(&pool, claim.sub.id, *rotation_id).await {
+            Ok(Some(entry_structure)) => serde_json::to_vec(&entry_structure).map(Bytes::from).map_err(Into::into),
+            Ok(None) => fetch_draft(&pool, &memory_cache, *rotation_id, last_updated).await,
+            Err(error) => Err(error)
+        }
+    };
+
+    match entries {
+        Ok(entries) => validators
+            .response(StatusCode::OK)
+            .content_type(ContentType::json())
+            .body(entries),
         Err(_) => HttpResponse::InternalServerError().finish()
     }
-}
\ No newline at end of file
+}
+
+/// Gets the serialized draft entries of a rotation from the memory cache, fetching them into it if they are not cached as of when the rotation was last updated.
+async fn fetch_draft(pool: &PgPool, memory_cache: &MemoryCache, rotation_id: i32, last_updated: DateTime<Utc>) -> anyhow::Result<Bytes> {
+    if let Some(entries) = memory_cache.get_entries(rotation_id, last_updated) {
+        return Ok(entries);
+    }
+
+    let entries = Bytes::from(serde_json::to_vec(&EntryStructure::fetch(pool, rotation_id).await?)?);
+    memory_cache.set_entries(rotation_id, CachedEntries { body: entries.clone(), last_updated });
+
+    Ok(entries)
+}
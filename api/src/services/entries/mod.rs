@@ -1,17 +1,40 @@
+use crate::{middlewares::if_match_extractor::revision_tag, models::entry_structure::{Conditional, Revised}};
+use actix_web::{http::header::ETag, HttpResponse};
+use serde::Serialize;
+
 pub(super) mod create_entries;
+pub(super) mod bulk_create_entries;
 pub(super) mod update_entries;
 pub(super) mod delete_entries;
+pub(super) mod reorder_entries;
+pub(super) mod move_entries;
+pub(super) mod entry_content;
 pub(super) mod get_entries;
+pub(super) mod trash;
+
+/// Responds with the state of an entry and its entity tag after a change made on the condition of its revision.
+/// If the entry has been changed since, its current state is sent with a `412 Precondition Failed` instead, and a change that cannot be made is a `409 Conflict`.
+fn conditional_response<T: Revised + Serialize>(outcome: anyhow::Result<Conditional<T>>) -> HttpResponse {
+    match outcome {
+        Ok(Conditional::Applied(state)) => HttpResponse::Ok()
+            .insert_header(ETag(revision_tag(state.revision())))
+            .json(state),
+        Ok(Conditional::NotFound) => HttpResponse::NotFound().finish(),
+        Ok(Conditional::Modified(state)) => HttpResponse::PreconditionFailed()
+            .insert_header(ETag(revision_tag(state.revision())))
+            .json(state),
+        Ok(Conditional::Conflict) => HttpResponse::Conflict().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
 
 #[macro_export]
 macro_rules! entry_wrapper {
-    ($pool:ident, $claim:ident, $block:block) => {
+    ($claim:ident, $change:expr) => {
         if !$claim.sub.is_admin {
             return HttpResponse::Unauthorized().finish();
         }
-    
-        $block
-    
-        HttpResponse::Ok().finish()
+
+        super::conditional_response($change.await)
     };
 }
\ No newline at end of file
@@ -0,0 +1,20 @@
+use crate::{middlewares::if_match_extractor::IfMatchRevisions, models::entry_structure::{Task, Subtask}};
+use crate::entry_wrapper;
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MoveEntryQuery {
+    pub entry_id: i32,
+    pub parent_id: i32
+}
+
+#[actix_web::patch("/move")]
+pub(super) async fn move_task(claim: JwtUserClaim, pool: Data<PgPool>, if_match: IfMatchRevisions, move_entry_query: Json<MoveEntryQuery>) -> impl Responder {
+    entry_wrapper! {claim, Task::move_to(&pool, move_entry_query.entry_id, move_entry_query.parent_id, if_match.revisions())}
+}
+
+#[actix_web::patch("/move")]
+pub(super) async fn move_subtask(claim: JwtUserClaim, pool: Data<PgPool>, if_match: IfMatchRevisions, move_entry_query: Json<MoveEntryQuery>) -> impl Responder {
+    entry_wrapper! {claim, Subtask::move_to(&pool, move_entry_query.entry_id, move_entry_query.parent_id, if_match.revisions())}
+}
@@ -0,0 +1,37 @@
+use crate::models::entry_structure::{Supertask, Task, Subtask};
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReorderEntriesQuery {
+    pub ordered_ids: Box<[i32]>
+}
+
+macro_rules! reorder_entries_wrapper {
+    ($claim:ident, $reorder:expr) => {
+        if !$claim.sub.is_admin {
+            return HttpResponse::Unauthorized().finish();
+        }
+
+        match $reorder.await {
+            Ok(true) => HttpResponse::Ok().finish(),
+            Ok(false) => HttpResponse::BadRequest().finish(),
+            Err(_) => HttpResponse::InternalServerError().finish()
+        }
+    };
+}
+
+#[actix_web::patch("/reorder")]
+pub(super) async fn reorder_supertasks(claim: JwtUserClaim, pool: Data<PgPool>, reorder_entries_query: Json<ReorderEntriesQuery>) -> impl Responder {
+    reorder_entries_wrapper! {claim, Supertask::reorder(&pool, &reorder_entries_query.ordered_ids)}
+}
+
+#[actix_web::patch("/reorder")]
+pub(super) async fn reorder_tasks(claim: JwtUserClaim, pool: Data<PgPool>, reorder_entries_query: Json<ReorderEntriesQuery>) -> impl Responder {
+    reorder_entries_wrapper! {claim, Task::reorder(&pool, &reorder_entries_query.ordered_ids)}
+}
+
+#[actix_web::patch("/reorder")]
+pub(super) async fn reorder_subtasks(claim: JwtUserClaim, pool: Data<PgPool>, reorder_entries_query: Json<ReorderEntriesQuery>) -> impl Responder {
+    reorder_entries_wrapper! {claim, Subtask::reorder(&pool, &reorder_entries_query.ordered_ids)}
+}
@@ -0,0 +1,56 @@
+use crate::models::{entry_structure::{EntryStructure, Restoration, Supertask, Task, Subtask}, rotation::Rotation};
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RestoreEntryQuery {
+    pub entry_id: i32
+}
+
+macro_rules! restore_entry_wrapper {
+    ($claim:ident, $pool:ident, $restore_entry_query:ident, $entry:ident) => {
+        if !$claim.sub.is_admin {
+            return HttpResponse::Unauthorized().finish();
+        }
+
+        match $entry::restore(&$pool, $restore_entry_query.entry_id).await {
+            Ok(Restoration::Restored) => HttpResponse::Ok().finish(),
+            Ok(Restoration::NotFound) => HttpResponse::NotFound().finish(),
+            Ok(Restoration::ParentDeleted) => HttpResponse::Conflict().finish(),
+            Err(_) => HttpResponse::InternalServerError().finish()
+        }
+    };
+}
+
+#[actix_web::get("/trash/{rotation_id}")]
+pub(super) async fn get_trash(claim: JwtUserClaim, pool: Data<PgPool>, rotation_id: Path<i32>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match Rotation::exists(&pool, *rotation_id).await {
+        Ok(true) => {},
+        Ok(false) => return HttpResponse::NotFound().finish(),
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    }
+
+    match EntryStructure::fetch_trash(&pool, *rotation_id).await {
+        Ok(entries) => HttpResponse::Ok().json(entries),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::patch("/restore")]
+pub(super) async fn restore_supertask(claim: JwtUserClaim, pool: Data<PgPool>, restore_entry_query: Json<RestoreEntryQuery>) -> impl Responder {
+    restore_entry_wrapper! {claim, pool, restore_entry_query, Supertask}
+}
+
+#[actix_web::patch("/restore")]
+pub(super) async fn restore_task(claim: JwtUserClaim, pool: Data<PgPool>, restore_entry_query: Json<RestoreEntryQuery>) -> impl Responder {
+    restore_entry_wrapper! {claim, pool, restore_entry_query, Task}
+}
+
+#[actix_web::patch("/restore")]
+pub(super) async fn restore_subtask(claim: JwtUserClaim, pool: Data<PgPool>, restore_entry_query: Json<RestoreEntryQuery>) -> impl Responder {
+    restore_entry_wrapper! {claim, pool, restore_entry_query, Subtask}
+}
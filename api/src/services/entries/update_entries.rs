@@ -1,7 +1,10 @@
-use crate::{models::entry_structure::{Supertask, Task, Subtask}, utilities::parsable::EntryTitle};
+use crate::{middlewares::if_match_extractor::IfMatchRevisions, models::entry_structure::{Supertask, Task, Subtask}, utilities::parsable::EntryTitle};
 use crate::entry_wrapper;
 use crate::services::prelude::*;
 
+/// The most encounters a subtask can require, matching the database constraint.
+const MAX_REQUIRED_COUNT: i32 = 1000;
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct UpdateEntryQuery {
@@ -10,28 +13,36 @@ struct UpdateEntryQuery {
 }
 
 #[actix_web::patch("/update")]
-pub(super) async fn update_supertask(claim: JwtUserClaim, pool: Data<PgPool>, update_entry_query: Json<UpdateEntryQuery>) -> impl Responder {
-    entry_wrapper! {pool, claim, {
-        if Supertask::update_title(&pool, update_entry_query.entry_id, update_entry_query.title.as_str()).await.is_err() {
-            return HttpResponse::InternalServerError().finish();
-        }
-    }}
+pub(super) async fn update_supertask(claim: JwtUserClaim, pool: Data<PgPool>, if_match: IfMatchRevisions, update_entry_query: Json<UpdateEntryQuery>) -> impl Responder {
+    entry_wrapper! {claim, Supertask::update_title(&pool, update_entry_query.entry_id, update_entry_query.title.as_str(), if_match.revisions())}
 }
 
 #[actix_web::patch("/update")]
-pub(super) async fn update_task(claim: JwtUserClaim, pool: Data<PgPool>, update_entry_query: Json<UpdateEntryQuery>) -> impl Responder {
-    entry_wrapper! {pool, claim, {
-        if Task::update_title(&pool, update_entry_query.entry_id, update_entry_query.title.as_str()).await.is_err() {
-            return HttpResponse::InternalServerError().finish();
-        }
-    }}
+pub(super) async fn update_task(claim: JwtUserClaim, pool: Data<PgPool>, if_match: IfMatchRevisions, update_entry_query: Json<UpdateEntryQuery>) -> impl Responder {
+    entry_wrapper! {claim, Task::update_title(&pool, update_entry_query.entry_id, update_entry_query.title.as_str(), if_match.revisions())}
 }
 
 #[actix_web::patch("/update")]
-pub(super) async fn update_subtask(claim: JwtUserClaim, pool: Data<PgPool>, update_entry_query: Json<UpdateEntryQuery>) -> impl Responder {
-    entry_wrapper! {pool, claim, {
-        if Subtask::update_title(&pool, update_entry_query.entry_id, update_entry_query.title.as_str()).await.is_err() {
-            return HttpResponse::InternalServerError().finish();
-        }
-    }}
+pub(super) async fn update_subtask(claim: JwtUserClaim, pool: Data<PgPool>, if_match: IfMatchRevisions, update_entry_query: Json<UpdateEntryQuery>) -> impl Responder {
+    entry_wrapper! {claim, Subtask::update_title(&pool, update_entry_query.entry_id, update_entry_query.title.as_str(), if_match.revisions())}
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateRequiredCountQuery {
+    pub entry_id: i32,
+    pub required_count: i32
+}
+
+#[actix_web::patch("/required-count")]
+pub(super) async fn update_subtask_required_count(claim: JwtUserClaim, pool: Data<PgPool>, if_match: IfMatchRevisions, update_required_count_query: Json<UpdateRequiredCountQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    if !(1..=MAX_REQUIRED_COUNT).contains(&update_required_count_query.required_count) {
+        return HttpResponse::BadRequest().finish();
+    }
+
+    super::conditional_response(Subtask::update_required_count(&pool, update_required_count_query.entry_id, update_required_count_query.required_count, if_match.revisions()).await)
 }
\ No newline at end of file
@@ -2,6 +2,10 @@ pub mod prelude;
 mod rotations;
 mod entries;
 mod tasks;
+mod competencies;
+mod cases;
+mod progress;
 mod users;
 mod healthcheck;
+mod cache_metrics;
 pub mod config;
\ No newline at end of file
@@ -0,0 +1,67 @@
+use crate::models::{progress_export::{ExportLayout, ProgressExport}, rotation::Rotation};
+use crate::utilities::spreadsheet::{SpreadsheetFormat, XLSX_MAX_COLUMNS};
+use crate::services::prelude::*;
+use actix_web::{error::ErrorInternalServerError, http::header::{ContentDisposition, DispositionParam, DispositionType}, web::Bytes};
+use futures_util::stream;
+use tokio::sync::mpsc;
+
+/// How many chunks of an export can be waiting to be sent before writing it is paused.
+const CHANNEL_CAPACITY: usize = 4;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportProgressQuery {
+    rotation_id: Option<i32>,
+
+    #[serde(default)]
+    format: SpreadsheetFormat,
+
+    #[serde(default)]
+    layout: ExportLayout
+}
+
+#[actix_web::get("/export")]
+pub(super) async fn export_progress(claim: JwtUserClaim, pool: Data<PgPool>, query: Query<ExportProgressQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    if let Some(rotation_id) = query.rotation_id {
+        match Rotation::exists(&pool, rotation_id).await {
+            Ok(true) => {},
+            Ok(false) => return HttpResponse::NotFound().finish(),
+            Err(_) => return HttpResponse::InternalServerError().finish()
+        }
+    }
+
+    let export = match ProgressExport::prepare(&pool, query.rotation_id, query.layout).await {
+        Ok(export) => export,
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    };
+
+    if query.format == SpreadsheetFormat::Xlsx && export.column_count() > XLSX_MAX_COLUMNS {
+        return HttpResponse::BadRequest().body("There are too many subtasks for an XLSX matrix, so export a single rotation or use CSV.");
+    }
+
+    let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+    actix_web::rt::spawn(export.stream(pool.get_ref().clone(), query.format, sender));
+
+    let body = stream::unfold(receiver, |mut receiver| async move {
+        let chunk = receiver.recv().await?.map(Bytes::from).map_err(ErrorInternalServerError);
+        Some((chunk, receiver))
+    });
+
+    let scope = query.rotation_id.map(|rotation_id| rotation_id.to_string()).unwrap_or_else(|| String::from("program"));
+    let layout = match query.layout {
+        ExportLayout::Rows => "rows",
+        ExportLayout::Matrix => "matrix"
+    };
+
+    HttpResponse::Ok()
+        .content_type(query.format.content_type())
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!("progress-{scope}-{layout}.{}", query.format.extension()))]
+        })
+        .streaming(body)
+}
@@ -0,0 +1,52 @@
+use crate::models::{progress::{CohortFilter, CohortProgress, CohortSort}, rotation::Rotation};
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum SortOrder {
+    Asc,
+    Desc
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CohortProgressQuery {
+    supertask_id: Option<i32>,
+    min_progress: Option<f64>,
+    max_progress: Option<f64>,
+    sort: Option<CohortSort>,
+    order: Option<SortOrder>
+}
+
+/// Responds with a matrix of the progress of every trainee on each supertask of a rotation.
+/// Trainees can be filtered to a range of progress and sorted by name or progress, on either the whole rotation or a single supertask.
+#[actix_web::get("/cohort/{rotation_id}")]
+pub(super) async fn get_cohort_progress(claim: JwtUserClaim, pool: Data<PgPool>, rotation_id: Path<i32>, query: Query<CohortProgressQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let filter = CohortFilter {
+        supertask_id: query.supertask_id,
+        min_progress: query.min_progress.unwrap_or(0.0),
+        max_progress: query.max_progress.unwrap_or(100.0),
+        sort: query.sort.unwrap_or_default(),
+        descending: matches!(query.order, Some(SortOrder::Desc))
+    };
+
+    if !(0.0..=100.0).contains(&filter.min_progress) || !(0.0..=100.0).contains(&filter.max_progress) || filter.min_progress > filter.max_progress {
+        return HttpResponse::BadRequest().finish();
+    }
+
+    match Rotation::exists(&pool, *rotation_id).await {
+        Ok(true) => {},
+        Ok(false) => return HttpResponse::NotFound().finish(),
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    }
+
+    match CohortProgress::fetch(&pool, *rotation_id, &filter).await {
+        Ok(Some(cohort_progress)) => HttpResponse::Ok().json(cohort_progress),
+        Ok(None) => HttpResponse::BadRequest().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
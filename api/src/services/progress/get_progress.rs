@@ -0,0 +1,35 @@
+use crate::models::{progress::RotationProgress, rotation::Rotation};
+use crate::services::prelude::*;
+
+/// Responds with the progress of a trainee on a rotation, or not found if the rotation does not exist.
+async fn progress_response(pool: &PgPool, user_id: i32, rotation_id: i32) -> HttpResponse {
+    match Rotation::exists(pool, rotation_id).await {
+        Ok(true) => {},
+        Ok(false) => return HttpResponse::NotFound().finish(),
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    }
+
+    match RotationProgress::fetch(pool, user_id, rotation_id).await {
+        Ok(progress) => HttpResponse::Ok().json(progress),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::get("/{user_id}/{rotation_id}")]
+pub(super) async fn get_user_progress(claim: JwtUserClaim, pool: Data<PgPool>, path: Path<(i32, i32)>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let (user_id, rotation_id) = path.into_inner();
+    progress_response(&pool, user_id, rotation_id).await
+}
+
+#[actix_web::get("/{rotation_id}")]
+pub(super) async fn get_own_progress(claim: JwtUserClaim, pool: Data<PgPool>, rotation_id: Path<i32>) -> impl Responder {
+    if claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    progress_response(&pool, claim.sub.id, *rotation_id).await
+}
@@ -0,0 +1,37 @@
+use crate::models::progress_report::render_progress_report;
+use crate::services::prelude::*;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+
+/// Responds with the progress report of a trainee on a rotation as a PDF download, or not found if either does not exist.
+async fn report_response(pool: &PgPool, user_id: i32, rotation_id: i32) -> HttpResponse {
+    match render_progress_report(pool, user_id, rotation_id).await {
+        Ok(Some(pdf)) => HttpResponse::Ok()
+            .content_type("application/pdf")
+            .insert_header(ContentDisposition {
+                disposition: DispositionType::Attachment,
+                parameters: vec![DispositionParam::Filename(format!("progress-report-{user_id}-{rotation_id}.pdf"))]
+            })
+            .body(pdf),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::get("/report/{user_id}/{rotation_id}")]
+pub(super) async fn get_user_progress_report(claim: JwtUserClaim, pool: Data<PgPool>, path: Path<(i32, i32)>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let (user_id, rotation_id) = path.into_inner();
+    report_response(&pool, user_id, rotation_id).await
+}
+
+#[actix_web::get("/report/{rotation_id}")]
+pub(super) async fn get_own_progress_report(claim: JwtUserClaim, pool: Data<PgPool>, rotation_id: Path<i32>) -> impl Responder {
+    if claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    report_response(&pool, claim.sub.id, *rotation_id).await
+}
@@ -0,0 +1,4 @@
+pub(super) mod get_progress;
+pub(super) mod get_cohort_progress;
+pub(super) mod get_progress_report;
+pub(super) mod export_progress;
\ No newline at end of file
@@ -0,0 +1,54 @@
+use crate::models::rotation::Rotation;
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchiveRotationQuery {
+    rotation_id: i32
+}
+
+#[actix_web::patch("/archive")]
+pub(super) async fn archive_rotation(claim: JwtUserClaim, pool: Data<PgPool>, memory_cache: Data<MemoryCache>, archive_rotation_query: Json<ArchiveRotationQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let rotation = match Rotation::archive(&pool, archive_rotation_query.rotation_id).await {
+        Ok(Some(rotation)) => rotation,
+        Ok(None) => return HttpResponse::NotFound().finish(),
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    };
+
+    memory_cache.delete_rotation(rotation.id());
+
+    HttpResponse::Ok().json(rotation)
+}
+
+#[actix_web::patch("/unarchive")]
+pub(super) async fn unarchive_rotation(claim: JwtUserClaim, pool: Data<PgPool>, memory_cache: Data<MemoryCache>, unarchive_rotation_query: Json<ArchiveRotationQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let rotation = match Rotation::unarchive(&pool, unarchive_rotation_query.rotation_id).await {
+        Ok(Some(rotation)) => rotation,
+        Ok(None) => return HttpResponse::NotFound().finish(),
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    };
+
+    memory_cache.insert_rotation(rotation.clone());
+
+    HttpResponse::Ok().json(rotation)
+}
+
+#[actix_web::get("/archived")]
+pub(super) async fn get_archived_rotations(claim: JwtUserClaim, pool: Data<PgPool>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match Rotation::fetch_archived_as_map(&pool).await {
+        Ok(rotations) => HttpResponse::Ok().json(rotations),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
@@ -0,0 +1,35 @@
+use crate::models::{entry_structure::EntryIdMapping, rotation::Rotation};
+use crate::utilities::{parsable::Name, phantom_data::Synced};
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+struct CloneRotationQuery {
+    name: Option<Name>
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CloneRotationResponse {
+    rotation: Rotation<Synced>,
+    entry_id_mapping: EntryIdMapping
+}
+
+#[actix_web::post("/{rotation_id}/clone")]
+pub(super) async fn clone_rotation(claim: JwtUserClaim, pool: Data<PgPool>, memory_cache: Data<MemoryCache>, rotation_id: Path<i32>, clone_rotation_query: Json<CloneRotationQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let (rotation, entry_id_mapping) = match Rotation::clone_with_entries(&pool, *rotation_id, clone_rotation_query.name.as_ref()).await {
+        Ok(Some(clone)) => clone,
+        Ok(None) => return HttpResponse::NotFound().finish(),
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    };
+
+    memory_cache.insert_rotation(rotation.clone());
+
+    HttpResponse::Created().json(CloneRotationResponse {
+        rotation,
+        entry_id_mapping
+    })
+}
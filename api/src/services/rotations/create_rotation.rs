@@ -26,7 +26,7 @@ pub(super) async fn create_rotation(claim: JwtUserClaim, pool: Data<PgPool>, mem
         Err(_) => return HttpResponse::InternalServerError().finish()
     };
 
-    let _ = memory_cache.insert_rotation(rotation.clone());
+    memory_cache.insert_rotation(rotation.clone());
 
     HttpResponse::Created().json(CreateRotationResponse {
         rotation_id: rotation.id(),
@@ -0,0 +1,116 @@
+use crate::models::{curriculum::{CurriculumDocument, CurriculumError, CurriculumFormat, CurriculumImportSummary}, entry_structure::EntryStructure, rotation::Rotation};
+use crate::utilities::phantom_data::Synced;
+use crate::services::prelude::*;
+use actix_web::{http::header::CONTENT_TYPE, web::Bytes, HttpRequest};
+
+#[derive(Deserialize)]
+struct ExportCurriculumQuery {
+    #[serde(default)]
+    format: CurriculumFormat
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportCurriculumQuery {
+    rotation_id: Option<i32>,
+
+    #[serde(default)]
+    dry_run: bool
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportCurriculumResponse {
+    rotation: Option<Rotation<Synced>>,
+    created: CurriculumImportSummary,
+    dry_run: bool
+}
+
+#[derive(Serialize)]
+struct CurriculumErrorsResponse {
+    errors: Vec<CurriculumError>
+}
+
+#[actix_web::get("/{rotation_id}/export")]
+pub(super) async fn export_curriculum(claim: JwtUserClaim, pool: Data<PgPool>, rotation_id: Path<i32>, query: Query<ExportCurriculumQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let rotation = match Rotation::fetch_by_id(&pool, *rotation_id).await {
+        Ok(Some(rotation)) => rotation,
+        Ok(None) => return HttpResponse::NotFound().finish(),
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    };
+
+    let entry_structure = match EntryStructure::fetch(&pool, *rotation_id).await {
+        Ok(entry_structure) => entry_structure,
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    };
+
+    match CurriculumDocument::from_structure(rotation.name(), &entry_structure).serialize(query.format) {
+        Ok(document) => HttpResponse::Ok()
+            .content_type(query.format.content_type())
+            .body(document),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::post("/import")]
+pub(super) async fn import_curriculum(claim: JwtUserClaim, pool: Data<PgPool>, memory_cache: Data<MemoryCache>, req: HttpRequest, query: Query<ImportCurriculumQuery>, body: Bytes) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let is_yaml = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("yaml"));
+
+    let format = match is_yaml {
+        true => CurriculumFormat::Yaml,
+        false => CurriculumFormat::Json
+    };
+
+    let document = match CurriculumDocument::parse(&body, format) {
+        Ok(document) => document,
+        Err(error) => return HttpResponse::BadRequest().json(CurriculumErrorsResponse { errors: vec![error] })
+    };
+
+    let errors = document.validate();
+    if !errors.is_empty() {
+        return HttpResponse::BadRequest().json(CurriculumErrorsResponse { errors });
+    }
+
+    let (rotation, created) = match Rotation::import_curriculum(&pool, &document, query.rotation_id, query.dry_run).await {
+        Ok(Some(import)) => import,
+        Ok(None) => return match Rotation::exists(&pool, query.rotation_id.unwrap_or_default()).await {
+            Ok(true) => HttpResponse::Conflict().body("Rotation must be unarchived before a curriculum can be imported into it."),
+            Ok(false) => HttpResponse::NotFound().finish(),
+            Err(_) => HttpResponse::InternalServerError().finish()
+        },
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    };
+
+    if query.dry_run {
+        return HttpResponse::Ok().json(ImportCurriculumResponse {
+            rotation: None,
+            created,
+            dry_run: true
+        });
+    }
+
+    memory_cache.insert_rotation(rotation.clone());
+
+    let response = ImportCurriculumResponse {
+        rotation: Some(rotation),
+        created,
+        dry_run: false
+    };
+
+    match query.rotation_id {
+        Some(_) => HttpResponse::Ok().json(response),
+        None => HttpResponse::Created().json(response)
+    }
+}
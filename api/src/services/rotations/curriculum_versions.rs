@@ -0,0 +1,110 @@
+use crate::models::{curriculum_version::{CurriculumVersion, Migration, SubtaskMapping}, rotation::Rotation};
+use crate::utilities::parsable::Description;
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+struct PublishVersionQuery {
+    notes: Option<Description>
+}
+
+#[derive(Deserialize)]
+struct DiffVersionsQuery {
+    from: i32,
+    to: Option<i32>
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MigrateVersionQuery {
+    #[serde(default)]
+    dry_run: bool
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MigrateVersionBody {
+    from_version: i32,
+    to_version: i32,
+    user_ids: Option<Vec<i32>>,
+
+    #[serde(default)]
+    mappings: Vec<SubtaskMapping>
+}
+
+#[actix_web::get("/{rotation_id}/versions")]
+pub(super) async fn get_curriculum_versions(claim: JwtUserClaim, pool: Data<PgPool>, rotation_id: Path<i32>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match Rotation::exists(&pool, *rotation_id).await {
+        Ok(true) => {},
+        Ok(false) => return HttpResponse::NotFound().finish(),
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    }
+
+    match CurriculumVersion::fetch_all(&pool, *rotation_id).await {
+        Ok(versions) => HttpResponse::Ok().json(versions),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::post("/{rotation_id}/versions/publish")]
+pub(super) async fn publish_curriculum_version(claim: JwtUserClaim, pool: Data<PgPool>, rotation_id: Path<i32>, publish_version_query: Json<PublishVersionQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let notes = publish_version_query.notes.as_ref().map(|notes| notes.as_str()).unwrap_or_default();
+
+    match CurriculumVersion::publish(&pool, *rotation_id, claim.sub.id, notes).await {
+        Ok(Some(version)) => HttpResponse::Created().json(version),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+/// Compares two versions of a curriculum, or a version with the draft when no version to compare to is given.
+#[actix_web::get("/{rotation_id}/versions/diff")]
+pub(super) async fn diff_curriculum_versions(claim: JwtUserClaim, pool: Data<PgPool>, rotation_id: Path<i32>, query: Query<DiffVersionsQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match CurriculumVersion::diff(&pool, *rotation_id, query.from, query.to).await {
+        Ok(Some(diff)) => HttpResponse::Ok().json(diff),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::post("/{rotation_id}/versions/migrate")]
+pub(super) async fn migrate_curriculum_version(claim: JwtUserClaim, pool: Data<PgPool>, rotation_id: Path<i32>, query: Query<MigrateVersionQuery>, migrate_version_body: Json<MigrateVersionBody>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let body = migrate_version_body.into_inner();
+
+    match CurriculumVersion::migrate(&pool, *rotation_id, body.from_version, body.to_version, body.user_ids.as_deref(), &body.mappings, query.dry_run).await {
+        Ok(Migration::Applied(summary)) => HttpResponse::Ok().json(summary),
+        Ok(Migration::VersionNotFound) => HttpResponse::NotFound().finish(),
+        Ok(Migration::InvalidMapping) => HttpResponse::BadRequest().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::get("/{rotation_id}/versions/{version}")]
+pub(super) async fn get_curriculum_version(claim: JwtUserClaim, pool: Data<PgPool>, path: Path<(i32, i32)>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let (rotation_id, version) = path.into_inner();
+
+    match CurriculumVersion::fetch_snapshot(&pool, rotation_id, version).await {
+        Ok(Some(snapshot)) => HttpResponse::Ok().json(snapshot),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
@@ -1,23 +1,66 @@
 use crate::models::rotation::Rotation;
+use crate::utilities::parsable::ConfirmationToken;
 use crate::services::prelude::*;
 
+const DELETION_TOKEN_EXPIRATION_MINUTES: i32 = 10;
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct DeleteRotationQuery {
+struct RequestDeleteRotationQuery {
     rotation_id: i32
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RequestDeleteRotationResponse {
+    deletion_token: ConfirmationToken,
+    deletion_token_expiry: DateTime<Utc>
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeleteRotationQuery {
+    rotation_id: i32,
+    deletion_token: ConfirmationToken
+}
+
+/// First step of permanently deleting a rotation. The rotation must already be archived.
+#[actix_web::post("/request-delete")]
+pub(super) async fn request_delete_rotation(claim: JwtUserClaim, pool: Data<PgPool>, request_delete_rotation_query: Json<RequestDeleteRotationQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let rotation_id = request_delete_rotation_query.rotation_id;
+
+    match Rotation::request_deletion(&pool, rotation_id, DELETION_TOKEN_EXPIRATION_MINUTES).await {
+        Ok(Some((deletion_token_expiry, deletion_token))) => HttpResponse::Ok().json(RequestDeleteRotationResponse {
+            deletion_token,
+            deletion_token_expiry
+        }),
+        Ok(None) => match Rotation::exists(&pool, rotation_id).await {
+            Ok(true) => HttpResponse::Conflict().body("Rotation must be archived before it can be deleted."),
+            Ok(false) => HttpResponse::NotFound().finish(),
+            Err(_) => HttpResponse::InternalServerError().finish()
+        },
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+/// Second step of permanently deleting a rotation, confirmed with the token issued by the first step.
 #[actix_web::delete("/delete")]
 pub(super) async fn delete_rotation(claim: JwtUserClaim, pool: Data<PgPool>, memory_cache: Data<MemoryCache>, delete_rotation_query: Json<DeleteRotationQuery>) -> impl Responder {
     if !claim.sub.is_admin {
         return HttpResponse::Unauthorized().finish();
     }
 
-    if Rotation::delete(&pool, delete_rotation_query.rotation_id).await.is_err() {
-        return HttpResponse::InternalServerError().finish();
+    match Rotation::delete(&pool, delete_rotation_query.rotation_id, delete_rotation_query.deletion_token.as_str()).await {
+        Ok(true) => {},
+        Ok(false) => return HttpResponse::Forbidden().finish(),
+        Err(_) => return HttpResponse::InternalServerError().finish()
     }
 
-    let _ = memory_cache.delete_rotation(delete_rotation_query.rotation_id);
+    memory_cache.delete_rotation(delete_rotation_query.rotation_id);
 
     HttpResponse::Ok().finish()
 }
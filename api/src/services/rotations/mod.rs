@@ -1,2 +1,7 @@
 pub(super) mod create_rotation;
-pub(super) mod delete_rotation;
\ No newline at end of file
+pub(super) mod update_rotation;
+pub(super) mod clone_rotation;
+pub(super) mod curriculum;
+pub(super) mod archive_rotation;
+pub(super) mod delete_rotation;
+pub(super) mod curriculum_versions;
\ No newline at end of file
@@ -0,0 +1,61 @@
+use crate::models::rotation::{Rotation, RotationCategory};
+use crate::utilities::parsable::{Description, Location, Name};
+use crate::services::prelude::*;
+use serde::Deserializer;
+
+const MAX_DURATION_WEEKS: i32 = 52;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateRotationQuery {
+    rotation_id: i32,
+    name: Option<Name>,
+    description: Option<Description>,
+    location: Option<Location>,
+    /// Left out to keep the duration, or `null` to clear it.
+    #[serde(default, deserialize_with = "deserialize_present")]
+    duration_weeks: Option<Option<i32>>,
+    category: Option<RotationCategory>
+}
+
+/// Deserializes a field that is present, even if it is `null`, so that it can be told apart from one that was left out.
+fn deserialize_present<'de, D: Deserializer<'de>, T: Deserialize<'de>>(deserializer: D) -> Result<Option<T>, D::Error> {
+    T::deserialize(deserializer).map(Some)
+}
+
+#[actix_web::patch("/update")]
+pub(super) async fn update_rotation(claim: JwtUserClaim, pool: Data<PgPool>, memory_cache: Data<MemoryCache>, update_rotation_query: Json<UpdateRotationQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let update_rotation_query = update_rotation_query.into_inner();
+
+    if let Some(Some(duration_weeks)) = update_rotation_query.duration_weeks {
+        if !(1..=MAX_DURATION_WEEKS).contains(&duration_weeks) {
+            return HttpResponse::BadRequest().finish();
+        }
+    }
+
+    let rotation = match Rotation::update(
+        &pool,
+        update_rotation_query.rotation_id,
+        update_rotation_query.name.as_ref(),
+        update_rotation_query.description.as_ref(),
+        update_rotation_query.location.as_ref(),
+        update_rotation_query.duration_weeks,
+        update_rotation_query.category
+    ).await {
+        Ok(Some(rotation)) => rotation,
+        Ok(None) => return match Rotation::exists(&pool, update_rotation_query.rotation_id).await {
+            Ok(true) => HttpResponse::Conflict().body("Rotation must be unarchived before it can be updated."),
+            Ok(false) => HttpResponse::NotFound().finish(),
+            Err(_) => HttpResponse::InternalServerError().finish()
+        },
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    };
+
+    memory_cache.insert_rotation(rotation.clone());
+
+    HttpResponse::Ok().json(rotation)
+}
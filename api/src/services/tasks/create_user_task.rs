@@ -1,12 +1,11 @@
-use crate::models::entry_structure::Subtask;
-use crate::models::rotation::Rotation;
+use crate::models::curriculum_version::CurriculumVersion;
 use crate::models::user_task::UserTask;
 use crate::utilities::parsable::Comment;
 use crate::services::prelude::*;
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct CreateUserTaskQuery {
+pub(crate) struct CreateUserTaskQuery {
     subtask_id: i32,
     rotation_id: i32,
     is_completed: bool,
@@ -18,8 +17,7 @@ struct CreateUserTaskResponse {
     id: i32
 }
 
-#[actix_web::post("/create")]
-pub(super) async fn create_user_task(claim: JwtUserClaim, pool: Data<PgPool>, create_user_task_query: Json<CreateUserTaskQuery>) -> impl Responder {
+pub(crate) async fn create_user_task(claim: JwtUserClaim, pool: Data<PgPool>, create_user_task_query: Json<CreateUserTaskQuery>) -> impl Responder {
     if claim.sub.is_admin {
         return HttpResponse::Unauthorized().finish();
     }
@@ -41,20 +39,13 @@ pub(super) async fn create_user_task(claim: JwtUserClaim, pool: Data<PgPool>, cr
         Err(_) => return HttpResponse::InternalServerError().body("Failed to check if user task exists.")
     }
 
-    match Subtask::exists(&pool, user_task.subtask_id()).await {
-        Ok(exists) => if !exists {
-            return HttpResponse::BadRequest().finish();
-        },
+    // The subtask must be in the curriculum the trainee follows on the rotation, which also ensures that it exists.
+    match CurriculumVersion::enroll_for_subtask(&pool, claim.sub.id, user_task.rotation_id(), user_task.subtask_id()).await {
+        Ok(true) => {},
+        Ok(false) => return HttpResponse::BadRequest().finish(),
         Err(_) => return HttpResponse::InternalServerError().body("Failed to check if subtask exists.")
     }
 
-    match Rotation::exists(&pool, user_task.rotation_id()).await {
-        Ok(exists) => if !exists {
-            return HttpResponse::BadRequest().finish();
-        },
-        Err(_) => return HttpResponse::InternalServerError().body("Failed to check if rotation exists.")
-    }
-
     let user_task = match user_task.insert(&pool).await {
         Ok(user_task) => user_task,
         Err(_) => return HttpResponse::InternalServerError().body("Failed to insert user task.")
@@ -0,0 +1,78 @@
+use crate::models::encounter::Encounter;
+use crate::utilities::parsable::Comment;
+use crate::services::prelude::*;
+use chrono::{Days, NaiveDate};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LogEncounterQuery {
+    user_task_id: i32,
+    encountered_on: NaiveDate,
+    comment: Comment
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeleteEncounterQuery {
+    encounter_id: i32
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogEncounterResponse {
+    encounter: Encounter,
+    is_completed: bool
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeleteEncounterResponse {
+    is_completed: bool
+}
+
+#[actix_web::post("/create")]
+pub(super) async fn log_encounter(claim: JwtUserClaim, pool: Data<PgPool>, log_encounter_query: Json<LogEncounterQuery>) -> impl Responder {
+    if claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    // A day of leeway allows for trainees in time zones ahead of the server.
+    let latest_date = Utc::now().date_naive() + Days::new(1);
+    if log_encounter_query.encountered_on > latest_date {
+        return HttpResponse::BadRequest().finish();
+    }
+
+    match Encounter::insert(&pool, claim.sub.id, log_encounter_query.user_task_id, log_encounter_query.encountered_on, &log_encounter_query.comment).await {
+        Ok(Some((encounter, is_completed))) => HttpResponse::Created().json(LogEncounterResponse { encounter, is_completed }),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::delete("/delete")]
+pub(super) async fn delete_encounter(claim: JwtUserClaim, pool: Data<PgPool>, delete_encounter_query: Json<DeleteEncounterQuery>) -> impl Responder {
+    if claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match Encounter::delete(&pool, claim.sub.id, delete_encounter_query.encounter_id).await {
+        Ok(Some(is_completed)) => HttpResponse::Ok().json(DeleteEncounterResponse { is_completed }),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+/// Gets the encounters logged against a user task, which admins can get for any trainee.
+#[actix_web::get("/{user_task_id}")]
+pub(super) async fn get_encounters(claim: JwtUserClaim, pool: Data<PgPool>, user_task_id: Path<i32>) -> impl Responder {
+    let user_id = match claim.sub.is_admin {
+        true => None,
+        false => Some(claim.sub.id)
+    };
+
+    match Encounter::fetch_all(&pool, *user_task_id, user_id).await {
+        Ok(Some(encounters)) => HttpResponse::Ok().json(encounters),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
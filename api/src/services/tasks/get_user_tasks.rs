@@ -1,8 +1,9 @@
 use crate::models::user_task::UserTask;
+use crate::utilities::http_cache::respond_json;
 use crate::services::prelude::*;
+use actix_web::HttpRequest;
 
-#[actix_web::get("/{user_id}/{rotation_id}")]
-pub(super) async fn get_user_tasks(claim: JwtUserClaim, pool: Data<PgPool>, path: Path<(i32, i32)>) -> impl Responder {
+pub(crate) async fn get_user_tasks(claim: JwtUserClaim, pool: Data<PgPool>, path: Path<(i32, i32)>) -> impl Responder {
     if !claim.sub.is_admin {
         return HttpResponse::Unauthorized().finish();
     }
@@ -15,14 +16,16 @@ pub(super) async fn get_user_tasks(claim: JwtUserClaim, pool: Data<PgPool>, path
     }
 }
 
-#[actix_web::get("/{rotation_id}")]
-pub(super) async fn get_own_tasks(claim: JwtUserClaim, pool: Data<PgPool>, rotation_id: Path<i32>) -> impl Responder {
+/// Fetches the progress of the trainee in a rotation, which is only sent if it has changed since the client last fetched it.
+///
+/// There is no single time that every change to progress is recorded at, such as an encounter being deleted, so only an entity tag is sent to validate it.
+pub(crate) async fn get_own_tasks(claim: JwtUserClaim, pool: Data<PgPool>, rotation_id: Path<i32>, req: HttpRequest) -> impl Responder {
     if claim.sub.is_admin {
         return HttpResponse::Unauthorized().finish();
     }
     
     match UserTask::fetch_as_map(&pool, claim.sub.id, *rotation_id).await {
-        Ok(user_tasks) => HttpResponse::Ok().json(user_tasks),
+        Ok(user_tasks) => respond_json(&req, &user_tasks, None),
         Err(_) => HttpResponse::InternalServerError().body("Failed to fetch own user tasks.")
     }
 }
\ No newline at end of file
@@ -1,3 +1,6 @@
 pub(super) mod create_user_task;
 pub(super) mod update_user_task;
-pub(super) mod get_user_tasks;
\ No newline at end of file
+pub(super) mod get_user_tasks;
+pub(super) mod encounters;
+pub(super) mod verifications;
+pub(super) mod user_task_history;
\ No newline at end of file
@@ -4,14 +4,13 @@ use crate::services::prelude::*;
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct UpdateUserTaskQuery {
+pub(crate) struct UpdateUserTaskQuery {
     id: i32,
     is_completed: bool,
     comment: Comment
 }
 
-#[actix_web::patch("/update")]
-pub(super) async fn update_user_task(claim: JwtUserClaim, pool: Data<PgPool>, update_user_task_query: Json<UpdateUserTaskQuery>) -> impl Responder {
+pub(crate) async fn update_user_task(claim: JwtUserClaim, pool: Data<PgPool>, update_user_task_query: Json<UpdateUserTaskQuery>) -> impl Responder {
     if claim.sub.is_admin {
         return HttpResponse::Unauthorized().finish();
     }
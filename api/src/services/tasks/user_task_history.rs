@@ -0,0 +1,11 @@
+use crate::models::user_task::UserTask;
+use crate::services::prelude::*;
+
+#[actix_web::get("/history/{user_task_id}")]
+pub(super) async fn get_user_task_history(claim: JwtUserClaim, pool: Data<PgPool>, user_task_id: Path<i32>) -> impl Responder {
+    match UserTask::fetch_history(&pool, *user_task_id, claim.sub.id, claim.sub.is_admin).await {
+        Ok(Some(changes)) => HttpResponse::Ok().json(changes),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
@@ -0,0 +1,77 @@
+use crate::models::verification::{PendingVerification, Transition, VerificationDecision, VerificationStatus};
+use crate::utilities::parsable::Comment;
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UserTaskVerificationQuery {
+    user_task_id: i32
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReturnUserTaskQuery {
+    user_task_id: i32,
+    reason: Comment
+}
+
+/// Responds with the decision if the transition was applied, or with a conflict if the task cannot move to the new status.
+fn transition_response(transition: Result<Transition, anyhow::Error>) -> HttpResponse {
+    match transition {
+        Ok(Transition::Applied(decision)) => HttpResponse::Ok().json(decision),
+        Ok(Transition::NotFound) => HttpResponse::NotFound().finish(),
+        Ok(Transition::Invalid) => HttpResponse::Conflict().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::patch("/submit")]
+pub(super) async fn submit_user_task(claim: JwtUserClaim, pool: Data<PgPool>, submit_user_task_query: Json<UserTaskVerificationQuery>) -> impl Responder {
+    if claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    transition_response(VerificationDecision::submit(&pool, claim.sub.id, submit_user_task_query.user_task_id).await)
+}
+
+#[actix_web::patch("/verify")]
+pub(super) async fn verify_user_task(claim: JwtUserClaim, pool: Data<PgPool>, verify_user_task_query: Json<UserTaskVerificationQuery>) -> impl Responder {
+    transition_response(
+        VerificationDecision::decide(&pool, claim.sub.id, claim.sub.is_admin, verify_user_task_query.user_task_id, VerificationStatus::Verified, "").await
+    )
+}
+
+#[actix_web::patch("/return")]
+pub(super) async fn return_user_task(claim: JwtUserClaim, pool: Data<PgPool>, return_user_task_query: Json<ReturnUserTaskQuery>) -> impl Responder {
+    if return_user_task_query.reason.as_str().is_empty() {
+        return HttpResponse::BadRequest().finish();
+    }
+
+    transition_response(
+        VerificationDecision::decide(
+            &pool,
+            claim.sub.id,
+            claim.sub.is_admin,
+            return_user_task_query.user_task_id,
+            VerificationStatus::Returned,
+            return_user_task_query.reason.as_str()
+        ).await
+    )
+}
+
+#[actix_web::get("/history/{user_task_id}")]
+pub(super) async fn get_verification_history(claim: JwtUserClaim, pool: Data<PgPool>, user_task_id: Path<i32>) -> impl Responder {
+    match VerificationDecision::fetch_history(&pool, *user_task_id, claim.sub.id, claim.sub.is_admin).await {
+        Ok(Some(decisions)) => HttpResponse::Ok().json(decisions),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::get("/inbox")]
+pub(super) async fn get_verification_inbox(claim: JwtUserClaim, pool: Data<PgPool>) -> impl Responder {
+    match PendingVerification::fetch_inbox(&pool, claim.sub.id, claim.sub.is_admin).await {
+        Ok(pending) => HttpResponse::Ok().json(pending),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
@@ -1,6 +1,12 @@
+use crate::utilities::http_cache::respond_json;
 use crate::services::prelude::*;
+use actix_web::HttpRequest;
 
+/// Fetches the session of the user, which is only sent if it has changed since the client last fetched it.
 #[actix_web::get("/session")]
-pub(super) async fn get_user_session(claim: JwtUserClaim, memory_cache: Data<MemoryCache>, pool: Data<PgPool>) -> impl Responder {
-    UserSession::respond(&pool, &memory_cache, claim.sub, None).await
+pub(super) async fn get_user_session(claim: JwtUserClaim, memory_cache: Data<MemoryCache>, pool: Data<PgPool>, req: HttpRequest) -> impl Responder {
+    match UserSession::build(&pool, &memory_cache, claim.sub).await {
+        Ok((session, changed_at)) => respond_json(&req, &session, Some(changed_at)),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
 }
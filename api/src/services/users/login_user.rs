@@ -10,8 +10,7 @@ pub struct LoginUserQuery {
     pub password: PlainTextPassword
 }
 
-#[actix_web::post("/login")]
-pub(super) async fn login_user(pool: Data<PgPool>, memory_cache: Data<MemoryCache>, login_user_query: Json<LoginUserQuery>) -> impl Responder {
+pub(crate) async fn login_user(pool: Data<PgPool>, memory_cache: Data<MemoryCache>, login_user_query: Json<LoginUserQuery>) -> impl Responder {
     let user = match User::login(&pool, login_user_query.username.as_str(), login_user_query.password.as_str()).await {
         Ok(user) => user,
         Err(_) => return HttpResponse::Unauthorized().finish()
@@ -3,4 +3,5 @@ pub(super) mod login_user;
 pub(super) mod delete_user;
 pub(super) mod get_user_session;
 pub(super) mod search_users;
-pub(super) mod reset_user_password;
\ No newline at end of file
+pub(super) mod reset_user_password;
+pub(super) mod supervisors;
\ No newline at end of file
@@ -5,15 +5,14 @@ use std::env::var;
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct RegisterUserQuery {
+pub(crate) struct RegisterUserQuery {
     pub username: Username,
     pub name: Name,
     pub password: PlainTextPassword,
     pub access_code: String
 }
 
-#[actix_web::post("/register")]
-pub(super) async fn register_user(pool: Data<PgPool>, register_user_query: Json<RegisterUserQuery>) -> impl Responder {
+pub(crate) async fn register_user(pool: Data<PgPool>, register_user_query: Json<RegisterUserQuery>) -> impl Responder {
     let access_code = var("ACCESS_CODE").expect("Access code not set in ENV.");
 
     if register_user_query.access_code != access_code {
@@ -6,7 +6,7 @@ const PASSWORD_RESET_EXPIRATION_HOURS: i32 = 2;
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct ResetPasswordQuery {
+pub(crate) struct ResetPasswordQuery {
     pub username: Username,
     pub password: PlainTextPassword,
     pub reset_token: ResetToken
@@ -25,8 +25,7 @@ struct AllowResetPasswordResponse {
     pub reset_token: ResetToken
 }
 
-#[actix_web::post("/reset-password")]
-pub(super) async fn reset_password(pool: Data<PgPool>, reset_password_query: Json<ResetPasswordQuery>) -> impl Responder {
+pub(crate) async fn reset_password(pool: Data<PgPool>, reset_password_query: Json<ResetPasswordQuery>) -> impl Responder {
     match User::update_password(&pool, reset_password_query.username.as_str(), reset_password_query.password.as_str(), reset_password_query.reset_token.as_str()).await {
         Ok(success) => match success {
             true => HttpResponse::Ok().finish(),
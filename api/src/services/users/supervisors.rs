@@ -0,0 +1,47 @@
+use crate::models::supervisor::SupervisorAssignment;
+use crate::services::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SupervisorAssignmentQuery {
+    supervisor_id: i32,
+    trainee_id: i32
+}
+
+#[actix_web::get("/supervisors")]
+pub(super) async fn get_supervisors(claim: JwtUserClaim, pool: Data<PgPool>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match SupervisorAssignment::fetch_all(&pool).await {
+        Ok(assignments) => HttpResponse::Ok().json(assignments),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::post("/supervisors/assign")]
+pub(super) async fn assign_supervisor(claim: JwtUserClaim, pool: Data<PgPool>, assign_supervisor_query: Json<SupervisorAssignmentQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match SupervisorAssignment::insert(&pool, assign_supervisor_query.supervisor_id, assign_supervisor_query.trainee_id).await {
+        Ok(true) => HttpResponse::Ok().finish(),
+        Ok(false) => HttpResponse::BadRequest().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
+
+#[actix_web::delete("/supervisors/unassign")]
+pub(super) async fn unassign_supervisor(claim: JwtUserClaim, pool: Data<PgPool>, unassign_supervisor_query: Json<SupervisorAssignmentQuery>) -> impl Responder {
+    if !claim.sub.is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match SupervisorAssignment::delete(&pool, unassign_supervisor_query.supervisor_id, unassign_supervisor_query.trainee_id).await {
+        Ok(true) => HttpResponse::Ok().finish(),
+        Ok(false) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
@@ -0,0 +1,247 @@
+use std::{collections::HashMap, hash::Hash, sync::{atomic::{AtomicU64, Ordering}, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard}, time::{Duration, Instant}};
+use serde::Serialize;
+
+/// How a cache has been used since it was created.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub size: usize,
+    pub capacity: usize
+}
+
+struct CacheEntry<V> {
+    value: V,
+    expires_at: Instant,
+    /// The tick of the cache when the entry was last used, so that the least recently used entry is evicted first.
+    last_used: AtomicU64
+}
+
+/// A map bounded in size whose entries expire after a time to live.
+///
+/// Reads only take a shared lock and no lock is held across an await, so under contention callers wait briefly instead of failing.
+/// A lock poisoned by a panic is recovered, since every change leaves the map consistent.
+pub struct Cache<K, V> {
+    entries: RwLock<HashMap<K, CacheEntry<V>>>,
+    ttl: Duration,
+    capacity: usize,
+    ticks: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Cache<K, V> {
+    /// Creates an empty cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `ttl` - How long an entry is kept after it is inserted.
+    /// * `capacity` - The most entries kept at once, which is at least one.
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            ttl,
+            capacity: capacity.max(1),
+            ticks: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0)
+        }
+    }
+
+    /// Gets a clone of the value cached for a key, or `None` if it is not cached or has expired.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let entries = self.read();
+
+        match entries.get(key).filter(|entry| entry.expires_at > Instant::now()) {
+            Some(entry) => {
+                entry.last_used.store(self.tick(), Ordering::Relaxed);
+                self.hits.fetch_add(1, Ordering::Relaxed);
+
+                Some(entry.value.clone())
+            },
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Caches a value for a key, evicting expired entries or otherwise the least recently used one if the cache is full.
+    pub fn insert(&self, key: K, value: V) {
+        self.insert_if(key, value, || true);
+    }
+
+    /// Caches a value for a key like `insert`, but only if `condition` holds while the cache cannot be changed by anyone else.
+    ///
+    /// # Returns
+    ///
+    /// Whether the value was cached.
+    pub fn insert_if(&self, key: K, value: V, condition: impl FnOnce() -> bool) -> bool {
+        let mut entries = self.write();
+
+        if !condition() {
+            return false;
+        }
+
+        if !entries.contains_key(&key) && entries.len() >= self.capacity {
+            self.evict(&mut entries);
+        }
+
+        entries.insert(key, CacheEntry {
+            value,
+            expires_at: Instant::now() + self.ttl,
+            last_used: AtomicU64::new(self.tick())
+        });
+
+        true
+    }
+
+    /// Changes the value cached for a key in place, without renewing its time to live.
+    ///
+    /// # Returns
+    ///
+    /// Whether a value was cached for the key.
+    pub fn update(&self, key: &K, change: impl FnOnce(&mut V)) -> bool {
+        let mut entries = self.write();
+
+        match entries.get_mut(key).filter(|entry| entry.expires_at > Instant::now()) {
+            Some(entry) => {
+                change(&mut entry.value);
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Removes the value cached for a key.
+    pub fn remove(&self, key: &K) {
+        self.write().remove(key);
+    }
+
+    /// Removes every cached value.
+    pub fn clear(&self) {
+        self.write().clear();
+    }
+
+    pub fn metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            size: self.read().len(),
+            capacity: self.capacity
+        }
+    }
+
+    fn evict(&self, entries: &mut HashMap<K, CacheEntry<V>>) {
+        let now = Instant::now();
+        let size = entries.len();
+
+        entries.retain(|_, entry| entry.expires_at > now);
+
+        if entries.len() == size {
+            let least_recently_used = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used.load(Ordering::Relaxed))
+                .map(|(key, _)| key.clone());
+
+            if let Some(key) = least_recently_used {
+                entries.remove(&key);
+            }
+        }
+
+        self.evictions.fetch_add((size - entries.len()) as u64, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    fn tick(&self) -> u64 {
+        self.ticks.fetch_add(1, Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<K, CacheEntry<V>>> {
+        self.entries.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline(always)]
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<K, CacheEntry<V>>> {
+        self.entries.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{panic::{catch_unwind, AssertUnwindSafe}, thread::sleep};
+
+    #[test]
+    fn test_get_and_metrics() {
+        let cache = Cache::new(Duration::from_secs(60), 4);
+        cache.insert(1, "one");
+
+        assert_eq!(cache.get(&1), Some("one"));
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.metrics(), CacheMetrics { hits: 1, misses: 1, evictions: 0, size: 1, capacity: 4 });
+    }
+
+    #[test]
+    fn test_entries_expire() {
+        let cache = Cache::new(Duration::from_millis(20), 4);
+        cache.insert(1, "one");
+        sleep(Duration::from_millis(40));
+
+        assert_eq!(cache.get(&1), None);
+        assert!(!cache.update(&1, |value| *value = "uno"));
+
+        cache.insert(2, "two");
+        cache.insert(3, "three");
+        cache.insert(4, "four");
+        cache.insert(5, "five");
+
+        assert_eq!(cache.metrics().evictions, 1);
+        assert_eq!(cache.get(&2), Some("two"));
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let cache = Cache::new(Duration::from_secs(60), 2);
+        cache.insert(1, "one");
+        cache.insert(2, "two");
+
+        assert_eq!(cache.get(&1), Some("one"));
+        cache.insert(3, "three");
+
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get(&1), Some("one"));
+        assert_eq!(cache.get(&3), Some("three"));
+        assert_eq!(cache.metrics().size, 2);
+    }
+
+    #[test]
+    fn test_insert_if() {
+        let cache = Cache::new(Duration::from_secs(60), 2);
+
+        assert!(!cache.insert_if(1, "one", || false));
+        assert_eq!(cache.get(&1), None);
+
+        assert!(cache.insert_if(1, "one", || true));
+        assert_eq!(cache.get(&1), Some("one"));
+    }
+
+    #[test]
+    fn test_poisoned_lock_is_recovered() {
+        let cache = Cache::new(Duration::from_secs(60), 2);
+        cache.insert(1, 1);
+
+        let _ = catch_unwind(AssertUnwindSafe(|| {
+            cache.update(&1, |_| panic!("Panicked while holding the lock."));
+        }));
+
+        assert!(cache.update(&1, |value| *value += 1));
+        assert_eq!(cache.get(&1), Some(2));
+    }
+}
@@ -0,0 +1,90 @@
+use super::memory_cache::MemoryCache;
+use std::time::Duration;
+use actix_web::{rt::time::sleep, web::Data};
+use serde::Deserialize;
+use sqlx::{postgres::PgListener, PgPool};
+
+/// The channel that changes to rotations are notified on by the database.
+const CHANNEL: &str = "cache_invalidation";
+
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Invalidation {
+    rotation_id: i32
+}
+
+/// Listens for changes to rotations made by any instance, and evicts what the memory cache holds about them.
+///
+/// The listener reconnects whenever its connection drops, backing off while the database is unreachable.
+/// Since notifications sent while disconnected are lost, the whole cache is cleared after every reconnection.
+///
+/// # Arguments
+///
+/// * `pool` - A connection pool to the database, which the listener takes a dedicated connection from.
+/// * `memory_cache` - The memory cache shared by every worker of this instance.
+pub async fn listen(pool: PgPool, memory_cache: Data<MemoryCache>) {
+    let mut reconnect_delay = MIN_RECONNECT_DELAY;
+
+    loop {
+        let mut listener = match subscribe(&pool).await {
+            Ok(listener) => listener,
+            Err(error) => {
+                eprintln!("Failed to listen for cache invalidations, retrying in {:?}: {}", reconnect_delay, error);
+
+                sleep(reconnect_delay).await;
+                reconnect_delay = (reconnect_delay * 2).min(MAX_RECONNECT_DELAY);
+
+                continue;
+            }
+        };
+
+        memory_cache.clear();
+        reconnect_delay = MIN_RECONNECT_DELAY;
+
+        loop {
+            match listener.try_recv().await {
+                Ok(Some(notification)) => match parse(notification.payload()) {
+                    Some(rotation_id) => memory_cache.invalidate_rotation(rotation_id),
+                    None => memory_cache.clear()
+                },
+
+                // The connection dropped and the listener reconnects on the next call, so anything could have changed meanwhile.
+                Ok(None) => memory_cache.clear(),
+                Err(error) => {
+                    eprintln!("Lost the cache invalidation listener: {}", error);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+async fn subscribe(pool: &PgPool) -> sqlx::Result<PgListener> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(CHANNEL).await?;
+
+    Ok(listener)
+}
+
+/// Parses the ID of the changed rotation from a notification, or `None` if it is malformed.
+#[inline(always)]
+fn parse(payload: &str) -> Option<i32> {
+    serde_json::from_str::<Invalidation>(payload)
+        .ok()
+        .map(|invalidation| invalidation.rotation_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(r#"{"rotationId" : 3}"#), Some(3));
+        assert_eq!(parse(r#"{"rotation_id": 3}"#), None);
+        assert_eq!(parse("3"), None);
+    }
+}
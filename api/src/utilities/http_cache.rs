@@ -0,0 +1,155 @@
+use actix_web::{http::{header::{self, CacheControl, CacheDirective, ContentType, ETag, EntityTag, Header, HttpDate, IfModifiedSince, IfNoneMatch, LastModified}, StatusCode}, HttpRequest, HttpResponse, HttpResponseBuilder};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::{hash::{DefaultHasher, Hasher}, time::SystemTime};
+
+/// The validators of a response, which a client sends back in a conditional request to find out whether its copy is still current.
+pub struct Validators {
+    etag: EntityTag,
+    last_modified: Option<DateTime<Utc>>
+}
+
+impl Validators {
+    /// Creates validators from what a response was derived from.
+    ///
+    /// # Arguments
+    ///
+    /// * `etag` - A tag that changes whenever the body of the response would.
+    /// * `last_modified` - When the body of the response last changed, or `None` if that is not known for certain.
+    pub fn new(etag: EntityTag, last_modified: Option<DateTime<Utc>>) -> Self {
+        Self { etag, last_modified }
+    }
+
+    /// Creates validators whose entity tag is a hash of the body of a response.
+    pub fn of_body(body: &[u8], last_modified: Option<DateTime<Utc>>) -> Self {
+        let mut hasher = DefaultHasher::new();
+        hasher.write(body);
+
+        Self::new(EntityTag::new_strong(format!("{:016x}", hasher.finish())), last_modified)
+    }
+
+    /// Checks whether the client making a request already has the current response.
+    ///
+    /// `If-None-Match` takes precedence, so `If-Modified-Since` is only used when there is no entity tag to compare.
+    /// Since HTTP dates are only precise to the second, a change in the same second as `If-Modified-Since` is only caught by the entity tag.
+    pub fn is_fresh(&self, req: &HttpRequest) -> bool {
+        if req.headers().contains_key(header::IF_NONE_MATCH) {
+            return match IfNoneMatch::parse(req) {
+                Ok(IfNoneMatch::Any) => true,
+                Ok(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&self.etag)),
+                Err(_) => false
+            };
+        }
+
+        match (self.last_modified, IfModifiedSince::parse(req)) {
+            (Some(last_modified), Ok(IfModifiedSince(since))) => SystemTime::from(truncate_to_seconds(last_modified)) <= SystemTime::from(since),
+            _ => false
+        }
+    }
+
+    /// Starts a response carrying the validators.
+    ///
+    /// Clients have to revalidate before reusing the response, and shared caches must not store it since it depends on who is asking.
+    pub fn response(&self, status: StatusCode) -> HttpResponseBuilder {
+        let mut response = HttpResponse::build(status);
+
+        response
+            .insert_header(ETag(self.etag.clone()))
+            .insert_header(CacheControl(vec![CacheDirective::Private, CacheDirective::NoCache]))
+            .insert_header((header::VARY, "Authorization"));
+
+        if let Some(last_modified) = self.last_modified {
+            response.insert_header(LastModified(HttpDate::from(SystemTime::from(truncate_to_seconds(last_modified)))));
+        }
+
+        response
+    }
+
+    /// Responds with `304 Not Modified` if the client already has the current response.
+    pub fn not_modified(&self, req: &HttpRequest) -> Option<HttpResponse> {
+        match self.is_fresh(req) {
+            true => Some(self.response(StatusCode::NOT_MODIFIED).finish()),
+            false => None
+        }
+    }
+}
+
+#[inline(always)]
+fn truncate_to_seconds(timestamp: DateTime<Utc>) -> DateTime<Utc> {
+    DateTime::from_timestamp(timestamp.timestamp(), 0).unwrap_or(timestamp)
+}
+
+/// Responds to a request that can be conditional with a JSON body, whose entity tag is a hash of it.
+///
+/// # Arguments
+///
+/// * `req` - The request, which may have `If-None-Match` or `If-Modified-Since` headers.
+/// * `body` - The body of the response.
+/// * `last_modified` - When the body last changed, or `None` if that is not known for certain.
+///
+/// # Returns
+///
+/// The JSON response, or `304 Not Modified` without a body if the client already has it.
+pub fn respond_json<T: Serialize>(req: &HttpRequest, body: &T, last_modified: Option<DateTime<Utc>>) -> HttpResponse {
+    let body = match serde_json::to_vec(body) {
+        Ok(body) => body,
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    };
+
+    let validators = Validators::of_body(&body, last_modified);
+
+    if let Some(response) = validators.not_modified(req) {
+        return response;
+    }
+
+    validators
+        .response(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use chrono::TimeZone;
+
+    fn validators() -> Validators {
+        Validators::new(EntityTag::new_strong("abc".to_string()), Some(Utc.with_ymd_and_hms(2026, 10, 19, 12, 0, 0).unwrap()))
+    }
+
+    #[test]
+    fn test_if_none_match() {
+        let req = TestRequest::default().insert_header((header::IF_NONE_MATCH, "\"xyz\", W/\"abc\"")).to_http_request();
+        assert!(validators().is_fresh(&req));
+
+        let req = TestRequest::default().insert_header((header::IF_NONE_MATCH, "\"xyz\"")).to_http_request();
+        assert!(!validators().is_fresh(&req));
+
+        let req = TestRequest::default().insert_header((header::IF_NONE_MATCH, "*")).to_http_request();
+        assert!(validators().is_fresh(&req));
+    }
+
+    #[test]
+    fn test_if_modified_since() {
+        let req = TestRequest::default().insert_header((header::IF_MODIFIED_SINCE, "Mon, 19 Oct 2026 12:00:00 GMT")).to_http_request();
+        assert!(validators().is_fresh(&req));
+
+        let req = TestRequest::default().insert_header((header::IF_MODIFIED_SINCE, "Mon, 19 Oct 2026 11:59:59 GMT")).to_http_request();
+        assert!(!validators().is_fresh(&req));
+
+        let unknown = Validators::new(EntityTag::new_strong("abc".to_string()), None);
+        let req = TestRequest::default().insert_header((header::IF_MODIFIED_SINCE, "Mon, 19 Oct 2026 12:00:00 GMT")).to_http_request();
+        assert!(!unknown.is_fresh(&req));
+    }
+
+    #[test]
+    fn test_if_none_match_takes_precedence() {
+        let req = TestRequest::default()
+            .insert_header((header::IF_NONE_MATCH, "\"xyz\""))
+            .insert_header((header::IF_MODIFIED_SINCE, "Mon, 19 Oct 2026 12:00:00 GMT"))
+            .to_http_request();
+
+        assert!(!validators().is_fresh(&req));
+    }
+}
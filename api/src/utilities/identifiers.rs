@@ -0,0 +1,56 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Patterns that commonly appear in patient identifiers, matched case insensitively.
+///
+/// * Runs of six or more digits, optionally separated by spaces or dashes, such as health numbers, MRNs and phone numbers.
+/// * Labels that introduce an identifier, such as `MRN`, `PHN`, `ULI` or `DOB`.
+/// * Full dates, which may be a date of birth.
+const IDENTIFIER_PATTERNS: [&str; 3] = [
+    r"\d(?:[\s\-]?\d){5,}",
+    r"\b(?:mrn|phn|uli|hcn|dob|d\.o\.b|health\s*(?:card|number|no)|medical\s*record|chart\s*(?:number|no)|date\s*of\s*birth|born\s*on)\b",
+    r"\b\d{1,4}[/\-.]\d{1,2}[/\-.]\d{2,4}\b"
+];
+
+fn identifier_patterns() -> &'static [Regex] {
+    static PATTERNS: OnceLock<Vec<Regex>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        IDENTIFIER_PATTERNS
+            .iter()
+            .map(|pattern| Regex::new(&format!("(?i){}", pattern)).expect("Expected identifier patterns to be valid."))
+            .collect()
+    })
+}
+
+/// Checks whether free text looks like it contains a patient identifier.
+///
+/// This is a safeguard against accidental disclosure rather than a guarantee, so it errs on the side of rejecting text.
+pub fn contains_identifier(text: &str) -> bool {
+    identifier_patterns()
+        .iter()
+        .any(|pattern| pattern.is_match(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_de_identified_text() {
+        assert!(!contains_identifier("Contoured a T2N1 larynx for 70 Gy in 35 fractions with bilateral neck."));
+    }
+
+    #[test]
+    fn test_health_numbers_and_mrns() {
+        assert!(contains_identifier("Patient 12345-6789 seen in clinic"));
+        assert!(contains_identifier("chart 0012 3456 78"));
+        assert!(contains_identifier("MRN: A1234"));
+        assert!(contains_identifier("their phn was on the requisition"));
+    }
+
+    #[test]
+    fn test_dates_of_birth() {
+        assert!(contains_identifier("DOB 1954"));
+        assert!(contains_identifier("born 03/07/1954"));
+    }
+}
@@ -1,58 +1,138 @@
-use super::phantom_data::Synced;
+use super::{cache::{Cache, CacheMetrics}, phantom_data::Synced};
 use crate::models::rotation::Rotation;
-use std::{collections::HashMap, sync::RwLock};
-use anyhow::{anyhow, Result};
+use std::{collections::HashMap, env::var, sync::atomic::{AtomicU64, Ordering}, time::Duration};
+use actix_web::web::Bytes;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 
-type CachedRotations = Option<HashMap<i32, Rotation<Synced>>>;
+const DEFAULT_ROTATIONS_TTL_SECONDS: u64 = 600;
+const DEFAULT_ENTRIES_TTL_SECONDS: u64 = 600;
+const DEFAULT_ENTRIES_CAPACITY: usize = 128;
 
+/// The rotations, along with when they were last changed in the cache.
+#[derive(Clone)]
+pub struct CachedRotations {
+    pub rotations: HashMap<i32, Rotation<Synced>>,
+    pub changed_at: DateTime<Utc>
+}
+
+/// The serialized draft entries of a rotation, along with when the rotation was last updated as of fetching them.
+#[derive(Clone)]
+pub struct CachedEntries {
+    pub body: Bytes,
+    pub last_updated: DateTime<Utc>
+}
+
+#[derive(Serialize)]
+pub struct MemoryCacheMetrics {
+    pub rotations: CacheMetrics,
+    pub entries: CacheMetrics
+}
+
+/// Data cached in memory to avoid database queries, shared by every worker of an instance.
 pub struct MemoryCache {
-    cached_rotations_lock: RwLock<CachedRotations>
+    /// There is only one set of rotations, so it is cached under the unit key.
+    rotations: Cache<(), CachedRotations>,
+    /// Changes whenever the rotations are invalidated, so that rotations fetched before then are not cached.
+    rotations_generation: AtomicU64,
+    entries: Cache<i32, CachedEntries>
 }
 
 impl MemoryCache {
-    pub fn new() -> Self {
-        Self { cached_rotations_lock: RwLock::new(None) }
+    /// Builds the memory cache from the environment.
+    ///
+    /// * `ROTATIONS_CACHE_TTL_SECONDS` - How long the rotations are cached before they are fetched again.
+    /// * `ENTRIES_CACHE_TTL_SECONDS` - How long the entries of a rotation are cached before they are fetched again.
+    /// * `ENTRIES_CACHE_CAPACITY` - The most rotations whose entries are cached at once.
+    pub fn from_env() -> Self {
+        let parse = |env_key: &str, default: u64| match var(env_key) {
+            Ok(value) => value.parse().expect("Expected memory cache configuration to be a whole number."),
+            Err(_) => default
+        };
+
+        let rotations_ttl = Duration::from_secs(parse("ROTATIONS_CACHE_TTL_SECONDS", DEFAULT_ROTATIONS_TTL_SECONDS));
+        let entries_ttl = Duration::from_secs(parse("ENTRIES_CACHE_TTL_SECONDS", DEFAULT_ENTRIES_TTL_SECONDS));
+        let entries_capacity = parse("ENTRIES_CACHE_CAPACITY", DEFAULT_ENTRIES_CAPACITY as u64) as usize;
+
+        Self {
+            rotations: Cache::new(rotations_ttl, 1),
+            rotations_generation: AtomicU64::new(0),
+            entries: Cache::new(entries_ttl, entries_capacity)
+        }
     }
 
-    pub fn get_rotations_as_clone(&self) -> Result<CachedRotations> {
-        let read_lock = self.cached_rotations_lock
-            .try_read()
-            .map_err(|_| anyhow!("Failed to acquire read lock on cached rotations."))?;
+    /// Gets a clone of the cached rotations.
+    /// Changes to rotations made through this instance update the cache and changes made elsewhere invalidate it,
+    /// so the rotations have not changed since they were last changed in it.
+    pub fn get_rotations(&self) -> Option<CachedRotations> {
+        self.rotations.get(&())
+    }
 
-        Ok((*read_lock).clone())
+    /// Gets the generation of the cached rotations, which is read before fetching them to pass to `set_rotations`.
+    pub fn rotations_generation(&self) -> u64 {
+        self.rotations_generation.load(Ordering::SeqCst)
     }
 
-    pub fn set_rotations(&self, rotations: CachedRotations) -> Result<()> {
-        let mut write_lock = self.cached_rotations_lock
-            .try_write()
-            .map_err(|_| anyhow!("Failed to acquire write lock on cached rotations."))?;
+    /// Caches the rotations, unless they were invalidated since `generation` was read and may have been fetched before a change.
+    ///
+    /// # Returns
+    ///
+    /// Whether the rotations were cached.
+    pub fn set_rotations(&self, rotations: CachedRotations, generation: u64) -> bool {
+        self.rotations.insert_if((), rotations, || self.rotations_generation() == generation)
+    }
 
-        *write_lock = rotations;
+    pub fn insert_rotation(&self, rotation: Rotation<Synced>) {
+        self.rotations.update(&(), |cached| {
+            cached.rotations.insert(rotation.id(), rotation);
+            cached.changed_at = Utc::now();
+        });
+    }
 
-        Ok(())
+    pub fn delete_rotation(&self, rotation_id: i32) {
+        self.rotations.update(&(), |cached| {
+            cached.rotations.remove(&rotation_id);
+            cached.changed_at = Utc::now();
+        });
+
+        self.entries.remove(&rotation_id);
     }
 
-    pub fn insert_rotation(&self, rotation: Rotation<Synced>) -> Result<()> {
-        let mut write_lock = self.cached_rotations_lock
-            .try_write()
-            .map_err(|_| anyhow!("Failed to acquire write lock on cached rotations."))?;
+    /// Evicts everything cached about a rotation after it has been changed, possibly by another instance.
+    pub fn invalidate_rotation(&self, rotation_id: i32) {
+        self.rotations_generation.fetch_add(1, Ordering::SeqCst);
+        self.rotations.remove(&());
+        self.entries.remove(&rotation_id);
+    }
 
-        if let Some(cached_rotations) = write_lock.as_mut() {
-            cached_rotations.insert(rotation.id(), rotation);
-        }
+    /// Evicts everything cached, for when changes may have been missed.
+    pub fn clear(&self) {
+        self.rotations_generation.fetch_add(1, Ordering::SeqCst);
+        self.rotations.clear();
+        self.entries.clear();
+    }
 
-        Ok(())
+    /// Gets the serialized draft entries of a rotation, if they are cached and the rotation has not been updated since.
+    ///
+    /// # Arguments
+    ///
+    /// * `rotation_id` - The ID of the rotation.
+    /// * `last_updated` - When the rotation was last updated according to the database.
+    pub fn get_entries(&self, rotation_id: i32, last_updated: DateTime<Utc>) -> Option<Bytes> {
+        self.entries
+            .get(&rotation_id)
+            .filter(|cached| cached.last_updated == last_updated)
+            .map(|cached| cached.body)
     }
 
-    pub fn delete_rotation(&self, rotation_id: i32) -> Result<()> {
-        let mut write_lock = self.cached_rotations_lock
-            .try_write()
-            .map_err(|_| anyhow!("Failed to acquire write lock on cached rotations."))?;
+    pub fn set_entries(&self, rotation_id: i32, entries: CachedEntries) {
+        self.entries.insert(rotation_id, entries);
+    }
 
-        if let Some(cached_rotations) = write_lock.as_mut() {
-            cached_rotations.remove(&rotation_id);
+    pub fn metrics(&self) -> MemoryCacheMetrics {
+        MemoryCacheMetrics {
+            rotations: self.rotations.metrics(),
+            entries: self.entries.metrics()
         }
-
-        Ok(())
     }
 }
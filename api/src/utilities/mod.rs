@@ -1,5 +1,14 @@
 mod regex;
 pub mod parsable;
+pub mod rich_text;
 pub mod phantom_data;
 pub mod user_session;
 pub mod memory_cache;
+pub mod trusted_proxies;
+pub mod outline;
+pub mod identifiers;
+pub mod pdf;
+pub mod spreadsheet;
+pub mod http_cache;
+pub mod cache;
+pub mod cache_invalidation;
\ No newline at end of file
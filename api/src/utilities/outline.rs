@@ -0,0 +1,245 @@
+use super::parsable::EntryTitle;
+use crate::models::curriculum::{CurriculumSubtask, CurriculumSupertask, CurriculumTask};
+use serde::Serialize;
+
+/// The number of spaces a tab is worth when measuring indentation.
+const TAB_WIDTH: usize = 4;
+
+/// A problem with a line of an outline or CSV file.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct OutlineError {
+    /// The 1-based line number the problem was found on.
+    pub line: u64,
+    pub message: String
+}
+
+impl OutlineError {
+    fn new(line: u64, message: impl Into<String>) -> Self {
+        Self { line, message: message.into() }
+    }
+}
+
+/// Parses a title, recording an error against the line if it is invalid.
+fn parse_title(errors: &mut Vec<OutlineError>, line: u64, title: &str) -> String {
+    match EntryTitle::parse(title.to_string()) {
+        Ok(title) => String::from(title),
+        Err(_) => {
+            errors.push(OutlineError::new(line, format!("Invalid title \"{}\"", title.trim())));
+            String::new()
+        }
+    }
+}
+
+/// Parses a Markdown style indented outline, where top level items are supertasks, their children are tasks and their grandchildren are subtasks.
+///
+/// ```text
+/// - Planning
+///   - Contouring
+///     - Prostate
+/// ```
+///
+/// List markers (`-`, `*` and `+`) are optional, and blank lines are ignored.
+///
+/// # Returns
+///
+/// The supertasks in the outline, or every problem found along with the line it was found on.
+pub fn parse_outline(text: &str) -> Result<Vec<CurriculumSupertask>, Vec<OutlineError>> {
+    let mut supertasks: Vec<CurriculumSupertask> = Vec::new();
+    let mut errors = Vec::new();
+
+    // The indentation of each level that is currently open, from supertasks down.
+    let mut indents: Vec<usize> = Vec::with_capacity(3);
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index as u64 + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let indent = line
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+            .sum::<usize>();
+
+        let content = line.trim();
+        let content = match content.strip_prefix(['-', '*', '+']) {
+            Some(stripped) if stripped.is_empty() || stripped.starts_with(char::is_whitespace) => stripped,
+            _ => content
+        };
+
+        while indents.last().is_some_and(|last| indent < *last) {
+            indents.pop();
+        }
+
+        let depth = match indents.last() {
+            Some(last) if indent == *last => indents.len() - 1,
+            Some(_) if indents.len() == 3 => {
+                errors.push(OutlineError::new(line_number, "Entries can only be nested three levels deep"));
+                continue;
+            },
+            Some(_) => {
+                indents.push(indent);
+                indents.len() - 1
+            },
+            None if supertasks.is_empty() || indent == 0 => {
+                indents.push(indent);
+                0
+            },
+            None => {
+                errors.push(OutlineError::new(line_number, "Indentation does not match any previous line"));
+                continue;
+            }
+        };
+
+        let title = parse_title(&mut errors, line_number, content);
+        match depth {
+            0 => supertasks.push(CurriculumSupertask { title, tasks: Vec::new() }),
+            1 => if let Some(supertask) = supertasks.last_mut() {
+                supertask.tasks.push(CurriculumTask { title, subtasks: Vec::new() });
+            },
+            _ => if let Some(task) = supertasks.last_mut().and_then(|supertask| supertask.tasks.last_mut()) {
+                task.subtasks.push(CurriculumSubtask { title });
+            }
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(supertasks),
+        false => Err(errors)
+    }
+}
+
+/// Parses a CSV file with supertask, task and subtask columns, where each row adds entries under the supertask and task it names.
+///
+/// The task and subtask columns may be left empty, and a `supertask,task,subtask` header row is optional.
+///
+/// # Returns
+///
+/// The supertasks in the file, or every problem found along with the line it was found on.
+pub fn parse_csv(bytes: &[u8]) -> Result<Vec<CurriculumSupertask>, Vec<OutlineError>> {
+    let mut supertasks: Vec<CurriculumSupertask> = Vec::new();
+    let mut errors = Vec::new();
+
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(bytes);
+
+    for (index, record) in reader.records().enumerate() {
+        let record = match record {
+            Ok(record) => record,
+            Err(error) => {
+                let line_number = error.position().map(|position| position.line()).unwrap_or_default();
+                errors.push(OutlineError::new(line_number, "Malformed row"));
+                continue;
+            }
+        };
+
+        let line_number = record.position().map(|position| position.line()).unwrap_or(index as u64 + 1);
+        let columns = record.iter().map(str::trim).collect::<Vec<_>>();
+
+        if index == 0 && columns.iter().map(|column| column.to_lowercase()).eq(["supertask", "task", "subtask"]) {
+            continue;
+        }
+
+        if columns.iter().all(|column| column.is_empty()) {
+            continue;
+        }
+
+        if columns.len() > 3 {
+            errors.push(OutlineError::new(line_number, "Expected at most 3 columns"));
+            continue;
+        }
+
+        let supertask_title = columns.first().copied().unwrap_or_default();
+        let task_title = columns.get(1).copied().unwrap_or_default();
+        let subtask_title = columns.get(2).copied().unwrap_or_default();
+
+        if task_title.is_empty() && !subtask_title.is_empty() {
+            errors.push(OutlineError::new(line_number, "A subtask must belong to a task"));
+            continue;
+        }
+
+        let supertask_title = parse_title(&mut errors, line_number, supertask_title);
+        let supertask_index = match supertasks.iter().position(|supertask| supertask.title.eq_ignore_ascii_case(&supertask_title)) {
+            Some(index) => index,
+            None => {
+                supertasks.push(CurriculumSupertask { title: supertask_title, tasks: Vec::new() });
+                supertasks.len() - 1
+            }
+        };
+
+        if task_title.is_empty() {
+            continue;
+        }
+
+        let tasks = &mut supertasks[supertask_index].tasks;
+        let task_title = parse_title(&mut errors, line_number, task_title);
+        let task_index = match tasks.iter().position(|task| task.title.eq_ignore_ascii_case(&task_title)) {
+            Some(index) => index,
+            None => {
+                tasks.push(CurriculumTask { title: task_title, subtasks: Vec::new() });
+                tasks.len() - 1
+            }
+        };
+
+        if subtask_title.is_empty() {
+            continue;
+        }
+
+        let title = parse_title(&mut errors, line_number, subtask_title);
+        tasks[task_index].subtasks.push(CurriculumSubtask { title });
+    }
+
+    match errors.is_empty() {
+        true => Ok(supertasks),
+        false => Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_outline_valid() {
+        let outline = "- Planning\n  - Contouring\n    - Prostate\n    - Breast\n\n  - Dose\n- Clinic\n";
+        let supertasks = parse_outline(outline).unwrap();
+
+        assert_eq!(supertasks.len(), 2);
+        assert_eq!(supertasks[0].tasks.len(), 2);
+        assert_eq!(supertasks[0].tasks[0].subtasks.len(), 2);
+        assert_eq!(supertasks[0].tasks[0].subtasks[1].title, "Breast");
+        assert_eq!(supertasks[1].title, "Clinic");
+    }
+
+    #[test]
+    fn test_parse_outline_without_markers() {
+        let supertasks = parse_outline("Planning\n\tContouring\n\t\tProstate").unwrap();
+        assert_eq!(supertasks[0].tasks[0].subtasks[0].title, "Prostate");
+    }
+
+    #[test]
+    fn test_parse_outline_invalid() {
+        let errors = parse_outline("- Planning\n  - <b>Contouring</b>\n    - Prostate\n      - Too Deep").unwrap_err();
+        assert_eq!(errors.iter().map(|error| error.line).collect::<Vec<_>>(), [2, 4]);
+    }
+
+    #[test]
+    fn test_parse_csv_valid() {
+        let csv = "Supertask,Task,Subtask\nPlanning,Contouring,Prostate\nplanning,Contouring,\"Head and Neck\"\nClinic,,\n";
+        let supertasks = parse_csv(csv.as_bytes()).unwrap();
+
+        assert_eq!(supertasks.len(), 2);
+        assert_eq!(supertasks[0].tasks.len(), 1);
+        assert_eq!(supertasks[0].tasks[0].subtasks.len(), 2);
+        assert!(supertasks[1].tasks.is_empty());
+    }
+
+    #[test]
+    fn test_parse_csv_invalid() {
+        let errors = parse_csv("Planning,Contouring,Prostate\nPlanning,,Orphan\nPlanning,Dose,<script>".as_bytes()).unwrap_err();
+        assert_eq!(errors.iter().map(|error| error.line).collect::<Vec<_>>(), [2, 3]);
+    }
+}
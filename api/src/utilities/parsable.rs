@@ -52,6 +52,11 @@ parsable!(PlainTextPassword, PASSWORD_REGEX);
 parsable!(Comment, COMMENT_REGEX);
 parsable!(EntryTitle, ENTRY_TITLE_REGEX);
 parsable!(ResetToken, RESET_TOKEN_REGEX);
+parsable!(Description, DESCRIPTION_REGEX);
+parsable!(LearningObjective, LEARNING_OBJECTIVE_REGEX);
+parsable!(ReferenceUrl, REFERENCE_URL_REGEX);
+parsable!(Location, LOCATION_REGEX);
+parsable!(ConfirmationToken, CONFIRMATION_TOKEN_REGEX);
 
 #[cfg(test)]
 mod tests {
@@ -134,4 +139,52 @@ mod tests {
         let result = ResetToken::parse("abc".to_string());
         assert!(result.is_err());
     }
-}
+
+    #[test]
+    fn test_parse_description_valid() {
+        let description = Description::parse("Inpatient service: 4 weeks (includes clinic).".to_string()).unwrap();
+        assert_eq!(description.as_str(), "Inpatient service: 4 weeks (includes clinic).");
+    }
+
+    #[test]
+    fn test_parse_description_invalid() {
+        let result = Description::parse("<img src=x onerror=alert(1)>".to_string());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_learning_objective_valid() {
+        let objective = LearningObjective::parse("Describe the OAR dose constraints for a 5 fraction SBRT plan.".to_string()).unwrap();
+        assert_eq!(objective.as_str(), "Describe the OAR dose constraints for a 5 fraction SBRT plan.");
+    }
+
+    #[test]
+    fn test_parse_learning_objective_invalid() {
+        let result = LearningObjective::parse("   ".to_string());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_reference_url_valid() {
+        let url = ReferenceUrl::parse("https://www.astro.org/patient-care-and-research/clinical-practice-statements?page=2".to_string()).unwrap();
+        assert_eq!(url.as_str(), "https://www.astro.org/patient-care-and-research/clinical-practice-statements?page=2");
+    }
+
+    #[test]
+    fn test_parse_reference_url_invalid() {
+        let result = ReferenceUrl::parse("javascript:alert(1)".to_string());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_location_valid() {
+        let location = Location::parse("Tom Baker Cancer Centre, Calgary".to_string()).unwrap();
+        assert_eq!(location.as_str(), "Tom Baker Cancer Centre, Calgary");
+    }
+
+    #[test]
+    fn test_parse_location_invalid() {
+        let result = Location::parse("<b>Calgary</b>".to_string());
+        assert!(result.is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,286 @@
+use anyhow::Result;
+use printpdf::{BuiltinFont, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Point};
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+const CONTENT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN;
+
+/// Where content starts below the page header and stops above the page footer, in millimetres from the bottom of the page.
+const CONTENT_TOP: f32 = PAGE_HEIGHT - 36.0;
+const CONTENT_BOTTOM: f32 = 22.0;
+
+/// The width of the column right aligned values are drawn in.
+const VALUE_COLUMN_WIDTH: f32 = 32.0;
+
+const POINTS_TO_MM: f32 = 25.4 / 72.0;
+const LINE_SPACING: f32 = 1.4;
+
+/// The height of a signature field, from the space left to sign in down to its label.
+const SIGNATURE_HEIGHT: f32 = 24.0;
+
+/// The advance widths of the printable ASCII characters in Helvetica, in thousandths of the font size.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556,
+    278, 278, 584, 584, 584, 556, 1015,
+    667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611,
+    278, 278, 278, 469, 556, 333,
+    556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500,
+    334, 260, 334, 584
+];
+
+/// Helvetica Bold is slightly wider than Helvetica, so bold text is measured as if it were this much wider to be safe.
+const BOLD_WIDTH_FACTOR: f32 = 1.1;
+
+/// Replaces anything the built in fonts cannot draw with a question mark.
+fn sanitize(text: &str) -> String {
+    text
+        .chars()
+        .map(|c| if c == ' ' || c.is_ascii_graphic() { c } else { '?' })
+        .collect()
+}
+
+/// Measures the width of a line of text in millimetres.
+fn text_width(text: &str, size: f32, bold: bool) -> f32 {
+    let units = text
+        .chars()
+        .map(|c| match c {
+            ' '..='~' => HELVETICA_WIDTHS[c as usize - ' ' as usize] as f32,
+            _ => 722.0
+        })
+        .sum::<f32>();
+
+    let factor = if bold { BOLD_WIDTH_FACTOR } else { 1.0 };
+    units / 1000.0 * size * POINTS_TO_MM * factor
+}
+
+/// The height of a line of text in millimetres.
+fn line_height(size: f32) -> f32 {
+    size * POINTS_TO_MM * LINE_SPACING
+}
+
+/// Wraps text into lines no wider than a width in millimetres, keeping its line breaks.
+///
+/// Words are kept whole unless they are wider than a line on their own, in which case they are broken wherever they overflow.
+pub fn wrap_text(text: &str, size: f32, max_width: f32, bold: bool) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+
+        for word in paragraph.split_whitespace() {
+            let candidate = match line.is_empty() {
+                true => word.to_string(),
+                false => format!("{line} {word}")
+            };
+
+            if text_width(&candidate, size, bold) <= max_width {
+                line = candidate;
+                continue;
+            }
+
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+
+            for c in word.chars() {
+                line.push(c);
+                if line.chars().count() > 1 && text_width(&line, size, bold) > max_width {
+                    line.pop();
+                    lines.push(std::mem::replace(&mut line, c.to_string()));
+                }
+            }
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// A paginated A4 document, which breaks onto new pages as content is added and repeats a header and footer on each of them.
+pub struct PdfReport {
+    document: PdfDocumentReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    title: String,
+    subtitle: String,
+    footer: String,
+    pages: Vec<PdfLayerReference>,
+    cursor: f32
+}
+
+impl PdfReport {
+    /// Starts a document on its first page.
+    ///
+    /// # Parameters
+    ///
+    /// * `title` - The title of the document, shown at the top of each page.
+    /// * `subtitle` - A line shown below the title on each page.
+    /// * `footer` - A line shown at the bottom left of each page, alongside the page number.
+    pub fn new(title: &str, subtitle: &str, footer: &str) -> Result<Self> {
+        let (document, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Content");
+        let regular = document.add_builtin_font(BuiltinFont::Helvetica)?;
+        let bold = document.add_builtin_font(BuiltinFont::HelveticaBold)?;
+        let first_page = document.get_page(page).get_layer(layer);
+
+        let mut report = Self {
+            document,
+            regular,
+            bold,
+            title: sanitize(title),
+            subtitle: sanitize(subtitle),
+            footer: sanitize(footer),
+            pages: Vec::new(),
+            cursor: CONTENT_TOP
+        };
+
+        report.start_page(first_page);
+        Ok(report)
+    }
+
+    fn start_page(&mut self, layer: PdfLayerReference) {
+        layer.use_text(self.title.as_str(), 14.0, Mm(MARGIN), Mm(PAGE_HEIGHT - 20.0), &self.bold);
+        layer.use_text(self.subtitle.as_str(), 9.0, Mm(MARGIN), Mm(PAGE_HEIGHT - 26.0), &self.regular);
+        Self::draw_rule(&layer, MARGIN, PAGE_WIDTH - MARGIN, PAGE_HEIGHT - 30.0);
+
+        self.pages.push(layer);
+        self.cursor = CONTENT_TOP;
+    }
+
+    fn layer(&self) -> &PdfLayerReference {
+        // There is always at least the first page.
+        &self.pages[self.pages.len() - 1]
+    }
+
+    fn draw_rule(layer: &PdfLayerReference, from: f32, to: f32, y: f32) {
+        layer.set_outline_thickness(0.5);
+        layer.add_line(Line {
+            points: vec![(Point::new(Mm(from), Mm(y)), false), (Point::new(Mm(to), Mm(y)), false)],
+            is_closed: false
+        });
+    }
+
+    /// Moves onto a new page unless there is enough room left on this one for content of a height.
+    fn reserve(&mut self, height: f32) {
+        if self.cursor - height >= CONTENT_BOTTOM || self.cursor == CONTENT_TOP {
+            return;
+        }
+
+        let (page, layer) = self.document.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Content");
+        let layer = self.document.get_page(page).get_layer(layer);
+        self.start_page(layer);
+    }
+
+    /// Adds vertical space, which is dropped at the top of a page.
+    pub fn space(&mut self, height: f32) {
+        if self.cursor - height < CONTENT_BOTTOM {
+            self.reserve(height);
+            return;
+        }
+
+        if self.cursor != CONTENT_TOP {
+            self.cursor -= height;
+        }
+    }
+
+    /// Adds wrapped text, breaking onto new pages between its lines as needed.
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The text to add.
+    /// * `size` - The font size in points.
+    /// * `indent` - How far to indent the text from the left margin in millimetres.
+    /// * `bold` - Whether to use the bold font.
+    pub fn text(&mut self, text: &str, size: f32, indent: f32, bold: bool) {
+        self.row(text, "", size, indent, bold);
+    }
+
+    /// Adds wrapped text with a value right aligned against its first line, such as a label and a percentage.
+    pub fn row(&mut self, text: &str, value: &str, size: f32, indent: f32, bold: bool) {
+        let height = line_height(size);
+        let width = match value.is_empty() {
+            true => CONTENT_WIDTH - indent,
+            false => CONTENT_WIDTH - indent - VALUE_COLUMN_WIDTH
+        };
+
+        let value = sanitize(value);
+        for (index, line) in wrap_text(&sanitize(text), size, width, bold).into_iter().enumerate() {
+            self.reserve(height);
+            self.cursor -= height;
+
+            let font = if bold { &self.bold } else { &self.regular };
+            let baseline = self.cursor + height * 0.3;
+            self.layer().use_text(line, size, Mm(MARGIN + indent), Mm(baseline), font);
+
+            if index == 0 && !value.is_empty() {
+                let x = PAGE_WIDTH - MARGIN - text_width(&value, size, bold);
+                self.layer().use_text(value.as_str(), size, Mm(x), Mm(baseline), font);
+            }
+        }
+    }
+
+    /// Adds a signature and date field for each signatory, all on the same page.
+    pub fn signature_block(&mut self, signatories: &[&str]) {
+        self.reserve(line_height(11.0) + 4.0 + SIGNATURE_HEIGHT * signatories.len() as f32);
+        self.text("Sign-off", 11.0, 0.0, true);
+        self.cursor -= 4.0;
+
+        for signatory in signatories {
+            self.cursor -= SIGNATURE_HEIGHT - 8.0;
+            let layer = self.layer();
+            Self::draw_rule(layer, MARGIN, MARGIN + 100.0, self.cursor);
+            Self::draw_rule(layer, PAGE_WIDTH - MARGIN - 50.0, PAGE_WIDTH - MARGIN, self.cursor);
+
+            layer.use_text(sanitize(&format!("{signatory} signature")), 9.0, Mm(MARGIN), Mm(self.cursor - 4.5), &self.regular);
+            layer.use_text("Date", 9.0, Mm(PAGE_WIDTH - MARGIN - 50.0), Mm(self.cursor - 4.5), &self.regular);
+            self.cursor -= 8.0;
+        }
+    }
+
+    /// Numbers each page in its footer and renders the document.
+    pub fn finish(self) -> Result<Vec<u8>> {
+        let count = self.pages.len();
+
+        for (index, layer) in self.pages.iter().enumerate() {
+            let number = format!("Page {} of {}", index + 1, count);
+            Self::draw_rule(layer, MARGIN, PAGE_WIDTH - MARGIN, 17.0);
+            layer.use_text(self.footer.as_str(), 8.0, Mm(MARGIN), Mm(12.0), &self.regular);
+            layer.use_text(number.as_str(), 8.0, Mm(PAGE_WIDTH - MARGIN - text_width(&number, 8.0, false)), Mm(12.0), &self.regular);
+        }
+
+        drop(self.pages);
+        Ok(self.document.save_to_bytes()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_text_fits_width() {
+        let text = "The quick brown fox jumps over the lazy dog and keeps on running through the field";
+        let lines = wrap_text(text, 10.0, 50.0, false);
+
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| text_width(line, 10.0, false) <= 50.0));
+        assert_eq!(lines.join(" "), text);
+    }
+
+    #[test]
+    fn test_wrap_text_breaks_long_words() {
+        let word = "W".repeat(40);
+        let lines = wrap_text(&word, 10.0, 30.0, true);
+
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| text_width(line, 10.0, true) <= 30.0));
+        assert_eq!(lines.concat(), word);
+    }
+
+    #[test]
+    fn test_wrap_text_keeps_line_breaks() {
+        assert_eq!(wrap_text("First\n\nSecond", 10.0, 100.0, false), ["First", "", "Second"]);
+    }
+}
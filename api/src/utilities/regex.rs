@@ -3,4 +3,9 @@ pub(super) const NAME_REGEX: &str = r"^[a-zA-Z\s]{1,35}$";
 pub(super) const PASSWORD_REGEX: &str = r"^.{8,200}$";
 pub(super) const COMMENT_REGEX: &str = r#"^[a-zA-Z0-9\s.,!?'"()-]{0,150}$"#;
 pub(super) const ENTRY_TITLE_REGEX: &str = r"^[a-zA-Z0-9+\-/()\s]{1,100}$";
-pub(super) const RESET_TOKEN_REGEX: &str = r"^[a-zA-Z0-9]{4}$";
\ No newline at end of file
+pub(super) const DESCRIPTION_REGEX: &str = r#"^[a-zA-Z0-9\s.,!?'"()/:;-]{0,500}$"#;
+pub(super) const LEARNING_OBJECTIVE_REGEX: &str = r#"^[a-zA-Z0-9\s.,!?'"()/:;%+-]{1,300}$"#;
+pub(super) const REFERENCE_URL_REGEX: &str = r#"^https?://[^\s<>"'\\]{1,2000}$"#;
+pub(super) const LOCATION_REGEX: &str = r"^[a-zA-Z0-9\s.,'()\-]{0,100}$";
+pub(super) const RESET_TOKEN_REGEX: &str = r"^[a-zA-Z0-9]{4}$";
+pub(super) const CONFIRMATION_TOKEN_REGEX: &str = r"^[a-zA-Z0-9]{8}$";
\ No newline at end of file
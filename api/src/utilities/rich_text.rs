@@ -0,0 +1,83 @@
+use ammonia::Builder;
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize, Deserializer};
+use std::collections::HashSet;
+
+/// The maximum number of characters in rich text, after it has been sanitized.
+const MAX_RICH_TEXT_LENGTH: usize = 10000;
+
+/// The tags that are kept when sanitizing rich text. Everything else is stripped, keeping its text.
+const ALLOWED_TAGS: [&str; 15] = ["p", "br", "strong", "em", "u", "s", "code", "pre", "blockquote", "ul", "ol", "li", "a", "h3", "h4"];
+
+/// HTML rich text that has been sanitized down to basic formatting, lists and links, so that it is safe to render as is.
+#[derive(Serialize, Clone, PartialEq, Debug, Default)]
+pub struct RichText(String);
+
+impl From<RichText> for String {
+    fn from(item: RichText) -> Self {
+        item.0
+    }
+}
+
+impl From<String> for RichText {
+    fn from(s: String) -> Self {
+        RichText(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for RichText {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: Deserializer<'de> {
+        let s = String::deserialize(deserializer)?;
+        RichText::parse(s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl RichText {
+    /// Sanitizes HTML, removing any tags, attributes and link schemes that are not allowed.
+    ///
+    /// # Returns
+    ///
+    /// The sanitized rich text, or an error if it is too long once sanitized.
+    pub fn parse(value: String) -> anyhow::Result<Self> {
+        let sanitized = Builder::empty()
+            .add_tags(ALLOWED_TAGS)
+            .add_tag_attributes("a", ["href"])
+            .url_schemes(HashSet::from(["http", "https", "mailto"]))
+            .link_rel(Some("noopener noreferrer nofollow"))
+            .clean(value.trim())
+            .to_string();
+
+        match sanitized.chars().count() <= MAX_RICH_TEXT_LENGTH {
+            true => Ok(Self(sanitized)),
+            false => Err(anyhow!("Invalid value"))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rich_text_keeps_formatting() {
+        let rich_text = RichText::parse("<p>Contour the <strong>CTV</strong>.</p><ul><li>Prostate</li></ul>".to_string()).unwrap();
+        assert_eq!(rich_text.as_str(), "<p>Contour the <strong>CTV</strong>.</p><ul><li>Prostate</li></ul>");
+    }
+
+    #[test]
+    fn test_parse_rich_text_removes_unsafe_markup() {
+        let rich_text = RichText::parse("<p onclick=\"alert(1)\">Read <a href=\"javascript:alert(1)\">this</a></p><script>alert(1)</script>".to_string()).unwrap();
+        assert_eq!(rich_text.as_str(), "<p>Read <a rel=\"noopener noreferrer nofollow\">this</a></p>");
+    }
+
+    #[test]
+    fn test_parse_rich_text_too_long() {
+        let result = RichText::parse("a".repeat(MAX_RICH_TEXT_LENGTH + 1));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook};
+use serde::Deserialize;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+/// How much CSV output to buffer before handing it off as a chunk.
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The most columns an XLSX worksheet can have.
+pub const XLSX_MAX_COLUMNS: usize = 16_384;
+
+/// The file formats spreadsheets can be written in.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SpreadsheetFormat {
+    #[default]
+    Csv,
+    Xlsx
+}
+
+impl SpreadsheetFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx"
+        }
+    }
+}
+
+/// A value in a spreadsheet cell.
+pub enum Cell {
+    Empty,
+    Text(String),
+    Number(f64),
+    Boolean(bool),
+    DateTime(DateTime<Utc>)
+}
+
+impl Cell {
+    fn to_csv(&self) -> String {
+        match self {
+            Self::Empty => String::new(),
+            Self::Text(text) => text.clone(),
+            Self::Number(number) => number.to_string(),
+            Self::Boolean(boolean) => boolean.to_string(),
+            Self::DateTime(datetime) => datetime.to_rfc3339_opts(SecondsFormat::Secs, true)
+        }
+    }
+}
+
+impl<T: Into<Cell>> From<Option<T>> for Cell {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Self::Empty)
+    }
+}
+
+impl From<String> for Cell {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<bool> for Cell {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+impl From<DateTime<Utc>> for Cell {
+    fn from(value: DateTime<Utc>) -> Self {
+        Self::DateTime(value)
+    }
+}
+
+/// Writes a spreadsheet one row at a time, so that large exports never have to be held in memory.
+///
+/// CSV output is handed off in chunks as rows are written. XLSX rows are kept in temporary files until the workbook is finished, since the archive can only be written once every row is known.
+pub enum SpreadsheetWriter {
+    Csv(csv::Writer<Vec<u8>>),
+    Xlsx {
+        workbook: Box<Workbook>,
+        header_format: Format,
+        datetime_format: Format,
+        row: u32
+    }
+}
+
+impl SpreadsheetWriter {
+    pub fn new(format: SpreadsheetFormat) -> Self {
+        match format {
+            SpreadsheetFormat::Csv => Self::Csv(Self::csv_writer()),
+            SpreadsheetFormat::Xlsx => {
+                let mut workbook = Workbook::new();
+                workbook.add_worksheet_with_constant_memory();
+
+                Self::Xlsx {
+                    workbook: Box::new(workbook),
+                    header_format: Format::new().set_bold(),
+                    datetime_format: Format::new().set_num_format("yyyy-mm-dd hh:mm:ss"),
+                    row: 0
+                }
+            }
+        }
+    }
+
+    fn csv_writer() -> csv::Writer<Vec<u8>> {
+        csv::Writer::from_writer(Vec::with_capacity(CHUNK_SIZE))
+    }
+
+    /// Writes a row of column headers, which are bold in XLSX worksheets.
+    pub fn write_header(&mut self, headers: &[String]) -> Result<()> {
+        match self {
+            Self::Csv(writer) => writer.write_record(headers)?,
+            Self::Xlsx { workbook, header_format, row, .. } => {
+                let worksheet = workbook.worksheet_from_index(0)?;
+                for (column, header) in headers.iter().enumerate() {
+                    worksheet.write_string_with_format(*row, column as u16, header, header_format)?;
+                }
+
+                worksheet.set_freeze_panes(*row + 1, 0)?;
+                *row += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn write_row(&mut self, cells: &[Cell]) -> Result<()> {
+        match self {
+            Self::Csv(writer) => writer.write_record(cells.iter().map(Cell::to_csv))?,
+            Self::Xlsx { workbook, datetime_format, row, .. } => {
+                let worksheet = workbook.worksheet_from_index(0)?;
+                for (column, cell) in cells.iter().enumerate() {
+                    let column = column as u16;
+                    match cell {
+                        Cell::Empty => continue,
+                        Cell::Text(text) => worksheet.write_string(*row, column, text)?,
+                        Cell::Number(number) => worksheet.write_number(*row, column, *number)?,
+                        Cell::Boolean(boolean) => worksheet.write_boolean(*row, column, *boolean)?,
+                        Cell::DateTime(datetime) => {
+                            let datetime = ExcelDateTime::from_timestamp(datetime.timestamp())?;
+                            worksheet.write_datetime_with_format(*row, column, datetime, datetime_format)?
+                        }
+                    };
+                }
+
+                *row += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Takes the output written so far once there is at least a chunk of it, which is only ever the case for CSV.
+    pub fn take_chunk(&mut self) -> Result<Option<Vec<u8>>> {
+        let Self::Csv(writer) = self else {
+            return Ok(None);
+        };
+
+        writer.flush()?;
+        match writer.get_ref().len() >= CHUNK_SIZE {
+            true => Ok(Some(std::mem::replace(writer, Self::csv_writer()).into_inner()?)),
+            false => Ok(None)
+        }
+    }
+
+    /// Finishes the spreadsheet, which can block while an XLSX workbook is written to a temporary file.
+    ///
+    /// # Returns
+    ///
+    /// A reader of the rest of the output that has not been taken yet.
+    pub fn finish(self) -> Result<Box<dyn Read + Send>> {
+        match self {
+            Self::Csv(writer) => Ok(Box::new(Cursor::new(writer.into_inner()?))),
+            Self::Xlsx { mut workbook, .. } => {
+                let mut file = tempfile::tempfile()?;
+                workbook.save_to_writer(&mut file)?;
+                file.seek(SeekFrom::Start(0))?;
+
+                Ok(Box::new(file))
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use std::net::IpAddr;
+use anyhow::{anyhow, Result};
+
+/// A network range in CIDR notation, such as `10.0.0.0/16`. A bare address is treated as a single host range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IpRange {
+    network: IpAddr,
+    prefix_length: u32
+}
+
+impl IpRange {
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+
+        let (address, prefix_length) = match value.split_once('/') {
+            Some((address, prefix_length)) => (address, Some(prefix_length.parse::<u32>()?)),
+            None => (value, None)
+        };
+
+        let network = address.parse::<IpAddr>()?;
+        let max_prefix_length = match network {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128
+        };
+
+        let prefix_length = prefix_length.unwrap_or(max_prefix_length);
+        if prefix_length > max_prefix_length {
+            return Err(anyhow!("Invalid prefix length"));
+        }
+
+        Ok(Self { network, prefix_length })
+    }
+
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix_length).unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            },
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix_length).unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            },
+            _ => false
+        }
+    }
+}
+
+/// The set of reverse proxies (such as the load balancer) whose `X-Forwarded-For` headers are trusted.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(Box<[IpRange]>);
+
+impl TrustedProxies {
+    /// Parses a comma separated list of addresses and CIDR ranges.
+    pub fn parse(value: &str) -> Result<Self> {
+        let ranges = value
+            .split(',')
+            .filter(|range| !range.trim().is_empty())
+            .map(IpRange::parse)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self(ranges.into_boxed_slice()))
+    }
+
+    #[inline(always)]
+    pub fn is_trusted(&self, ip: &IpAddr) -> bool {
+        self.0.iter().any(|range| range.contains(ip))
+    }
+
+    /// Resolves the address of the client that originated a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer` - The address of the peer directly connected to the server.
+    /// * `forwarded_for` - The value of the `X-Forwarded-For` header, if any.
+    ///
+    /// # Returns
+    ///
+    /// The peer address if it is not a trusted proxy. Otherwise, the right-most address in `X-Forwarded-For`
+    /// that is not itself a trusted proxy, since everything to the left of it can be forged by the client.
+    pub fn client_ip(&self, peer: IpAddr, forwarded_for: Option<&str>) -> IpAddr {
+        if !self.is_trusted(&peer) {
+            return peer;
+        }
+
+        let forwarded_for = match forwarded_for {
+            Some(forwarded_for) => forwarded_for,
+            None => return peer
+        };
+
+        let mut client_ip = peer;
+        for hop in forwarded_for.rsplit(',') {
+            match hop.trim().parse::<IpAddr>() {
+                Ok(ip) => {
+                    client_ip = ip;
+
+                    if !self.is_trusted(&ip) {
+                        break;
+                    }
+                },
+                Err(_) => break
+            }
+        }
+
+        client_ip
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_ip_range_valid() {
+        let range = IpRange::parse("10.0.0.0/16").unwrap();
+        assert!(range.contains(&ip("10.0.42.1")));
+        assert!(!range.contains(&ip("10.1.0.1")));
+    }
+
+    #[test]
+    fn test_parse_ip_range_invalid() {
+        assert!(IpRange::parse("10.0.0.0/33").is_err());
+        assert!(IpRange::parse("not an ip").is_err());
+    }
+
+    #[test]
+    fn test_ip_range_single_host() {
+        let range = IpRange::parse("192.168.1.1").unwrap();
+        assert!(range.contains(&ip("192.168.1.1")));
+        assert!(!range.contains(&ip("192.168.1.2")));
+    }
+
+    #[test]
+    fn test_untrusted_peer_ignores_forwarded_for() {
+        let proxies = TrustedProxies::parse("10.0.0.0/16").unwrap();
+        assert_eq!(proxies.client_ip(ip("203.0.113.7"), Some("198.51.100.1")), ip("203.0.113.7"));
+    }
+
+    #[test]
+    fn test_trusted_peer_uses_forwarded_for() {
+        let proxies = TrustedProxies::parse("10.0.0.0/16, 172.16.0.1").unwrap();
+        assert_eq!(proxies.client_ip(ip("10.0.3.4"), Some("198.51.100.1")), ip("198.51.100.1"));
+        assert_eq!(proxies.client_ip(ip("10.0.3.4"), Some("198.51.100.1, 172.16.0.1")), ip("198.51.100.1"));
+    }
+
+    #[test]
+    fn test_forged_forwarded_for_is_skipped() {
+        let proxies = TrustedProxies::parse("10.0.0.0/16").unwrap();
+        assert_eq!(proxies.client_ip(ip("10.0.3.4"), Some("1.1.1.1, 198.51.100.1")), ip("198.51.100.1"));
+    }
+
+    #[test]
+    fn test_no_trusted_proxies() {
+        let proxies = TrustedProxies::parse("").unwrap();
+        assert_eq!(proxies.client_ip(ip("10.0.3.4"), Some("198.51.100.1")), ip("10.0.3.4"));
+    }
+}
@@ -1,7 +1,9 @@
-use super::{memory_cache::MemoryCache, phantom_data::Synced};
+use super::{memory_cache::{CachedRotations, MemoryCache}, phantom_data::Synced};
 use crate::models::{client_user::ClientUser, rotation::Rotation};
 use std::collections::HashMap;
 use actix_web::{http::header, HttpResponse};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 use sqlx::PgPool;
 
@@ -12,36 +14,57 @@ pub struct UserSession {
 }
 
 impl UserSession {
-    /// Builds a `UserSession` to be returned as a JSON response.
+    /// Builds a `UserSession` from the cached rotations, fetching them into the cache if they are not cached yet.
     ///
     /// # Arguments
     ///
     /// * `pool` - A connection pool to the database.
     /// * `memory_cache` - A memory cache to store and retrieve cached data to avoid database queries.
     /// * `user` - A `ClientUser` to be included in the session.
-    /// * `token` - An optional JWT token to be included in the response headers.
     ///
     /// # Returns
     ///
-    /// An `HttpResponse` containing the `UserSession` as a JSON response.
-    pub async fn respond(pool: &PgPool, memory_cache: &MemoryCache, user: ClientUser, token: Option<&str>) -> HttpResponse {
-        let rotations = match memory_cache.get_rotations_as_clone() {
-            Ok(Some(rotations)) => rotations,
-            _ => {
-                let rotations = match Rotation::fetch_all_as_map(pool).await {
-                    Ok(rotations) => rotations,
-                    Err(_) => return HttpResponse::InternalServerError().finish()
-                };
+    /// The session, along with when the rotations in it last changed.
+    pub async fn build(pool: &PgPool, memory_cache: &MemoryCache, user: ClientUser) -> Result<(Self, DateTime<Utc>)> {
+        let cached = match memory_cache.get_rotations() {
+            Some(cached) => cached,
+            None => {
+                let generation = memory_cache.rotations_generation();
 
-                let _ = memory_cache.set_rotations(Some(rotations.clone()));
+                let cached = CachedRotations {
+                    rotations: Rotation::fetch_all_as_map(pool).await?,
+                    changed_at: Utc::now()
+                };
 
-                rotations
+                memory_cache.set_rotations(cached.clone(), generation);
+                cached
             }
         };
 
         let session = Self {
             user,
-            rotations
+            rotations: cached.rotations
+        };
+
+        Ok((session, cached.changed_at))
+    }
+
+    /// Builds a `UserSession` to be returned as a JSON response.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A connection pool to the database.
+    /// * `memory_cache` - A memory cache to store and retrieve cached data to avoid database queries.
+    /// * `user` - A `ClientUser` to be included in the session.
+    /// * `token` - An optional JWT token to be included in the response headers.
+    ///
+    /// # Returns
+    ///
+    /// An `HttpResponse` containing the `UserSession` as a JSON response.
+    pub async fn respond(pool: &PgPool, memory_cache: &MemoryCache, user: ClientUser, token: Option<&str>) -> HttpResponse {
+        let session = match Self::build(pool, memory_cache, user).await {
+            Ok((session, _)) => session,
+            Err(_) => return HttpResponse::InternalServerError().finish()
         };
 
         let mut response = HttpResponse::Ok();
@@ -0,0 +1,73 @@
+use crate::prelude::*;
+
+fn case(learning_points: &str) -> serde_json::Value {
+    json!({
+        "encounteredOn": "2024-06-01",
+        "setting": "Outpatient Clinic",
+        "diagnosisCategory": "Head and Neck",
+        "treatmentIntent": "Curative",
+        "role": "Observed",
+        "learningPoints": learning_points
+    })
+}
+
+#[tokio::test]
+async fn test_case_log() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (status, json) = log_case(&client_clone, case("Bilateral neck irradiation for a T2N1 larynx."), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let case_id = json.unwrap()["id"].as_i64().unwrap() as i32;
+
+        let (status, json) = get_case_summary(&client_clone, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let summary = json.unwrap();
+        assert_eq!(summary.total, 1);
+        assert_eq!(summary.counts["diagnosisCategory"]["Head and Neck"], 1);
+
+        let status = delete_case(&client_clone, case_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let status = delete_case(&client_clone, case_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_case_log_rejects_invalid_cases() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (status, json) = log_case(&client_clone, case("Seen with MRN 00123456 in clinic."), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(json.unwrap()["errors"][0]["field"], "learningPoints");
+
+        let mut invalid_case = case("");
+        invalid_case["setting"] = json!("Somewhere Else");
+        invalid_case["subtaskIds"] = json!([0]);
+
+        let (status, json) = log_case(&client_clone, invalid_case, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let fields = json.unwrap()["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["field"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(fields, ["setting", "subtaskIds"]);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
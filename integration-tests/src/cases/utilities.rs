@@ -0,0 +1,30 @@
+use crate::prelude::*;
+
+pub async fn log_case(client: &Client, case: serde_json::Value, jwt: &str) -> Result<(StatusCode, Option<serde_json::Value>)> {
+    let response = client.post(endpoint!("/api/cases/create"))
+        .json(&case)
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn delete_case(client: &Client, case_id: i32, jwt: &str) -> Result<StatusCode> {
+    let response = client.delete(endpoint!("/api/cases/delete"))
+        .json(&json!({ "caseId": case_id }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok(response.status())
+}
+
+pub async fn get_case_summary(client: &Client, jwt: &str) -> Result<(StatusCode, Option<CaseLogSummary>)> {
+    let response = client.get(endpoint!("/api/cases/summary"))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
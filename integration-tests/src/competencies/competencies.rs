@@ -0,0 +1,94 @@
+use crate::prelude::*;
+
+#[tokio::test]
+async fn test_cannot_manage_competencies_if_not_admin() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (status, _) = create_competency(&client_clone, "CanMEDS", "Medical Expert", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let status = tag_subtask(&client_clone, 0, &[0], jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let status = delete_competency(&client_clone, 0, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_competency_progress() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    let (rotation_id, competency_id, subtask_ids) = try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation Competencies", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let rotation_id = json.unwrap().rotation_id;
+
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/plain", "Supertask\n  Task\n    First\n    Second\n", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let subtask_ids = json.unwrap().0[0].children[0].children.iter().map(|subtask| subtask.id).collect::<Vec<_>>();
+
+        let title = format!("Test Competency {}", thread_rng().gen_range(0..1000000));
+        let (status, json) = create_competency(&client_clone, "EPA", title.as_str(), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let competency_id = json.unwrap().id;
+
+        let (status, _) = create_competency(&client_clone, "EPA", title.as_str(), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        for subtask_id in subtask_ids.iter() {
+            let status = tag_subtask(&client_clone, *subtask_id, &[competency_id], jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        let status = tag_subtask(&client_clone, subtask_ids[0], &[competency_id, 0], jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let status = tag_subtask(&client_clone, 0, &[competency_id], jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        Ok((rotation_id, competency_id, subtask_ids))
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (status, _) = create_user_task(&client_clone, rotation_id, subtask_ids[0], true, "Done", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, json) = get_competency_progress(&client_clone, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        // Trainees only see their own progress.
+        let json = json.unwrap();
+        let progress = json.progress
+            .iter()
+            .find(|progress| progress.competency_id == competency_id)
+            .unwrap();
+
+        assert!(json.progress.iter().all(|other| other.user_id == progress.user_id));
+        assert_eq!(progress.completed_subtasks, 1);
+        assert_eq!(progress.total_subtasks, 2);
+
+        let admin_client = client_clone.clone();
+
+        try_admin_authorized_test(&admin_client, |jwt| async move {
+            let status = delete_competency(&client_clone, competency_id, jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let status = delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            Ok(())
+        }).await
+    }).await?;
+
+    Ok(())
+}
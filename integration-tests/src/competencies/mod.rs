@@ -0,0 +1,2 @@
+pub mod competencies;
+pub mod utilities;
\ No newline at end of file
@@ -0,0 +1,47 @@
+use crate::prelude::*;
+
+pub async fn create_competency(client: &Client, framework: &str, title: &str, jwt: &str) -> Result<(StatusCode, Option<Competency>)> {
+    let response = client.post(endpoint!("/api/competencies/create"))
+        .json(&json!({
+            "framework": framework,
+            "title": title,
+            "description": ""
+        }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn delete_competency(client: &Client, competency_id: i32, jwt: &str) -> Result<StatusCode> {
+    let response = client.delete(endpoint!("/api/competencies/delete"))
+        .json(&json!({ "competencyId": competency_id }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok(response.status())
+}
+
+pub async fn tag_subtask(client: &Client, subtask_id: i32, competency_ids: &[i32], jwt: &str) -> Result<StatusCode> {
+    let response = client.patch(endpoint!("/api/competencies/subtasks"))
+        .json(&json!({
+            "subtaskId": subtask_id,
+            "competencyIds": competency_ids
+        }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok(response.status())
+}
+
+pub async fn get_competency_progress(client: &Client, jwt: &str) -> Result<(StatusCode, Option<CompetencyProgressResponse>)> {
+    let response = client.get(endpoint!("/api/competencies/progress"))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
@@ -84,6 +84,54 @@ async fn test_cannot_create_entry_without_rotation() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cannot_create_entry_under_invalid_parent() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let rotation_id = json.unwrap().rotation_id;
+
+        let (status, json) = create_rotation(&client_clone, "Other Rotation", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let other_rotation_id = json.unwrap().rotation_id;
+
+        let (status, supertask_id) = create_supertask(&client_clone, "Test Supertask", rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let supertask_id = supertask_id.unwrap();
+
+        let (status, task_id) = create_task(&client_clone, "Test Task", rotation_id, supertask_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let task_id = task_id.unwrap();
+
+        // The parent is in another rotation.
+        let (status, _) = create_task(&client_clone, "Test Task", other_rotation_id, supertask_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = create_subtask(&client_clone, "Test Subtask", other_rotation_id, task_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // The parent does not exist.
+        let (status, _) = create_task(&client_clone, "Test Task", rotation_id, 0, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = create_subtask(&client_clone, "Test Subtask", rotation_id, 0, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let status = delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let status = delete_rotation(&client_clone, other_rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_create_and_delete_entry() -> Result<()> {
     let client = client()?;
@@ -166,6 +214,79 @@ async fn test_get_entries_caching() -> Result<()> {
 
     let client_clone = client.clone();
 
+    try_authorized_test(&client, |jwt| async move {
+        let header = |headers: &HeaderMap, name| headers.get(name).map(|value| value.to_str().unwrap().to_string());
+
+        // Entries and the session have both validators, while progress only has an entity tag.
+        let path = format!("/api/entries/{}", rotation_id);
+        let (status, headers) = conditional_get(&client_clone, path.as_str(), None, None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(header(&headers, CACHE_CONTROL).as_deref(), Some("private, no-cache"));
+
+        let etag = header(&headers, ETAG).unwrap();
+        let last_modified = header(&headers, LAST_MODIFIED).unwrap();
+
+        let (status, headers) = conditional_get(&client_clone, path.as_str(), Some(etag.as_str()), None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::NOT_MODIFIED);
+        assert_eq!(header(&headers, ETAG), Some(etag.clone()));
+
+        let (status, _) = conditional_get(&client_clone, path.as_str(), None, Some(last_modified.as_str()), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::NOT_MODIFIED);
+
+        // The entity tag takes precedence over the modification time.
+        let (status, _) = conditional_get(&client_clone, path.as_str(), Some("\"stale\""), Some(last_modified.as_str()), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        // Other tests create rotations at the same time, so the session is only sent again if it has changed.
+        let (status, headers) = conditional_get(&client_clone, "/api/users/session", None, None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(header(&headers, CACHE_CONTROL).as_deref(), Some("private, no-cache"));
+        assert!(header(&headers, LAST_MODIFIED).is_some());
+
+        let etag = header(&headers, ETAG).unwrap();
+
+        let (status, headers) = conditional_get(&client_clone, "/api/users/session", Some(etag.as_str()), None, jwt.as_str()).await?;
+        assert!(status == StatusCode::NOT_MODIFIED || header(&headers, ETAG) != Some(etag));
+
+        let path = format!("/api/tasks/{}", rotation_id);
+        let (status, headers) = conditional_get(&client_clone, path.as_str(), None, None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert!(header(&headers, LAST_MODIFIED).is_none());
+
+        let etag = header(&headers, ETAG).unwrap();
+
+        let (status, _) = conditional_get(&client_clone, path.as_str(), Some(etag.as_str()), None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::NOT_MODIFIED);
+
+        Ok(())
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let path = format!("/api/entries/{}", rotation_id);
+        let (status, headers) = conditional_get(&client_clone, path.as_str(), None, None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let etag = headers.get(ETAG).unwrap().to_str()?.to_string();
+
+        let (status, _) = create_supertask(&client_clone, "Another Supertask", rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, headers) = conditional_get(&client_clone, path.as_str(), Some(etag.as_str()), None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_ne!(headers.get(ETAG).unwrap().to_str()?, etag);
+
+        // The entries cached on the server are not sent once the rotation has been updated.
+        let (status, json) = get_entries(&client_clone, rotation_id, None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert!(json.unwrap().0.iter().any(|hierarchy| hierarchy.entry.title == "Another Supertask"));
+
+        Ok(())
+    }).await?;
+
+    let client_clone = client.clone();
+
     try_admin_authorized_test(&client, |jwt| async move {
         let status = delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
         assert_eq!(status, StatusCode::OK);
@@ -176,6 +297,42 @@ async fn test_get_entries_caching() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cache_metrics() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let response = client_clone.get(endpoint!("/api/cache/metrics"))
+            .header(AUTHORIZATION, jwt)
+            .send()
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let response = client_clone.get(endpoint!("/api/cache/metrics"))
+            .header(AUTHORIZATION, jwt)
+            .send()
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let json: serde_json::Value = response.json().await?;
+        assert!(json["entries"]["capacity"].as_u64().unwrap() > 0);
+        assert!(json["rotations"]["hits"].is_u64());
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_update_entry() -> Result<()> {
     let client = client()?;
@@ -215,3 +372,337 @@ async fn test_update_entry() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_concurrent_entry_edits() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation For Concurrent Edits", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let rotation_id = json.unwrap().rotation_id;
+
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/plain", "First\n  Task\n    Subtask\nSecond\n  Other Task\n", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let entries = json.unwrap();
+        let task = &entries.0[0].children[0].entry;
+        let subtask = &entries.0[0].children[0].children[0];
+        let other_task_id = entries.0[1].children[0].entry.id;
+        assert_eq!(subtask.revision, 1);
+
+        let update = |title: &str| json!({ "entryId": subtask.id, "title": title });
+
+        // Changes must say which revision they were made against.
+        let (status, _, _) = change_entry_if_match(&client_clone, Method::PATCH, "/api/entries/subtasks/update", update("Unconditional"), None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::PRECONDITION_REQUIRED);
+
+        let (status, etag, json) = change_entry_if_match(&client_clone, Method::PATCH, "/api/entries/subtasks/update", update("First Edit"), Some("\"1\""), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(etag.as_deref(), Some("\"2\""));
+        assert_eq!(json.unwrap()["title"], "First Edit");
+
+        // A second director still editing the first revision is sent the current state instead of overwriting it.
+        let (status, etag, json) = change_entry_if_match(&client_clone, Method::PATCH, "/api/entries/subtasks/update", update("Second Edit"), Some("\"1\""), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+        assert_eq!(etag.as_deref(), Some("\"2\""));
+
+        let json = json.unwrap();
+        assert_eq!(json["title"], "First Edit");
+        assert_eq!(json["revision"], 2);
+
+        let (status, _, _) = change_entry_if_match(&client_clone, Method::PATCH, "/api/entries/subtasks/update", update("Weak Edit"), Some("W/\"2\""), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+        let (status, _, _) = change_entry_if_match(&client_clone, Method::PATCH, "/api/entries/subtasks/move", json!({ "entryId": subtask.id, "parentId": other_task_id }), Some("\"1\""), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+        let (status, etag, _) = change_entry_if_match(&client_clone, Method::PATCH, "/api/entries/subtasks/move", json!({ "entryId": subtask.id, "parentId": other_task_id }), Some("\"1\", \"2\""), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(etag.as_deref(), Some("\"3\""));
+
+        let (status, _, _) = change_entry_if_match(&client_clone, Method::PATCH, "/api/entries/subtasks/content", json!({ "entryId": subtask.id, "description": "Stale" }), Some("\"2\""), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+        let (status, _, _) = change_entry_if_match(&client_clone, Method::DELETE, "/api/entries/tasks/delete", json!({ "entryId": task.id }), Some("\"2\""), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+        let (status, _, _) = change_entry_if_match(&client_clone, Method::DELETE, "/api/entries/tasks/delete", json!({ "entryId": task.id }), Some("\"1\""), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _, _) = change_entry_if_match(&client_clone, Method::DELETE, "/api/entries/tasks/delete", json!({ "entryId": task.id }), Some("*"), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let status = delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_create_entries() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation For Bulk", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let rotation_id = json.unwrap().rotation_id;
+
+        let outline = "- Planning\n  - Contouring\n    - Prostate\n    - Breast\n  - Dose\n- Clinic\n";
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/plain", outline, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let entries = json.unwrap();
+        assert_eq!(entries.0.len(), 2);
+        assert_eq!(entries.0[0].children.len(), 2);
+        assert_eq!(entries.0[0].children[0].children[1].title, "Breast");
+
+        let csv = "supertask,task,subtask\nClinic,Consults,New Patient\nClinic,Consults,Follow Up\n";
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/csv", csv, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(json.unwrap().0[0].children[0].children.len(), 2);
+
+        let (status, _) = bulk_create_entries(&client_clone, rotation_id, "text/plain", "- Planning\n  - <b>Bold</b>", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, json) = get_entries(&client_clone, rotation_id, None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json.unwrap().0.len(), 3);
+
+        let status = delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reorder_entries() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation For Reorder", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let rotation_id = json.unwrap().rotation_id;
+
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/plain", "First\n  Task\n    A\n    B\n    C\nSecond\n", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let entries = json.unwrap();
+        let supertask_ids = entries.0.iter().map(|supertask| supertask.entry.id).collect::<Vec<_>>();
+        let subtask_ids = entries.0[0].children[0].children.iter().map(|subtask| subtask.id).collect::<Vec<_>>();
+
+        let cache_timestamp = Utc::now();
+
+        let status = reorder_entries(&client_clone, "subtasks", &[subtask_ids[2], subtask_ids[0], subtask_ids[1]], jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let status = reorder_entries(&client_clone, "supertasks", &[supertask_ids[1], supertask_ids[0]], jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        // Every sibling must be included, and only siblings.
+        let status = reorder_entries(&client_clone, "subtasks", &[subtask_ids[0], subtask_ids[1]], jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let status = reorder_entries(&client_clone, "supertasks", &[supertask_ids[0], subtask_ids[0]], jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // Reordering invalidates cached entries.
+        let (status, json) = get_entries(&client_clone, rotation_id, Some(cache_timestamp), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let entries = json.unwrap();
+        assert_eq!(entries.0[0].entry.title, "Second");
+        assert_eq!(entries.0[1].children[0].children.iter().map(|subtask| subtask.title.as_str()).collect::<Vec<_>>(), ["C", "A", "B"]);
+        assert!(entries.0[1].children[0].children.iter().all(|subtask| subtask.revision == 2));
+
+        let status = delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_entry_content() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation For Content", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let rotation_id = json.unwrap().rotation_id;
+
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/plain", "Planning\n  Contouring\n    Prostate\n", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let subtask_id = json.unwrap().0[0].children[0].children[0].id;
+
+        let (status, json) = get_entry_content(&client_clone, "subtasks", subtask_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let content = json.unwrap();
+        assert!(content.description.is_empty());
+        assert!(content.learning_objectives.is_empty());
+
+        let (status, _) = get_entries(&client_clone, rotation_id, None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, json) = update_entry_content(&client_clone, "subtasks", subtask_id, json!({
+            "description": "<p>Contour the <strong>prostate</strong>.</p><script>alert(1)</script>",
+            "learningObjectives": ["Identify the apex on MRI."],
+            "referenceLinks": [{ "title": "RTOG Atlas", "url": "https://www.nrgoncology.org/atlases" }]
+        }), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json.unwrap().description, "<p>Contour the <strong>prostate</strong>.</p>");
+
+        let (status, json) = get_entry_content(&client_clone, "subtasks", subtask_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let content = json.unwrap();
+        assert_eq!(content.description, "<p>Contour the <strong>prostate</strong>.</p>");
+        assert_eq!(content.learning_objectives, ["Identify the apex on MRI."]);
+        assert_eq!(content.reference_links[0].url, "https://www.nrgoncology.org/atlases");
+
+        // The entries are not served from before the content changed, so their revision can be used for the next change.
+        let (status, json) = get_entries(&client_clone, rotation_id, None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let revision = json.unwrap().0[0].children[0].children[0].revision;
+        assert_eq!(revision, 2);
+
+        let if_match = format!("\"{}\"", revision);
+        let (status, _, _) = change_entry_if_match(&client_clone, Method::PATCH, "/api/entries/subtasks/update", json!({ "entryId": subtask_id, "title": "Prostate Bed" }), Some(if_match.as_str()), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = update_entry_content(&client_clone, "subtasks", subtask_id, json!({
+            "referenceLinks": [{ "title": "Bad Link", "url": "javascript:alert(1)" }]
+        }), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = get_entry_content(&client_clone, "tasks", 0, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let status = delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_trash_and_restore_entries() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    let (rotation_id, supertask_id, first_subtask_id, second_subtask_id) = try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation Trash", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let rotation_id = json.unwrap().rotation_id;
+
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/plain", "Supertask\n  Task\n    First\n    Second\n", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let entries = json.unwrap();
+        let task = &entries.0[0].children[0];
+
+        Ok((rotation_id, entries.0[0].entry.id, task.children[0].id, task.children[1].id))
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (status, _) = create_user_task(&client_clone, rotation_id, first_subtask_id, true, "Done", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, _) = get_trash(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let status = restore_entry(&client_clone, "subtasks", first_subtask_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (_, json) = session(&client_clone, Some(jwt.as_str())).await?;
+        let user_id = json.unwrap().user.id;
+        let admin_client = client_clone.clone();
+
+        try_admin_authorized_test(&admin_client, |admin_jwt| async move {
+            let status = delete_subtask(&client_clone, second_subtask_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let status = delete_supertask(&client_clone, supertask_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let (status, json) = get_entries(&client_clone, rotation_id, None, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            assert!(json.unwrap().0.is_empty());
+
+            let (status, _) = get_entry_content(&client_clone, "subtasks", second_subtask_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+
+            // The supertask was deleted last, and the subtask deleted before it stays in the trash on its own.
+            let (status, json) = get_trash(&client_clone, rotation_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let trash = json.unwrap();
+            assert_eq!(trash.len(), 2);
+            assert_eq!((trash[0].kind.as_str(), trash[0].id), ("supertask", supertask_id));
+            assert_eq!((trash[0].subtask_count, trash[0].user_task_count), (1, 1));
+            assert_eq!((trash[1].kind.as_str(), trash[1].id), ("subtask", second_subtask_id));
+
+            let status = restore_entry(&client_clone, "subtasks", second_subtask_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::CONFLICT);
+
+            let status = restore_entry(&client_clone, "supertasks", supertask_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let status = restore_entry(&client_clone, "supertasks", supertask_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+
+            let (status, json) = get_entries(&client_clone, rotation_id, None, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let subtask_ids = json.unwrap().0[0].children[0].children.iter().map(|subtask| subtask.id).collect::<Vec<_>>();
+            assert_eq!(subtask_ids, [first_subtask_id]);
+
+            let (status, json) = get_user_tasks(&client_clone, rotation_id, user_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            assert!(json.unwrap().get(&first_subtask_id).is_some_and(|user_task| user_task.is_completed));
+
+            let status = restore_entry(&client_clone, "subtasks", second_subtask_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let (status, json) = get_trash(&client_clone, rotation_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            assert!(json.unwrap().is_empty());
+
+            let (status, json) = get_entries(&client_clone, rotation_id, None, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json.unwrap().0[0].children[0].children.len(), 2);
+
+            let (status, _) = get_entry_content(&client_clone, "subtasks", second_subtask_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let status = delete_rotation(&client_clone, rotation_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            Ok(())
+        }).await
+    }).await?;
+
+    Ok(())
+}
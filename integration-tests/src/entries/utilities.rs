@@ -15,6 +15,121 @@ pub async fn get_entries(client: &Client, rotation_id: i32, entries_cache_timest
     Ok((response.status(), response.json().await.ok()))
 }
 
+pub async fn bulk_create_entries(client: &Client, rotation_id: i32, content_type: &str, body: &str, jwt: &str) -> Result<(StatusCode, Option<EntryStructure>)> {
+    let response = client.post(endpoint!(format!("/api/entries/bulk?rotationId={}", rotation_id)))
+        .header(CONTENT_TYPE, content_type)
+        .body(body.to_string())
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn reorder_entries(client: &Client, level: &str, ordered_ids: &[i32], jwt: &str) -> Result<StatusCode> {
+    let response = client.patch(endpoint!(format!("/api/entries/{}/reorder", level)))
+        .json(&json!({ "orderedIds": ordered_ids }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok(response.status())
+}
+
+pub async fn get_trash(client: &Client, rotation_id: i32, jwt: &str) -> Result<(StatusCode, Option<Vec<TrashedEntry>>)> {
+    let response = client.get(endpoint!(format!("/api/entries/trash/{}", rotation_id)))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn restore_entry(client: &Client, level: &str, entry_id: i32, jwt: &str) -> Result<StatusCode> {
+    let response = client.patch(endpoint!(format!("/api/entries/{}/restore", level)))
+        .json(&json!({ "entryId": entry_id }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok(response.status())
+}
+
+pub async fn move_entry(client: &Client, level: &str, entry_id: i32, parent_id: i32, jwt: &str) -> Result<StatusCode> {
+    let response = client.patch(endpoint!(format!("/api/entries/{}/move", level)))
+        .json(&json!({
+            "entryId": entry_id,
+            "parentId": parent_id
+        }))
+        .header(IF_MATCH, "*")
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok(response.status())
+}
+
+pub async fn get_entry_content(client: &Client, level: &str, entry_id: i32, jwt: &str) -> Result<(StatusCode, Option<EntryContent>)> {
+    let response = client.get(endpoint!(format!("/api/entries/{}/{}/content", level, entry_id)))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn update_entry_content(client: &Client, level: &str, entry_id: i32, content: serde_json::Value, jwt: &str) -> Result<(StatusCode, Option<EntryContent>)> {
+    let mut body = content;
+    body["entryId"] = json!(entry_id);
+
+    let response = client.patch(endpoint!(format!("/api/entries/{}/content", level)))
+        .json(&body)
+        .header(IF_MATCH, "*")
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn update_required_count(client: &Client, subtask_id: i32, required_count: i32, jwt: &str) -> Result<StatusCode> {
+    let response = client.patch(endpoint!("/api/entries/subtasks/required-count"))
+        .json(&json!({
+            "entryId": subtask_id,
+            "requiredCount": required_count
+        }))
+        .header(IF_MATCH, "*")
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok(response.status())
+}
+
+/// Sends a request that changes an entry with an `If-Match` header, which is left out if it is `None`.
+///
+/// # Returns
+///
+/// The status code, the entity tag and the body of the response.
+pub async fn change_entry_if_match(client: &Client, method: Method, path: &str, body: serde_json::Value, if_match: Option<&str>, jwt: &str) -> Result<(StatusCode, Option<String>, Option<serde_json::Value>)> {
+    let mut request = client.request(method, endpoint!(path))
+        .json(&body)
+        .header(AUTHORIZATION, jwt);
+
+    if let Some(if_match) = if_match {
+        request = request.header(IF_MATCH, if_match);
+    }
+
+    let response = request.send().await?;
+    let status_code = response.status();
+    let etag = response.headers()
+        .get(ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .map(str::to_string);
+
+    Ok((status_code, etag, response.json().await.ok()))
+}
+
 pub async fn create_supertask(client: &Client, title: &str, rotation_id: i32, jwt: &str) -> Result<(StatusCode, Option<i32>)> {
     let response = client.post(endpoint!("/api/entries/supertasks/create"))
         .json(&json!({
@@ -11,6 +11,7 @@ macro_rules! delete_entry_fn {
         pub async fn $fn_name(client: &Client, entry_id: i32, jwt: &str) -> Result<StatusCode> {
             let response = client.delete(endpoint!(format!("/api/entries/{}/delete", $entry_level)))
                 .json(&json!({ "entryId": entry_id }))
+                .header(IF_MATCH, "*")
                 .header(AUTHORIZATION, jwt)
                 .send()
                 .await?;
@@ -29,6 +30,7 @@ macro_rules! update_entry_fn {
                     "entryId": entry_id,
                     "title": title
                 }))
+                .header(IF_MATCH, "*")
                 .header(AUTHORIZATION, jwt)
                 .send()
                 .await?;
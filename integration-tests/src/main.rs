@@ -5,6 +5,8 @@
 mod entries;
 mod rotations;
 mod tasks;
+mod competencies;
+mod cases;
 mod users;
 mod macros;
 mod responses;
@@ -3,13 +3,15 @@ pub use crate::responses::*;
 pub use crate::entries::utilities::*;
 pub use crate::rotations::utilities::*;
 pub use crate::tasks::utilities::*;
+pub use crate::competencies::utilities::*;
+pub use crate::cases::utilities::*;
 pub use crate::users::utilities::*;
 pub use crate::utilities::*;
 pub use crate::{ACCESS_CODE, ADMIN_USERNAME, ADMIN_PASSWORD};
 pub use crate::{endpoint, update_entry_fn, delete_entry_fn};
 
 pub use chrono::{DateTime, Utc};
-pub use reqwest::{Client, StatusCode, header::AUTHORIZATION};
+pub use reqwest::{Client, Method, StatusCode, header::{HeaderMap, AUTHORIZATION, CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_MATCH, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED}};
 pub use anyhow::{Result, anyhow};
 pub use serde_json::json;
 pub use rand::{thread_rng, Rng, distributions::Alphanumeric};
@@ -19,17 +19,91 @@ pub struct UserTask {
     pub user_id: i32,
     pub subtask_id: i32,
     pub is_completed: bool,
-    pub comment: String
+    pub comment: String,
+    pub status: String,
+    pub encounter_count: i64,
+    pub completed_at: Option<DateTime<Utc>>
 }
 
 pub type GetUserTasksResponse = HashMap<i32, UserTask>;
 
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskProgress {
+    pub task_id: i32,
+    pub progress: f64
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SupertaskProgress {
+    pub supertask_id: i32,
+    pub progress: f64,
+    pub tasks: Vec<TaskProgress>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RotationProgress {
+    pub progress: f64,
+    pub completed_subtasks: usize,
+    pub total_subtasks: usize,
+    pub supertasks: Vec<SupertaskProgress>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CohortProgressRow {
+    pub user_id: i32,
+    pub progress: f64,
+    pub supertask_progress: Vec<f64>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CohortProgress {
+    pub supertasks: Vec<Supertask>,
+    pub rows: Vec<CohortProgressRow>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTaskChange {
+    pub is_completed: bool,
+    pub comment: String,
+    pub changed_by: Option<i32>,
+    pub changed_at: DateTime<Utc>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationDecision {
+    pub id: i32,
+    pub user_task_id: i32,
+    pub status: String,
+    pub reason: String,
+    pub decided_by: Option<i32>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingVerification {
+    pub user_task_id: i32,
+    pub user_id: i32,
+    pub subtask_id: i32
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Rotation {
-    id: i32,
-    name: String,
-    last_updated: DateTime<Utc>
+    pub id: i32,
+    pub name: String,
+    pub description: String,
+    pub location: String,
+    pub duration_weeks: Option<i32>,
+    pub category: String,
+    pub last_updated: DateTime<Utc>,
+    pub archived_at: Option<DateTime<Utc>>
 }
 
 pub type SearchUserResponse = HashMap<i32, User>;
@@ -47,37 +121,112 @@ pub struct CreateRotationResponse {
     pub last_updated: DateTime<Utc>
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestDeleteRotationResponse {
+    pub deletion_token: String,
+    pub deletion_token_expiry: DateTime<Utc>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EntryIdMapping {
+    pub supertasks: HashMap<i32, i32>,
+    pub tasks: HashMap<i32, i32>,
+    pub subtasks: HashMap<i32, i32>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CloneRotationResponse {
+    pub rotation: Rotation,
+    pub entry_id_mapping: EntryIdMapping
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CurriculumImportSummary {
+    pub supertasks: u64,
+    pub tasks: u64,
+    pub subtasks: u64
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportCurriculumResponse {
+    pub rotation: Option<Rotation>,
+    pub created: CurriculumImportSummary,
+    pub dry_run: bool
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CurriculumVersion {
+    pub id: i32,
+    pub version: i32,
+    pub notes: String,
+    pub trainee_count: i64
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CurriculumSnapshot {
+    pub version: i32,
+    pub entries: EntryStructure
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationSummary {
+    pub user_ids: Vec<i32>,
+    pub remapped_tasks: usize,
+    pub unmapped_tasks: usize,
+    pub dry_run: bool
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CurriculumError {
+    pub path: String,
+    pub message: String
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CurriculumErrorsResponse {
+    pub errors: Vec<CurriculumError>
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Supertask {
-    id: i32,
-    title: String,
-    rotation_id: i32,
+    pub id: i32,
+    pub title: String,
+    pub rotation_id: i32,
+    pub revision: i32
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Task {
-    id: i32,
-    supertask_id: i32,
-    title: String,
-    rotation_id: i32
+    pub id: i32,
+    pub supertask_id: i32,
+    pub title: String,
+    pub rotation_id: i32,
+    pub revision: i32
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Subtask {
-    id: i32,
-    task_id: i32,
-    title: String,
-    rotation_id: i32
+    pub id: i32,
+    pub task_id: i32,
+    pub title: String,
+    pub rotation_id: i32,
+    pub revision: i32
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct EntryLevel<T: Sized, U: Sized> {
-    entry: T,
-    children: Box<[U]>
+    pub entry: T,
+    pub children: Box<[U]>
 }
 
 type EntryHierarchy = EntryLevel<Supertask, EntryLevel<Task, Subtask>>;
@@ -85,12 +234,85 @@ type EntryHierarchy = EntryLevel<Supertask, EntryLevel<Task, Subtask>>;
 #[derive(Deserialize, Debug)]
 pub struct EntryStructure(pub Vec<EntryHierarchy>);
 
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedEntry {
+    pub kind: String,
+    pub id: i32,
+    pub subtask_count: i64,
+    pub user_task_count: i64
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReferenceLink {
+    pub title: String,
+    pub url: String
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryContent {
+    pub description: String,
+    pub learning_objectives: Vec<String>,
+    pub reference_links: Vec<ReferenceLink>
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateEntryResponse {
     pub entry_id: i32
 }
 
+#[derive(Deserialize, Debug)]
+pub struct Competency {
+    pub id: i32,
+    pub framework: String,
+    pub title: String
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompetencyProgress {
+    pub user_id: i32,
+    pub competency_id: i32,
+    pub completed_subtasks: i64,
+    pub total_subtasks: i64
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CompetencyProgressResponse {
+    pub competencies: Vec<Competency>,
+    pub progress: Vec<CompetencyProgress>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Encounter {
+    pub id: i32,
+    pub user_task_id: i32,
+    pub encountered_on: String,
+    pub comment: String
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEncounterResponse {
+    pub encounter: Encounter,
+    pub is_completed: bool
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteEncounterResponse {
+    pub is_completed: bool
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CaseLogSummary {
+    pub total: i64,
+    pub counts: HashMap<String, HashMap<String, i64>>
+}
+
 #[derive(Deserialize)]
 pub struct CreateUserTaskResponse {
     pub id: i32
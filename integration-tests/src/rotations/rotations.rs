@@ -47,3 +47,592 @@ async fn test_create_and_delete_rotation() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_non_admin_cannot_update_rotation() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (status, _) = update_rotation(&client_clone, 0, json!({ "name": "Test Rotation" }), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_nonexistent_rotation() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let (status, _) = update_rotation(&client_clone, 999999, json!({ "name": "Test Rotation" }), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_rotation() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let rotation_id = json.unwrap().rotation_id;
+
+        let (status, _) = update_rotation(&client_clone, rotation_id, json!({ "description": "<script></script>" }), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = update_rotation(&client_clone, rotation_id, json!({ "durationWeeks": 0 }), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = update_rotation(&client_clone, rotation_id, json!({ "category": "mandatory" }), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, json) = update_rotation(&client_clone, rotation_id, json!({
+            "name": "Renamed Rotation",
+            "description": "Inpatient consults.",
+            "location": "Cross Cancer Institute",
+            "durationWeeks": 4,
+            "category": "elective"
+        }), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let rotation = json.unwrap();
+        assert_eq!(rotation.name, "Renamed Rotation");
+        assert_eq!(rotation.duration_weeks, Some(4));
+        assert_eq!(rotation.category, "elective");
+
+        let (status, json) = session(&client_clone, Some(jwt.as_str())).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let rotation = &json.unwrap().rotations[&rotation_id];
+        assert_eq!(rotation.name, "Renamed Rotation");
+        assert_eq!(rotation.location, "Cross Cancer Institute");
+
+        // Fields that are left out are kept, and the duration is cleared with null.
+        let (status, json) = update_rotation(&client_clone, rotation_id, json!({ "name": "Radiation Oncology Consults" }), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let rotation = json.unwrap();
+        assert_eq!(rotation.name, "Radiation Oncology Consults");
+        assert_eq!(rotation.location, "Cross Cancer Institute");
+        assert_eq!(rotation.duration_weeks, Some(4));
+        assert_eq!(rotation.category, "elective");
+
+        let (status, json) = update_rotation(&client_clone, rotation_id, json!({ "durationWeeks": null }), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let rotation = json.unwrap();
+        assert_eq!(rotation.duration_weeks, None);
+        assert_eq!(rotation.name, "Radiation Oncology Consults");
+
+        let status = delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_non_admin_cannot_archive_or_delete_rotation() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let status = archive_rotation(&client_clone, 0, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let status = unarchive_rotation(&client_clone, 0, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, _) = get_archived_rotations(&client_clone, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, _) = request_delete_rotation(&client_clone, 0, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let status = confirm_delete_rotation(&client_clone, 0, "abcdefgh", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_archive_and_unarchive_rotation() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    let rotation_id = try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation For Archive", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let rotation_id = json.unwrap().rotation_id;
+
+        let status = archive_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, json) = get_archived_rotations(&client_clone, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert!(json.unwrap()[&rotation_id].archived_at.is_some());
+
+        // Archived rotations are read only, so that updating one cannot bring it back into sessions.
+        let (status, _) = update_rotation(&client_clone, rotation_id, json!({ "name": "Archived Rotation" }), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        Ok(rotation_id)
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (status, json) = session(&client_clone, Some(jwt.as_str())).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert!(!json.unwrap().rotations.contains_key(&rotation_id));
+
+        Ok(())
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let status = unarchive_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, json) = session(&client_clone, Some(jwt.as_str())).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert!(json.unwrap().rotations.contains_key(&rotation_id));
+
+        let status = delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete_rotation_requires_archive_and_confirmation() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation For Delete", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let rotation_id = json.unwrap().rotation_id;
+
+        let (status, _) = request_delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let status = archive_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, json) = request_delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let deletion_token = json.unwrap().deletion_token;
+        let wrong_token = match deletion_token.as_str() {
+            "abcdefgh" => "hgfedcba",
+            _ => "abcdefgh"
+        };
+
+        let status = confirm_delete_rotation(&client_clone, rotation_id, wrong_token, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let status = confirm_delete_rotation(&client_clone, rotation_id, deletion_token.as_str(), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = request_delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_non_admin_cannot_clone_rotation() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (status, _) = clone_rotation(&client_clone, 0, None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_clone_nonexistent_rotation() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let (status, _) = clone_rotation(&client_clone, 999999, None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_clone_rotation() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation To Clone", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let rotation_id = json.unwrap().rotation_id;
+
+        let (status, supertask_id) = create_supertask(&client_clone, "Test Supertask", rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let supertask_id = supertask_id.unwrap();
+
+        let (status, task_id) = create_task(&client_clone, "Test Task", rotation_id, supertask_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let task_id = task_id.unwrap();
+
+        for title in ["First Subtask", "Second Subtask"] {
+            let (status, _) = create_subtask(&client_clone, title, rotation_id, task_id, jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::CREATED);
+        }
+
+        let (status, json) = clone_rotation(&client_clone, rotation_id, Some("Cloned Rotation"), jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let json = json.unwrap();
+        let clone_id = json.rotation.id;
+        assert_ne!(clone_id, rotation_id);
+        assert_eq!(json.rotation.name, "Cloned Rotation");
+        assert_eq!(json.entry_id_mapping.supertasks.len(), 1);
+        assert_eq!(json.entry_id_mapping.tasks.len(), 1);
+        assert_eq!(json.entry_id_mapping.subtasks.len(), 2);
+        assert!(json.entry_id_mapping.supertasks.contains_key(&supertask_id));
+        assert!(json.entry_id_mapping.tasks.contains_key(&task_id));
+
+        let (status, json) = get_entries(&client_clone, clone_id, None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let entries = json.unwrap();
+        assert_eq!(entries.0.len(), 1);
+        assert_eq!(entries.0[0].children.len(), 1);
+        assert_eq!(entries.0[0].children[0].children.len(), 2);
+
+        for id in [rotation_id, clone_id] {
+            let status = delete_rotation(&client_clone, id, jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_non_admin_cannot_import_or_export_curriculum() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (status, _) = export_curriculum(&client_clone, 0, "json", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let document = json!({ "version": 1, "name": "Test Rotation" });
+        let (status, _) = import_curriculum::<ImportCurriculumResponse>(&client_clone, &document, None, false, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invalid_curriculum_reports_paths() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let document = json!({
+            "version": 1,
+            "name": "Test Rotation",
+            "supertasks": [
+                { "title": "Valid Supertask", "tasks": [
+                    { "title": "Valid Task", "subtasks": [{ "title": "<b>Invalid</b>" }] },
+                    { "title": "valid task" }
+                ]}
+            ]
+        });
+
+        let (status, json) = import_curriculum::<CurriculumErrorsResponse>(&client_clone, &document, None, false, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let paths = json.unwrap().errors.into_iter().map(|error| error.path).collect::<Vec<_>>();
+        assert_eq!(paths, ["supertasks[0].tasks[0].subtasks[0].title", "supertasks[0].tasks[1].title"]);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_import_and_export_curriculum() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    try_admin_authorized_test(&client, |jwt| async move {
+        let document = json!({
+            "version": 1,
+            "name": "Imported Rotation",
+            "supertasks": [
+                { "title": "Planning", "tasks": [
+                    { "title": "Contouring", "subtasks": [{ "title": "Prostate" }, { "title": "Head and Neck" }] }
+                ]},
+                { "title": "Clinic" }
+            ]
+        });
+
+        let (status, json) = import_curriculum::<ImportCurriculumResponse>(&client_clone, &document, None, true, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let json = json.unwrap();
+        assert!(json.dry_run);
+        assert!(json.rotation.is_none());
+        assert_eq!(json.created.subtasks, 2);
+
+        let (status, json) = import_curriculum::<ImportCurriculumResponse>(&client_clone, &document, None, false, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let rotation_id = json.unwrap().rotation.unwrap().id;
+
+        let merged = json!({
+            "version": 1,
+            "name": "Imported Rotation",
+            "supertasks": [
+                { "title": "Planning", "tasks": [
+                    { "title": "Contouring", "subtasks": [{ "title": "Prostate" }, { "title": "Breast" }] }
+                ]}
+            ]
+        });
+
+        let (status, json) = import_curriculum::<ImportCurriculumResponse>(&client_clone, &merged, Some(rotation_id), false, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let created = json.unwrap().created;
+        assert_eq!((created.supertasks, created.tasks, created.subtasks), (0, 0, 1));
+
+        let (status, json) = export_curriculum(&client_clone, rotation_id, "json", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let exported: serde_json::Value = serde_json::from_str(json.unwrap().as_str())?;
+        assert_eq!(exported["name"], "Imported Rotation");
+        assert_eq!(exported["supertasks"].as_array().unwrap().len(), 2);
+        assert_eq!(exported["supertasks"][0]["tasks"][0]["subtasks"].as_array().unwrap().len(), 3);
+        assert!(exported["supertasks"][0].get("id").is_none());
+
+        let (status, json) = export_curriculum(&client_clone, rotation_id, "yaml", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert!(json.unwrap().contains("- title: Breast"));
+
+        let status = archive_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = import_curriculum::<ImportCurriculumResponse>(&client_clone, &merged, Some(rotation_id), false, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let status = delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        Ok(())
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_curriculum_versions() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    let (rotation_id, task_id, old_subtask_id, kept_subtask_id) = try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation Versions", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let rotation_id = json.unwrap().rotation_id;
+
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/plain", "Planning\n  Contouring\n    Old Subtask\n    Kept Subtask\n", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let entries = json.unwrap();
+        let task = &entries.0[0].children[0];
+
+        Ok((rotation_id, task.entry.id, task.children[0].id, task.children[1].id))
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (status, _) = create_user_task(&client_clone, rotation_id, old_subtask_id, true, "Done", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, _) = publish_curriculum_version(&client_clone, rotation_id, "", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (_, json) = session(&client_clone, Some(jwt.as_str())).await?;
+        let user_id = json.unwrap().user.id;
+
+        let admin_client = client_clone.clone();
+        let new_subtask_id = try_admin_authorized_test(&client_clone, |admin_jwt| async move {
+            let (status, json) = publish_curriculum_version(&admin_client, rotation_id, "First version.", admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::CREATED);
+
+            let version = json.unwrap();
+            assert_eq!((version.version, version.trainee_count), (1, 1));
+
+            // Deleting a published subtask keeps the progress on it for trainees following the version.
+            let status = delete_subtask(&admin_client, old_subtask_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let (status, new_subtask_id) = create_subtask(&admin_client, "New Subtask", rotation_id, task_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::CREATED);
+            let new_subtask_id = new_subtask_id.unwrap();
+
+            let (status, json) = get_entries(&admin_client, rotation_id, None, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let subtask_ids = json.unwrap().0[0].children[0].children.iter().map(|subtask| subtask.id).collect::<Vec<_>>();
+            assert_eq!(subtask_ids, [kept_subtask_id, new_subtask_id]);
+
+            let (status, json) = diff_curriculum_versions(&admin_client, rotation_id, 1, None, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let diff = json.unwrap();
+            assert_eq!(diff["toVersion"], serde_json::Value::Null);
+            assert_eq!(diff["subtasks"]["removed"][0]["id"], old_subtask_id);
+            assert_eq!(diff["subtasks"]["added"][0]["id"], new_subtask_id);
+
+            let (status, json) = publish_curriculum_version(&admin_client, rotation_id, "Replaced a subtask.", admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::CREATED);
+
+            let version = json.unwrap();
+            assert_eq!((version.version, version.trainee_count), (2, 0));
+
+            let (status, json) = diff_curriculum_versions(&admin_client, rotation_id, 1, Some(2), admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let diff = json.unwrap();
+            assert_eq!(diff["subtasks"]["removed"].as_array().unwrap().len(), 1);
+            assert_eq!(diff["subtasks"]["added"].as_array().unwrap().len(), 1);
+            assert!(diff["tasks"]["renamed"].as_array().unwrap().is_empty());
+
+            let (status, _) = diff_curriculum_versions(&admin_client, rotation_id, 1, Some(3), admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+
+            let (status, json) = get_curriculum_version(&admin_client, rotation_id, 1, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let snapshot = json.unwrap();
+            assert_eq!(snapshot.version, 1);
+            assert_eq!(snapshot.entries.0[0].children[0].children[0].id, old_subtask_id);
+
+            let (status, json) = get_curriculum_versions(&admin_client, rotation_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json.unwrap().iter().map(|version| version.version).collect::<Vec<_>>(), [2, 1]);
+
+            Ok(new_subtask_id)
+        }).await?;
+
+        // The trainee keeps following the version they started on.
+        let (status, json) = get_entries(&client_clone, rotation_id, None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json.unwrap().0[0].children[0].children[0].title, "Old Subtask");
+
+        let (status, json) = get_own_progress(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json.unwrap().completed_subtasks, 1);
+
+        let (status, _) = create_user_task(&client_clone, rotation_id, new_subtask_id, true, "", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let admin_client = client_clone.clone();
+        try_admin_authorized_test(&client_clone, |admin_jwt| async move {
+            let migration = json!({
+                "fromVersion": 1,
+                "toVersion": 2,
+                "userIds": [user_id],
+                "mappings": [{ "from": old_subtask_id, "to": new_subtask_id }]
+            });
+
+            let (status, json) = migrate_curriculum_version(&admin_client, rotation_id, &migration, true, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let summary = json.unwrap();
+            assert!(summary.dry_run);
+            assert_eq!((summary.user_ids, summary.remapped_tasks, summary.unmapped_tasks), (vec![user_id], 1, 0));
+
+            let invalid = json!({ "fromVersion": 1, "toVersion": 2, "mappings": [{ "from": new_subtask_id, "to": old_subtask_id }] });
+            let (status, _) = migrate_curriculum_version(&admin_client, rotation_id, &invalid, false, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+
+            let missing = json!({ "fromVersion": 1, "toVersion": 9 });
+            let (status, _) = migrate_curriculum_version(&admin_client, rotation_id, &missing, false, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+
+            let (status, json) = migrate_curriculum_version(&admin_client, rotation_id, &migration, false, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json.unwrap().remapped_tasks, 1);
+
+            Ok(())
+        }).await?;
+
+        let (status, json) = get_entries(&client_clone, rotation_id, None, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json.unwrap().0[0].children[0].children[1].id, new_subtask_id);
+
+        let (status, json) = get_owned_user_tasks(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let user_tasks = json.unwrap();
+        assert!(user_tasks.contains_key(&new_subtask_id));
+        assert!(!user_tasks.contains_key(&old_subtask_id));
+
+        let (status, json) = get_own_progress(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json.unwrap().completed_subtasks, 1);
+
+        let admin_client = client_clone.clone();
+        try_admin_authorized_test(&client_clone, |admin_jwt| async move {
+            let status = delete_rotation(&admin_client, rotation_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            Ok(())
+        }).await
+    }).await?;
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
 use crate::prelude::*;
+use std::collections::HashMap;
+use serde::de::DeserializeOwned;
 
 pub async fn create_rotation(client: &Client, name: &str, jwt: &str) -> Result<(StatusCode, Option<CreateRotationResponse>)> {
     let response = client.post(endpoint!("/api/rotations/create"))
@@ -10,12 +12,169 @@ pub async fn create_rotation(client: &Client, name: &str, jwt: &str) -> Result<(
     Ok((response.status(), response.json().await.ok()))
 }
 
-pub async fn delete_rotation(client: &Client, rotation_id: i32, jwt: &str) -> Result<StatusCode> {
-    let response = client.delete(endpoint!("/api/rotations/delete"))
+pub async fn update_rotation(client: &Client, rotation_id: i32, changes: serde_json::Value, jwt: &str) -> Result<(StatusCode, Option<Rotation>)> {
+    let mut body = changes;
+    body["rotationId"] = json!(rotation_id);
+
+    let response = client.patch(endpoint!("/api/rotations/update"))
+        .json(&body)
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn clone_rotation(client: &Client, rotation_id: i32, name: Option<&str>, jwt: &str) -> Result<(StatusCode, Option<CloneRotationResponse>)> {
+    let response = client.post(endpoint!(format!("/api/rotations/{}/clone", rotation_id)))
+        .json(&json!({ "name": name }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn export_curriculum(client: &Client, rotation_id: i32, format: &str, jwt: &str) -> Result<(StatusCode, Option<String>)> {
+    let response = client.get(endpoint!(format!("/api/rotations/{}/export?format={}", rotation_id, format)))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.text().await.ok()))
+}
+
+pub async fn import_curriculum<T: DeserializeOwned>(client: &Client, document: &serde_json::Value, rotation_id: Option<i32>, dry_run: bool, jwt: &str) -> Result<(StatusCode, Option<T>)> {
+    let mut url = format!("/api/rotations/import?dryRun={}", dry_run);
+    if let Some(rotation_id) = rotation_id {
+        url.push_str(format!("&rotationId={}", rotation_id).as_str());
+    }
+
+    let response = client.post(endpoint!(url))
+        .json(document)
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn archive_rotation(client: &Client, rotation_id: i32, jwt: &str) -> Result<StatusCode> {
+    let response = client.patch(endpoint!("/api/rotations/archive"))
+        .json(&json!({ "rotationId": rotation_id }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok(response.status())
+}
+
+pub async fn unarchive_rotation(client: &Client, rotation_id: i32, jwt: &str) -> Result<StatusCode> {
+    let response = client.patch(endpoint!("/api/rotations/unarchive"))
+        .json(&json!({ "rotationId": rotation_id }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok(response.status())
+}
+
+pub async fn get_archived_rotations(client: &Client, jwt: &str) -> Result<(StatusCode, Option<HashMap<i32, Rotation>>)> {
+    let response = client.get(endpoint!("/api/rotations/archived"))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn request_delete_rotation(client: &Client, rotation_id: i32, jwt: &str) -> Result<(StatusCode, Option<RequestDeleteRotationResponse>)> {
+    let response = client.post(endpoint!("/api/rotations/request-delete"))
         .json(&json!({ "rotationId": rotation_id }))
         .header(AUTHORIZATION, jwt)
         .send()
         .await?;
 
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn confirm_delete_rotation(client: &Client, rotation_id: i32, deletion_token: &str, jwt: &str) -> Result<StatusCode> {
+    let response = client.delete(endpoint!("/api/rotations/delete"))
+        .json(&json!({
+            "rotationId": rotation_id,
+            "deletionToken": deletion_token
+        }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
     Ok(response.status())
 }
+
+/// Permanently deletes a rotation by archiving it, then requesting and confirming its deletion.
+pub async fn delete_rotation(client: &Client, rotation_id: i32, jwt: &str) -> Result<StatusCode> {
+    let status = archive_rotation(client, rotation_id, jwt).await?;
+    if status != StatusCode::OK {
+        return Ok(status);
+    }
+
+    let (status, json) = request_delete_rotation(client, rotation_id, jwt).await?;
+    let json = match json {
+        Some(json) if status == StatusCode::OK => json,
+        _ => return Ok(status)
+    };
+
+    confirm_delete_rotation(client, rotation_id, json.deletion_token.as_str(), jwt).await
+}
+
+pub async fn get_curriculum_versions(client: &Client, rotation_id: i32, jwt: &str) -> Result<(StatusCode, Option<Vec<CurriculumVersion>>)> {
+    let response = client.get(endpoint!(format!("/api/rotations/{}/versions", rotation_id)))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn publish_curriculum_version(client: &Client, rotation_id: i32, notes: &str, jwt: &str) -> Result<(StatusCode, Option<CurriculumVersion>)> {
+    let response = client.post(endpoint!(format!("/api/rotations/{}/versions/publish", rotation_id)))
+        .json(&json!({ "notes": notes }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn get_curriculum_version(client: &Client, rotation_id: i32, version: i32, jwt: &str) -> Result<(StatusCode, Option<CurriculumSnapshot>)> {
+    let response = client.get(endpoint!(format!("/api/rotations/{}/versions/{}", rotation_id, version)))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn diff_curriculum_versions(client: &Client, rotation_id: i32, from: i32, to: Option<i32>, jwt: &str) -> Result<(StatusCode, Option<serde_json::Value>)> {
+    let mut url = format!("/api/rotations/{}/versions/diff?from={}", rotation_id, from);
+    if let Some(to) = to {
+        url.push_str(format!("&to={}", to).as_str());
+    }
+
+    let response = client.get(endpoint!(url))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn migrate_curriculum_version(client: &Client, rotation_id: i32, body: &serde_json::Value, dry_run: bool, jwt: &str) -> Result<(StatusCode, Option<MigrationSummary>)> {
+    let response = client.post(endpoint!(format!("/api/rotations/{}/versions/migrate?dryRun={}", rotation_id, dry_run)))
+        .json(body)
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
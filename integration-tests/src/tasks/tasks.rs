@@ -175,3 +175,770 @@ async fn test_regular_user_cannot_get_other_tasks() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_moving_entries_keeps_user_tasks() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    let (from_rotation_id, to_rotation_id, task_id, subtask_id, to_supertask_id) = try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation Move From", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let from_rotation_id = json.unwrap().rotation_id;
+
+        let (status, json) = create_rotation(&client_clone, "Test Rotation Move To", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let to_rotation_id = json.unwrap().rotation_id;
+
+        let (status, json) = bulk_create_entries(&client_clone, from_rotation_id, "text/plain", "Supertask\n  Task\n    Subtask\n", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let entries = json.unwrap();
+        let task_id = entries.0[0].children[0].entry.id;
+        let subtask_id = entries.0[0].children[0].children[0].id;
+
+        let (status, to_supertask_id) = create_supertask(&client_clone, "Destination", to_rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        Ok((from_rotation_id, to_rotation_id, task_id, subtask_id, to_supertask_id.unwrap()))
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (status, _) = create_user_task(&client_clone, from_rotation_id, subtask_id, true, "Done", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, json) = session(&client_clone, Some(jwt.as_str())).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let user_id = json.unwrap().user.id;
+        let admin_client = client_clone.clone();
+
+        try_admin_authorized_test(&admin_client, |jwt| async move {
+            let status = move_entry(&client_clone, "tasks", task_id, 999999, jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+
+            let status = move_entry(&client_clone, "tasks", task_id, to_supertask_id, jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let (status, json) = get_entries(&client_clone, to_rotation_id, None, jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let entries = json.unwrap();
+            assert_eq!(entries.0[0].children[0].entry.id, task_id);
+            assert_eq!(entries.0[0].children[0].children[0].rotation_id, to_rotation_id);
+
+            let (status, json) = get_user_tasks(&client_clone, to_rotation_id, user_id, jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            assert!(json.unwrap().contains_key(&subtask_id));
+
+            let (status, json) = get_user_tasks(&client_clone, from_rotation_id, user_id, jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            assert!(json.unwrap().is_empty());
+
+            for rotation_id in [from_rotation_id, to_rotation_id] {
+                let status = delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+            }
+
+            Ok(())
+        }).await
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_published_entries_stay_in_their_rotation() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    let (from_rotation_id, to_rotation_id, task_id, subtask_id, to_supertask_id, to_task_id) = try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation Published From", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let from_rotation_id = json.unwrap().rotation_id;
+
+        let (status, json) = create_rotation(&client_clone, "Test Rotation Published To", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let to_rotation_id = json.unwrap().rotation_id;
+
+        let (status, json) = bulk_create_entries(&client_clone, from_rotation_id, "text/plain", "Supertask\n  Task\n    Subtask\n", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let entries = json.unwrap();
+        let task_id = entries.0[0].children[0].entry.id;
+        let subtask_id = entries.0[0].children[0].children[0].id;
+
+        let (status, json) = bulk_create_entries(&client_clone, to_rotation_id, "text/plain", "Destination\n  Task\n", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let entries = json.unwrap();
+        let to_supertask_id = entries.0[0].entry.id;
+        let to_task_id = entries.0[0].children[0].entry.id;
+
+        let (status, _) = publish_curriculum_version(&client_clone, from_rotation_id, "First edition", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        // Trainees following the published version still make progress on its entries in this rotation.
+        let status = move_entry(&client_clone, "subtasks", subtask_id, to_task_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let status = move_entry(&client_clone, "tasks", task_id, to_supertask_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        Ok((from_rotation_id, to_rotation_id, task_id, subtask_id, to_supertask_id, to_task_id))
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (status, _) = create_user_task(&client_clone, from_rotation_id, subtask_id, true, "Done", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let admin_client = client_clone.clone();
+
+        try_admin_authorized_test(&admin_client, |jwt| async move {
+            // Entries added since the last version was published can still leave the rotation.
+            let (status, new_subtask_id) = create_subtask(&client_clone, "Unpublished", from_rotation_id, task_id, jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::CREATED);
+
+            let status = move_entry(&client_clone, "subtasks", new_subtask_id.unwrap(), to_task_id, jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let (status, json) = get_entries(&client_clone, to_rotation_id, None, jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json.unwrap().0[0].children[0].children.len(), 1);
+
+            let status = move_entry(&client_clone, "tasks", task_id, to_supertask_id, jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::CONFLICT);
+
+            for rotation_id in [from_rotation_id, to_rotation_id] {
+                let status = delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+            }
+
+            Ok(())
+        }).await
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_encounters_complete_counted_subtasks() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    let (rotation_id, subtask_id) = try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation Encounters", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let rotation_id = json.unwrap().rotation_id;
+
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/plain", "Procedures\n  Bone Marrow\n    Perform Biopsies\n", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let subtask_id = json.unwrap().0[0].children[0].children[0].id;
+
+        let status = update_required_count(&client_clone, subtask_id, 0, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let status = update_required_count(&client_clone, subtask_id, 2, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        Ok((rotation_id, subtask_id))
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        // Completion of counted subtasks can only come from logged encounters.
+        let (status, user_task_id) = create_user_task(&client_clone, rotation_id, subtask_id, true, "", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let user_task_id = user_task_id.unwrap();
+
+        let (status, json) = log_encounter(&client_clone, user_task_id, "2024-06-01", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        assert!(!json.unwrap().is_completed);
+
+        let (status, json) = log_encounter(&client_clone, user_task_id, "2024-06-02", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let json = json.unwrap();
+        assert!(json.is_completed);
+        let encounter_id = json.encounter.id;
+
+        let (status, _) = log_encounter(&client_clone, user_task_id, "2999-01-01", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = log_encounter(&client_clone, 0, "2024-06-02", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, json) = get_encounters(&client_clone, user_task_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json.unwrap()[0].encountered_on, "2024-06-02");
+
+        let (status, json) = delete_encounter(&client_clone, encounter_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert!(!json.unwrap().is_completed);
+
+        let (status, json) = get_owned_user_tasks(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let json = json.unwrap();
+        let user_task = json.get(&subtask_id).unwrap();
+        assert_eq!(user_task.encounter_count, 1);
+        assert!(!user_task.is_completed);
+
+        let admin_client = client_clone.clone();
+
+        try_admin_authorized_test(&admin_client, |jwt| async move {
+            let status = delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            Ok(())
+        }).await
+    }).await?;
+
+    Ok(())
+}
+#[tokio::test]
+async fn test_supervisor_verification() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    let (rotation_id, subtask_id) = try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation Verification", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let rotation_id = json.unwrap().rotation_id;
+
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/plain", "Planning\n  Contouring\n    Contour a Larynx\n", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let subtask_id = json.unwrap().0[0].children[0].children[0].id;
+
+        Ok((rotation_id, subtask_id))
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |trainee_jwt| async move {
+        let (_, json) = session(&client_clone, Some(trainee_jwt.as_str())).await?;
+        let trainee_id = json.unwrap().user.id;
+
+        let (status, user_task_id) = create_user_task(&client_clone, rotation_id, subtask_id, false, "", trainee_jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let user_task_id = user_task_id.unwrap();
+
+        // Only completed tasks can be submitted.
+        let (status, _) = submit_user_task(&client_clone, user_task_id, trainee_jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let status = update_user_task(&client_clone, user_task_id, true, "Contoured with staff", trainee_jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, json) = submit_user_task(&client_clone, user_task_id, trainee_jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json.unwrap().status, "submitted");
+
+        let (status, _) = submit_user_task(&client_clone, user_task_id, trainee_jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let supervisor_client = client_clone.clone();
+
+        try_authorized_test(&supervisor_client, |supervisor_jwt| async move {
+            let (_, json) = session(&client_clone, Some(supervisor_jwt.as_str())).await?;
+            let supervisor_id = json.unwrap().user.id;
+
+            // Supervisors can only decide on the tasks of the trainees they are assigned.
+            let (status, _) = verify_user_task(&client_clone, user_task_id, supervisor_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+
+            let (status, json) = get_verification_inbox(&client_clone, supervisor_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            assert!(json.unwrap().is_empty());
+
+            let admin_client = client_clone.clone();
+            let jwt = supervisor_jwt.clone();
+
+            try_admin_authorized_test(&admin_client, |admin_jwt| async move {
+                let status = assign_supervisor(&client_clone, supervisor_id, trainee_id, admin_jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+
+                let status = assign_supervisor(&client_clone, supervisor_id, supervisor_id, admin_jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+
+                let (status, json) = get_verification_inbox(&client_clone, jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+
+                let json = json.unwrap();
+                assert_eq!(json.len(), 1);
+                assert_eq!(json[0].user_task_id, user_task_id);
+                assert_eq!(json[0].user_id, trainee_id);
+
+                let (status, _) = return_user_task(&client_clone, user_task_id, " ", jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+
+                let (status, json) = return_user_task(&client_clone, user_task_id, "Include the planning CT", jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+                assert_eq!(json.unwrap().status, "returned");
+
+                let (status, _) = verify_user_task(&client_clone, user_task_id, jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::CONFLICT);
+
+                let (status, _) = submit_user_task(&client_clone, user_task_id, trainee_jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+
+                let (status, json) = verify_user_task(&client_clone, user_task_id, jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+                assert_eq!(json.unwrap().decided_by, Some(supervisor_id));
+
+                let (status, json) = get_verification_history(&client_clone, user_task_id, trainee_jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+
+                let history = json.unwrap();
+                let statuses = history.iter().map(|decision| decision.status.as_str()).collect::<Vec<_>>();
+                assert_eq!(statuses, ["submitted", "returned", "submitted", "verified"]);
+                assert_eq!(history[1].reason, "Include the planning CT");
+
+                // Rewriting the comment of a verified task returns it to a draft, since it is no longer what was verified.
+                let status = update_user_task(&client_clone, user_task_id, true, "Contoured alone", trainee_jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+
+                let (_, json) = get_owned_user_tasks(&client_clone, rotation_id, trainee_jwt.as_str()).await?;
+                assert_eq!(json.unwrap().get(&subtask_id).unwrap().status, "draft");
+
+                let (status, _) = submit_user_task(&client_clone, user_task_id, trainee_jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+
+                // So does changing its completion.
+                let status = update_user_task(&client_clone, user_task_id, false, "", trainee_jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+
+                let (_, json) = get_owned_user_tasks(&client_clone, rotation_id, trainee_jwt.as_str()).await?;
+                assert_eq!(json.unwrap().get(&subtask_id).unwrap().status, "draft");
+
+                let status = delete_rotation(&client_clone, rotation_id, admin_jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+
+                Ok(())
+            }).await
+        }).await
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_user_task_history() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    let (rotation_id, subtask_id) = try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation History", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let rotation_id = json.unwrap().rotation_id;
+
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/plain", "Planning\n  Dosimetry\n    Review a Plan\n", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let subtask_id = json.unwrap().0[0].children[0].children[0].id;
+
+        Ok((rotation_id, subtask_id))
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (_, json) = session(&client_clone, Some(jwt.as_str())).await?;
+        let user_id = json.unwrap().user.id;
+
+        let (status, user_task_id) = create_user_task(&client_clone, rotation_id, subtask_id, false, "", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let user_task_id = user_task_id.unwrap();
+
+        let status = update_user_task(&client_clone, user_task_id, true, "", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        // Saving the same progress again is not a change.
+        let status = update_user_task(&client_clone, user_task_id, true, "", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let status = update_user_task(&client_clone, user_task_id, true, "Reviewed with physics", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, json) = get_owned_user_tasks(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert!(json.unwrap().get(&subtask_id).unwrap().completed_at.is_some());
+
+        let (status, json) = get_user_task_history(&client_clone, user_task_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let history = json.unwrap();
+        let states = history
+            .iter()
+            .map(|change| (change.is_completed, change.comment.as_str()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(states, [(false, ""), (true, ""), (true, "Reviewed with physics")]);
+        assert!(history.iter().all(|change| change.changed_by == Some(user_id)));
+        assert!(history.windows(2).all(|changes| changes[0].changed_at <= changes[1].changed_at));
+
+        let other_client = client_clone.clone();
+
+        try_authorized_test(&other_client, |other_jwt| async move {
+            let (status, _) = get_user_task_history(&client_clone, user_task_id, other_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+
+            let admin_client = client_clone.clone();
+
+            try_admin_authorized_test(&admin_client, |admin_jwt| async move {
+                let (status, json) = get_user_task_history(&client_clone, user_task_id, admin_jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+                assert_eq!(json.unwrap().len(), 3);
+
+                let status = delete_rotation(&client_clone, rotation_id, admin_jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+
+                Ok(())
+            }).await
+        }).await
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_progress() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    let (rotation_id, subtask_ids) = try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation Progress", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let rotation_id = json.unwrap().rotation_id;
+
+        let outline = "Planning\n  Contouring\n    Contour a Larynx\n    Contour a Prostate\n  Dosimetry\n    Review a Plan\nBrachytherapy\n  Gynecologic\n    Insert a Tandem\n";
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/plain", outline, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let subtask_ids = json.unwrap().0
+            .iter()
+            .flat_map(|supertask| supertask.children.iter())
+            .flat_map(|task| task.children.iter().map(|subtask| subtask.id))
+            .collect::<Vec<_>>();
+
+        Ok((rotation_id, subtask_ids))
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (_, json) = session(&client_clone, Some(jwt.as_str())).await?;
+        let user_id = json.unwrap().user.id;
+
+        for subtask_id in [subtask_ids[0], subtask_ids[2]] {
+            let (status, _) = create_user_task(&client_clone, rotation_id, subtask_id, true, "", jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::CREATED);
+        }
+
+        let (status, json) = get_own_progress(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        // Tasks are at 50% and 100%, so planning is at 75% and the rotation is the mean of it and brachytherapy at 0%.
+        let progress = json.unwrap();
+        assert_eq!(progress.progress, 37.5);
+        assert_eq!((progress.completed_subtasks, progress.total_subtasks), (2, 4));
+        assert_eq!(progress.supertasks[0].progress, 75.0);
+        assert_eq!(progress.supertasks[0].tasks[0].progress, 50.0);
+        assert_eq!(progress.supertasks[1].progress, 0.0);
+
+        let (status, _) = get_user_progress(&client_clone, user_id, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, _) = get_own_progress(&client_clone, 0, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, content_type, body) = get_own_progress_report(&client_clone, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type.as_deref(), Some("application/pdf"));
+        assert!(body.starts_with(b"%PDF"));
+
+        let (status, _, _) = get_user_progress_report(&client_clone, user_id, rotation_id, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let admin_client = client_clone.clone();
+
+        try_admin_authorized_test(&admin_client, |admin_jwt| async move {
+            let (status, json) = get_user_progress(&client_clone, user_id, rotation_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json.unwrap().progress, 37.5);
+
+            let (status, content_type, body) = get_user_progress_report(&client_clone, user_id, rotation_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(content_type.as_deref(), Some("application/pdf"));
+            assert!(body.starts_with(b"%PDF"));
+
+            let status = delete_rotation(&client_clone, rotation_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            Ok(())
+        }).await
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cohort_progress() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    let (rotation_id, subtask_ids) = try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation Cohort Progress", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let rotation_id = json.unwrap().rotation_id;
+
+        let outline = "Planning\n  Contouring\n    Contour a Larynx\n    Contour a Prostate\n    Contour a Breast\nBrachytherapy\n  Gynecologic\n    Insert a Tandem\n";
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/plain", outline, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let subtask_ids = json.unwrap().0
+            .iter()
+            .flat_map(|supertask| supertask.children.iter())
+            .flat_map(|task| task.children.iter().map(|subtask| subtask.id))
+            .collect::<Vec<_>>();
+
+        Ok((rotation_id, subtask_ids))
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |first_jwt| async move {
+        let (_, json) = session(&client_clone, Some(first_jwt.as_str())).await?;
+        let first_id = json.unwrap().user.id;
+
+        let (status, _) = create_user_task(&client_clone, rotation_id, subtask_ids[0], true, "", first_jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (_, json) = get_own_progress(&client_clone, rotation_id, first_jwt.as_str()).await?;
+        let first_progress = json.unwrap();
+
+        let second_client = client_clone.clone();
+
+        try_authorized_test(&second_client, |second_jwt| async move {
+            let (_, json) = session(&client_clone, Some(second_jwt.as_str())).await?;
+            let second_id = json.unwrap().user.id;
+
+            let (status, _) = create_user_task(&client_clone, rotation_id, subtask_ids[3], true, "", second_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::CREATED);
+
+            let admin_client = client_clone.clone();
+
+            try_admin_authorized_test(&admin_client, |jwt| async move {
+                let (status, json) = get_cohort_progress(&client_clone, rotation_id, &[], jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+
+                // The matrix has the same numbers as the progress of each trainee.
+                let cohort = json.unwrap();
+                let first_row = cohort.rows.iter().find(|row| row.user_id == first_id).unwrap();
+                assert_eq!(first_row.progress, first_progress.progress);
+
+                let supertask_progress = first_progress.supertasks.iter().map(|supertask| supertask.progress).collect::<Vec<_>>();
+                assert_eq!(first_row.supertask_progress, supertask_progress);
+
+                // Other trainees have no progress in the rotation, so they are filtered out.
+                let query = [("minProgress", "10"), ("sort", "progress"), ("order", "desc")];
+                let (status, json) = get_cohort_progress(&client_clone, rotation_id, &query, jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+
+                let user_ids = json.unwrap().rows.iter().map(|row| row.user_id).collect::<Vec<_>>();
+                assert_eq!(user_ids, [second_id, first_id]);
+
+                let brachytherapy_id = cohort.supertasks[1].id.to_string();
+                let query = [("supertaskId", brachytherapy_id.as_str()), ("minProgress", "50")];
+                let (status, json) = get_cohort_progress(&client_clone, rotation_id, &query, jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+
+                let user_ids = json.unwrap().rows.iter().map(|row| row.user_id).collect::<Vec<_>>();
+                assert_eq!(user_ids, [second_id]);
+
+                let (status, _) = get_cohort_progress(&client_clone, rotation_id, &[("minProgress", "150")], jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+
+                let (status, _) = get_cohort_progress(&client_clone, rotation_id, &[("supertaskId", "0")], jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+
+                let (status, _) = get_cohort_progress(&client_clone, rotation_id, &[], second_jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+                let status = delete_rotation(&client_clone, rotation_id, jwt.as_str()).await?;
+                assert_eq!(status, StatusCode::OK);
+
+                Ok(())
+            }).await
+        }).await
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_progress_export() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    let (rotation_id, subtask_ids) = try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation Progress Export", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let rotation_id = json.unwrap().rotation_id;
+
+        let outline = "Planning\n  Contouring\n    Contour a Larynx\n    Contour a Prostate\n";
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/plain", outline, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let subtask_ids = json.unwrap().0
+            .iter()
+            .flat_map(|supertask| supertask.children.iter())
+            .flat_map(|task| task.children.iter().map(|subtask| subtask.id))
+            .collect::<Vec<_>>();
+
+        Ok((rotation_id, subtask_ids))
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (_, json) = session(&client_clone, Some(jwt.as_str())).await?;
+        let username = json.unwrap().user.username;
+
+        let (status, _) = create_user_task(&client_clone, rotation_id, subtask_ids[1], true, "Reviewed with attending", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let rotation_query = rotation_id.to_string();
+        let (status, _, _) = export_progress(&client_clone, &[("rotationId", rotation_query.as_str())], jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let admin_client = client_clone.clone();
+
+        try_admin_authorized_test(&admin_client, |admin_jwt| async move {
+            let (status, content_type, body) = export_progress(&client_clone, &[("rotationId", rotation_query.as_str())], admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(content_type.as_deref(), Some("text/csv"));
+
+            // There is a row for every subtask of the trainee, whether or not they have started it.
+            let csv = String::from_utf8(body)?;
+            let rows = csv.lines().filter(|line| line.contains(username.as_str())).collect::<Vec<_>>();
+            assert!(csv.starts_with("Rotation,Supertask,Task,Subtask,Username"));
+            assert_eq!(rows.len(), 2);
+            assert!(rows[0].contains("Contour a Larynx") && rows[0].contains(",false,"));
+            assert!(rows[1].contains("Contour a Prostate") && rows[1].contains(",true,draft,") && rows[1].contains("Reviewed with attending"));
+
+            let query = [("rotationId", rotation_query.as_str()), ("layout", "matrix")];
+            let (status, _, body) = export_progress(&client_clone, &query, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let csv = String::from_utf8(body)?;
+            let row = csv.lines().find(|line| line.starts_with(username.as_str())).unwrap();
+            assert!(csv.starts_with("Username,Name,Completed Subtasks,Planning / Contouring / Contour a Larynx,Planning / Contouring / Contour a Prostate"));
+            assert!(row.ends_with(",1,false,true"));
+
+            let query = [("rotationId", rotation_query.as_str()), ("format", "xlsx")];
+            let (status, content_type, body) = export_progress(&client_clone, &query, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(content_type.as_deref(), Some("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"));
+            assert!(body.starts_with(b"PK"));
+
+            let (status, _, _) = export_progress(&client_clone, &[("rotationId", "0")], admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+
+            let status = delete_rotation(&client_clone, rotation_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            Ok(())
+        }).await
+    }).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cohort_progress_follows_pinned_version() -> Result<()> {
+    let client = client()?;
+    let client_clone = client.clone();
+
+    let (rotation_id, task_id, subtask_ids) = try_admin_authorized_test(&client, |jwt| async move {
+        let (status, json) = create_rotation(&client_clone, "Test Rotation Pinned Cohort", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let rotation_id = json.unwrap().rotation_id;
+
+        let outline = "Planning\n  Contouring\n    Contour a Larynx\n    Contour a Prostate\n";
+        let (status, json) = bulk_create_entries(&client_clone, rotation_id, "text/plain", outline, jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let structure = json.unwrap();
+        let task = &structure.0[0].children[0];
+        let subtask_ids = task.children.iter().map(|subtask| subtask.id).collect::<Vec<_>>();
+
+        let (status, _) = publish_curriculum_version(&client_clone, rotation_id, "First edition", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        Ok((rotation_id, task.entry.id, subtask_ids))
+    }).await?;
+
+    let client_clone = client.clone();
+
+    try_authorized_test(&client, |jwt| async move {
+        let (_, json) = session(&client_clone, Some(jwt.as_str())).await?;
+        let user = json.unwrap().user;
+
+        let (status, _) = create_user_task(&client_clone, rotation_id, subtask_ids[0], true, "", jwt.as_str()).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let admin_client = client_clone.clone();
+
+        try_admin_authorized_test(&admin_client, |admin_jwt| async move {
+            // The draft gains a subtask that is not part of the version the trainee follows.
+            let (status, _) = create_subtask(&client_clone, "Contour a Breast", rotation_id, task_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::CREATED);
+
+            let (status, json) = get_user_progress(&client_clone, user.id, rotation_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+            let progress = json.unwrap();
+            assert_eq!(progress.total_subtasks, 2);
+
+            let (status, json) = get_cohort_progress(&client_clone, rotation_id, &[], admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let cohort = json.unwrap();
+            let row = cohort.rows.iter().find(|row| row.user_id == user.id).unwrap();
+            assert_eq!(row.progress, progress.progress);
+
+            let supertask_progress = progress.supertasks.iter().map(|supertask| supertask.progress).collect::<Vec<_>>();
+            assert_eq!(row.supertask_progress, supertask_progress);
+
+            // The export only has rows for the subtasks of the followed version.
+            let rotation_query = rotation_id.to_string();
+            let (status, _, body) = export_progress(&client_clone, &[("rotationId", rotation_query.as_str())], admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let csv = String::from_utf8(body)?;
+            let rows = csv.lines().filter(|line| line.contains(user.username.as_str())).collect::<Vec<_>>();
+            assert_eq!(rows.len(), 2);
+            assert!(rows.iter().all(|row| !row.contains("Contour a Breast")));
+
+            // In the matrix, the subtask outside the followed version is left empty.
+            let query = [("rotationId", rotation_query.as_str()), ("layout", "matrix")];
+            let (status, _, body) = export_progress(&client_clone, &query, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            let csv = String::from_utf8(body)?;
+            let row = csv.lines().find(|line| line.starts_with(user.username.as_str())).unwrap();
+            assert!(row.ends_with(",1,true,false,"));
+
+            let status = delete_rotation(&client_clone, rotation_id, admin_jwt.as_str()).await?;
+            assert_eq!(status, StatusCode::OK);
+
+            Ok(())
+        }).await
+    }).await?;
+
+    Ok(())
+}
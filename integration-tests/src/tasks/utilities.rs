@@ -1,7 +1,8 @@
 use crate::prelude::*;
 
-pub async fn get_owned_user_tasks(client: &Client, rotation_id: i32) -> Result<(StatusCode, Option<GetUserTasksResponse>)> {
+pub async fn get_owned_user_tasks(client: &Client, rotation_id: i32, jwt: &str) -> Result<(StatusCode, Option<GetUserTasksResponse>)> {
     let response = client.get(endpoint!(format!("/api/tasks/{}", rotation_id)).as_str())
+        .header(AUTHORIZATION, jwt)
         .send()
         .await?;
 
@@ -9,7 +10,7 @@ pub async fn get_owned_user_tasks(client: &Client, rotation_id: i32) -> Result<(
 }
 
 pub async fn get_user_tasks(client: &Client, rotation_id: i32, user_id: i32, jwt: &str) -> Result<(StatusCode, Option<GetUserTasksResponse>)> {
-    let response = client.get(endpoint!(format!("/api/tasks/{}/{}", rotation_id, user_id)).as_str())
+    let response = client.get(endpoint!(format!("/api/tasks/{}/{}", user_id, rotation_id)).as_str())
         .header(AUTHORIZATION, jwt)
         .send()
         .await?;
@@ -38,15 +39,165 @@ pub async fn create_user_task(client: &Client, rotation_id: i32, subtask_id: i32
     Ok((status_code, json.map(|json| { json.id })))
 }
 
-pub async fn update_user_task(client: &Client, user_task_id: i32, is_completed: bool, comment: &str) -> Result<StatusCode> {
+pub async fn update_user_task(client: &Client, user_task_id: i32, is_completed: bool, comment: &str, jwt: &str) -> Result<StatusCode> {
     let response = client.patch(endpoint!("/api/tasks/update"))
         .json(&json!({
             "id": user_task_id,
             "isCompleted": is_completed,
             "comment": comment
         }))
+        .header(AUTHORIZATION, jwt)
         .send()
         .await?;
 
     Ok(response.status())
 }
+
+pub async fn log_encounter(client: &Client, user_task_id: i32, encountered_on: &str, jwt: &str) -> Result<(StatusCode, Option<LogEncounterResponse>)> {
+    let response = client.post(endpoint!("/api/tasks/encounters/create"))
+        .json(&json!({
+            "userTaskId": user_task_id,
+            "encounteredOn": encountered_on,
+            "comment": ""
+        }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn delete_encounter(client: &Client, encounter_id: i32, jwt: &str) -> Result<(StatusCode, Option<DeleteEncounterResponse>)> {
+    let response = client.delete(endpoint!("/api/tasks/encounters/delete"))
+        .json(&json!({ "encounterId": encounter_id }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn get_encounters(client: &Client, user_task_id: i32, jwt: &str) -> Result<(StatusCode, Option<Vec<Encounter>>)> {
+    let response = client.get(endpoint!(format!("/api/tasks/encounters/{}", user_task_id)))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn submit_user_task(client: &Client, user_task_id: i32, jwt: &str) -> Result<(StatusCode, Option<VerificationDecision>)> {
+    let response = client.patch(endpoint!("/api/tasks/verifications/submit"))
+        .json(&json!({ "userTaskId": user_task_id }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn verify_user_task(client: &Client, user_task_id: i32, jwt: &str) -> Result<(StatusCode, Option<VerificationDecision>)> {
+    let response = client.patch(endpoint!("/api/tasks/verifications/verify"))
+        .json(&json!({ "userTaskId": user_task_id }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn return_user_task(client: &Client, user_task_id: i32, reason: &str, jwt: &str) -> Result<(StatusCode, Option<VerificationDecision>)> {
+    let response = client.patch(endpoint!("/api/tasks/verifications/return"))
+        .json(&json!({ "userTaskId": user_task_id, "reason": reason }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn get_verification_history(client: &Client, user_task_id: i32, jwt: &str) -> Result<(StatusCode, Option<Vec<VerificationDecision>>)> {
+    let response = client.get(endpoint!(format!("/api/tasks/verifications/history/{}", user_task_id)).as_str())
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn get_verification_inbox(client: &Client, jwt: &str) -> Result<(StatusCode, Option<Vec<PendingVerification>>)> {
+    let response = client.get(endpoint!("/api/tasks/verifications/inbox"))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn get_user_task_history(client: &Client, user_task_id: i32, jwt: &str) -> Result<(StatusCode, Option<Vec<UserTaskChange>>)> {
+    let response = client.get(endpoint!(format!("/api/tasks/history/{}", user_task_id)).as_str())
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn get_own_progress(client: &Client, rotation_id: i32, jwt: &str) -> Result<(StatusCode, Option<RotationProgress>)> {
+    let response = client.get(endpoint!(format!("/api/progress/{}", rotation_id)).as_str())
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn get_user_progress(client: &Client, user_id: i32, rotation_id: i32, jwt: &str) -> Result<(StatusCode, Option<RotationProgress>)> {
+    let response = client.get(endpoint!(format!("/api/progress/{}/{}", user_id, rotation_id)).as_str())
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn get_cohort_progress(client: &Client, rotation_id: i32, query: &[(&str, &str)], jwt: &str) -> Result<(StatusCode, Option<CohortProgress>)> {
+    let response = client.get(endpoint!(format!("/api/progress/cohort/{}", rotation_id)).as_str())
+        .query(query)
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok((response.status(), response.json().await.ok()))
+}
+
+pub async fn get_own_progress_report(client: &Client, rotation_id: i32, jwt: &str) -> Result<(StatusCode, Option<String>, Vec<u8>)> {
+    let response = client.get(endpoint!(format!("/api/progress/report/{}", rotation_id)).as_str())
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    let content_type = response.headers().get(CONTENT_TYPE).and_then(|value| value.to_str().ok()).map(String::from);
+    Ok((response.status(), content_type, response.bytes().await?.to_vec()))
+}
+
+pub async fn get_user_progress_report(client: &Client, user_id: i32, rotation_id: i32, jwt: &str) -> Result<(StatusCode, Option<String>, Vec<u8>)> {
+    let response = client.get(endpoint!(format!("/api/progress/report/{}/{}", user_id, rotation_id)).as_str())
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    let content_type = response.headers().get(CONTENT_TYPE).and_then(|value| value.to_str().ok()).map(String::from);
+    Ok((response.status(), content_type, response.bytes().await?.to_vec()))
+}
+
+pub async fn export_progress(client: &Client, query: &[(&str, &str)], jwt: &str) -> Result<(StatusCode, Option<String>, Vec<u8>)> {
+    let response = client.get(endpoint!("/api/progress/export"))
+        .query(query)
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    let content_type = response.headers().get(CONTENT_TYPE).and_then(|value| value.to_str().ok()).map(String::from);
+    Ok((response.status(), content_type, response.bytes().await?.to_vec()))
+}
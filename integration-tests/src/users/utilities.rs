@@ -102,3 +102,13 @@ pub async fn allow_reset_password(client: &Client, user_id: i32, jwt: &str) -> R
 
     Ok((response.status(), response.json().await.ok()))
 }
+
+pub async fn assign_supervisor(client: &Client, supervisor_id: i32, trainee_id: i32, jwt: &str) -> Result<StatusCode> {
+    let response = client.post(endpoint!("/api/users/supervisors/assign"))
+        .json(&json!({ "supervisorId": supervisor_id, "traineeId": trainee_id }))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await?;
+
+    Ok(response.status())
+}
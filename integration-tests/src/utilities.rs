@@ -20,6 +20,28 @@ pub fn format_timestamp(timestamp: DateTime<Utc>) -> String {
     timestamp.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
 }
 
+/// Sends a `GET` request that is conditional on the `If-None-Match` and `If-Modified-Since` headers that are given.
+///
+/// # Returns
+///
+/// The status code and the headers of the response.
+pub async fn conditional_get(client: &Client, path: &str, if_none_match: Option<&str>, if_modified_since: Option<&str>, jwt: &str) -> Result<(StatusCode, HeaderMap)> {
+    let mut request = client.get(endpoint!(path))
+        .header(AUTHORIZATION, jwt);
+
+    if let Some(if_none_match) = if_none_match {
+        request = request.header(IF_NONE_MATCH, if_none_match);
+    }
+
+    if let Some(if_modified_since) = if_modified_since {
+        request = request.header(IF_MODIFIED_SINCE, if_modified_since);
+    }
+
+    let response = request.send().await?;
+
+    Ok((response.status(), response.headers().clone()))
+}
+
 pub async fn try_authorized_test<F, T, V>(client: &Client, callback: T) -> Result<V>
 where
     F: Future<Output = Result<V>>,
@@ -1,8 +1,7 @@
 import { Ref, inject } from "vue"
-import { EntryStructure } from "../models/tasks"
+import { EntryStructure, Supertask, Task, Subtask } from "../models/tasks"
 import { API_ENDPOINT } from "../utilities"
 
-import useCache from "./useCache"
 import useJwt from "./useJwt"
 
 interface CreateEntryResponse {
@@ -10,17 +9,23 @@ interface CreateEntryResponse {
 }
 
 const useEntries = () => {
-    const { cacheRotationEntries, retrieveRotationEntries } = useCache()
     const { defaultHeaders } = useJwt()
 
     const entries = inject<Ref<Record<number, EntryStructure>>>("entries")!
 
     const entriesMemo = new Map<number, EntryStructure>()
-    const cacheAndMemoEntries = (rotationId: number, entries: EntryStructure) => {
-        cacheRotationEntries(rotationId, entries)
+    const memoEntries = (rotationId: number, entries: EntryStructure) => {
         entriesMemo.set(rotationId, entries)
     }
 
+    // Changes are made against the revision of the entry that was last seen, so that edits by someone else are not overwritten.
+    const ifMatchHeaders = (entry: Supertask | Task | Subtask): HeadersInit => {
+        return {
+            ...defaultHeaders(),
+            "If-Match": `"${entry.revision}"`
+        }
+    }
+
     const createSupertask = async (title: string, rotationId: number): Promise<boolean> => {
         const response = await fetch(`${API_ENDPOINT}/api/entries/supertasks/create`, {
             credentials: "include",
@@ -39,12 +44,13 @@ const useEntries = () => {
                 entry: {
                     id: data.entryId,
                     title,
-                    rotationId
+                    rotationId,
+                    revision: 1
                 },
                 children: []
             })
 
-            cacheAndMemoEntries(rotationId, entries.value[rotationId])
+            memoEntries(rotationId, entries.value[rotationId])
             return true
         }
 
@@ -54,7 +60,7 @@ const useEntries = () => {
     const updateSupertask = async (rotationId: number, supertaskIndex: number, supertaskId: number, title: string): Promise<boolean> => {
         const response = await fetch(`${API_ENDPOINT}/api/entries/supertasks/update`, {
             credentials: "include",
-            headers: defaultHeaders(),
+            headers: ifMatchHeaders(entries.value[rotationId][supertaskIndex].entry),
             method: "PATCH",
             body: JSON.stringify({
                 entryId: supertaskId,
@@ -62,11 +68,12 @@ const useEntries = () => {
             })
         })
 
-        if (response.ok) {
-            entries.value[rotationId][supertaskIndex].entry.title = title
-            cacheAndMemoEntries(rotationId, entries.value[rotationId])
+        // Both a successful update and a conflicting one send back the current state of the entry.
+        if (response.ok || response.status === 412) {
+            entries.value[rotationId][supertaskIndex].entry = await response.json()
+            memoEntries(rotationId, entries.value[rotationId])
 
-            return true
+            return response.ok
         }
 
         return false
@@ -75,16 +82,23 @@ const useEntries = () => {
     const deleteSupertask = async (rotationId: number, supertaskIndex: number, supertaskId: number): Promise<boolean> => {
         const response = await fetch(`${API_ENDPOINT}/api/entries/supertasks/delete`, {
             credentials: "include",
-            headers: defaultHeaders(),
+            headers: ifMatchHeaders(entries.value[rotationId][supertaskIndex].entry),
             method: "DELETE",
             body: JSON.stringify({
                 entryId: supertaskId
             })
         })
 
+        if (response.status === 412) {
+            entries.value[rotationId][supertaskIndex].entry = await response.json()
+            memoEntries(rotationId, entries.value[rotationId])
+
+            return false
+        }
+
         if (response.ok) {
             entries.value[rotationId].splice(supertaskIndex, 1);
-            cacheAndMemoEntries(rotationId, entries.value[rotationId])
+            memoEntries(rotationId, entries.value[rotationId])
 
             return true
         }
@@ -112,12 +126,13 @@ const useEntries = () => {
                     id: data.entryId,
                     title,
                     rotationId,
-                    supertaskId
+                    supertaskId,
+                    revision: 1
                 },
                 children: []
             })
 
-            cacheAndMemoEntries(rotationId, entries.value[rotationId])
+            memoEntries(rotationId, entries.value[rotationId])
             return true
         }
 
@@ -127,7 +142,7 @@ const useEntries = () => {
     const updateTask = async (rotationId: number, supertaskIndex: number, taskIndex: number, taskId: number, title: string): Promise<boolean> => {
         const response = await fetch(`${API_ENDPOINT}/api/entries/tasks/update`, {
             credentials: "include",
-            headers: defaultHeaders(),
+            headers: ifMatchHeaders(entries.value[rotationId][supertaskIndex].children[taskIndex].entry),
             method: "PATCH",
             body: JSON.stringify({
                 entryId: taskId,
@@ -135,11 +150,12 @@ const useEntries = () => {
             })
         })
 
-        if (response.ok) {
-            entries.value[rotationId][supertaskIndex].children[taskIndex].entry.title = title
-            cacheAndMemoEntries(rotationId, entries.value[rotationId])
+        // Both a successful update and a conflicting one send back the current state of the entry.
+        if (response.ok || response.status === 412) {
+            entries.value[rotationId][supertaskIndex].children[taskIndex].entry = await response.json()
+            memoEntries(rotationId, entries.value[rotationId])
 
-            return true
+            return response.ok
         }
 
         return false
@@ -148,16 +164,23 @@ const useEntries = () => {
     const deleteTask = async (rotationId: number, supertaskIndex: number, taskIndex: number, taskId: number): Promise<boolean> => {
         const response = await fetch(`${API_ENDPOINT}/api/entries/tasks/delete`, {
             credentials: "include",
-            headers: defaultHeaders(),
+            headers: ifMatchHeaders(entries.value[rotationId][supertaskIndex].children[taskIndex].entry),
             method: "DELETE",
             body: JSON.stringify({
                 entryId: taskId
             })
         })
 
+        if (response.status === 412) {
+            entries.value[rotationId][supertaskIndex].children[taskIndex].entry = await response.json()
+            memoEntries(rotationId, entries.value[rotationId])
+
+            return false
+        }
+
         if (response.ok) {
             entries.value[rotationId][supertaskIndex].children.splice(taskIndex, 1);
-            cacheAndMemoEntries(rotationId, entries.value[rotationId])
+            memoEntries(rotationId, entries.value[rotationId])
 
             return true
         }
@@ -184,10 +207,12 @@ const useEntries = () => {
                 id: data.entryId,
                 title,
                 rotationId,
-                taskId
+                taskId,
+                requiredCount: 1,
+                revision: 1
             })
 
-            cacheAndMemoEntries(rotationId, entries.value[rotationId])
+            memoEntries(rotationId, entries.value[rotationId])
             return true
         }
 
@@ -197,7 +222,7 @@ const useEntries = () => {
     const updateSubtask = async (rotationId: number, supertaskIndex: number, taskIndex: number, subtaskIndex: number, subtaskId: number, title: string): Promise<boolean> => {
         const response = await fetch(`${API_ENDPOINT}/api/entries/subtasks/update`, {
             credentials: "include",
-            headers: defaultHeaders(),
+            headers: ifMatchHeaders(entries.value[rotationId][supertaskIndex].children[taskIndex].children[subtaskIndex]),
             method: "PATCH",
             body: JSON.stringify({
                 entryId: subtaskId,
@@ -205,11 +230,12 @@ const useEntries = () => {
             })
         })
 
-        if (response.ok) {
-            entries.value[rotationId][supertaskIndex].children[taskIndex].children[subtaskIndex].title = title
-            cacheAndMemoEntries(rotationId, entries.value[rotationId])
+        // Both a successful update and a conflicting one send back the current state of the entry.
+        if (response.ok || response.status === 412) {
+            entries.value[rotationId][supertaskIndex].children[taskIndex].children[subtaskIndex] = await response.json()
+            memoEntries(rotationId, entries.value[rotationId])
 
-            return true
+            return response.ok
         }
 
         return false
@@ -218,16 +244,23 @@ const useEntries = () => {
     const deleteSubtask = async (rotationId: number, supertaskIndex: number, taskIndex: number, subtaskIndex: number, subtaskId: number): Promise<boolean> => {
         const response = await fetch(`${API_ENDPOINT}/api/entries/subtasks/delete`, {
             credentials: "include",
-            headers: defaultHeaders(),
+            headers: ifMatchHeaders(entries.value[rotationId][supertaskIndex].children[taskIndex].children[subtaskIndex]),
             method: "DELETE",
             body: JSON.stringify({
                 entryId: subtaskId
             })
         })
 
+        if (response.status === 412) {
+            entries.value[rotationId][supertaskIndex].children[taskIndex].children[subtaskIndex] = await response.json()
+            memoEntries(rotationId, entries.value[rotationId])
+
+            return false
+        }
+
         if (response.ok) {
             entries.value[rotationId][supertaskIndex].children[taskIndex].children.splice(subtaskIndex, 1);
-            cacheAndMemoEntries(rotationId, entries.value[rotationId])
+            memoEntries(rotationId, entries.value[rotationId])
 
             return true
         }
@@ -235,39 +268,61 @@ const useEntries = () => {
         return false
     }
 
-    const fetchEntries = async (rotationId: number): Promise<boolean> => {
-        const memo = entriesMemo.get(rotationId)
+    const reorderEntries = async <T>(level: "supertasks" | "tasks" | "subtasks", rotationId: number, siblings: T[], idOf: (sibling: T) => number, fromIndex: number, toIndex: number): Promise<boolean> => {
+        const reordered = [...siblings]
+        const [moved] = reordered.splice(fromIndex, 1)
+        reordered.splice(toIndex, 0, moved)
+
+        const response = await fetch(`${API_ENDPOINT}/api/entries/${level}/reorder`, {
+            credentials: "include",
+            headers: defaultHeaders(),
+            method: "PATCH",
+            body: JSON.stringify({
+                orderedIds: reordered.map(idOf)
+            })
+        })
+
+        if (response.ok) {
+            siblings.splice(0, siblings.length, ...reordered)
+            memoEntries(rotationId, entries.value[rotationId])
 
-        if (memo) {
-            entries.value[rotationId] = memo
             return true
         }
 
-        const [cachedEntries, cacheTimestamp] = retrieveRotationEntries(rotationId)
+        return false
+    }
 
-        const url = new URL(`${API_ENDPOINT}/api/entries/${rotationId}`)
+    const reorderSupertasks = (rotationId: number, fromIndex: number, toIndex: number): Promise<boolean> => {
+        return reorderEntries("supertasks", rotationId, entries.value[rotationId], (supertask) => supertask.entry.id, fromIndex, toIndex)
+    }
 
-        if (cacheTimestamp) {
-            url.searchParams.append("entriesCacheTimestamp", cacheTimestamp)
-        }
+    const reorderTasks = (rotationId: number, supertaskIndex: number, fromIndex: number, toIndex: number): Promise<boolean> => {
+        return reorderEntries("tasks", rotationId, entries.value[rotationId][supertaskIndex].children, (task) => task.entry.id, fromIndex, toIndex)
+    }
 
-        const response = await fetch(url, {
-            credentials: "include",
-            headers: defaultHeaders()
-        })
+    const reorderSubtasks = (rotationId: number, supertaskIndex: number, taskIndex: number, fromIndex: number, toIndex: number): Promise<boolean> => {
+        return reorderEntries("subtasks", rotationId, entries.value[rotationId][supertaskIndex].children[taskIndex].children, (subtask) => subtask.id, fromIndex, toIndex)
+    }
 
-        if (response.status === 304) {
-            entries.value[rotationId] = cachedEntries || []
-            cacheAndMemoEntries(rotationId, entries.value[rotationId])
+    const fetchEntries = async (rotationId: number): Promise<boolean> => {
+        const memo = entriesMemo.get(rotationId)
 
+        if (memo) {
+            entries.value[rotationId] = memo
             return true
         }
 
+        // The browser revalidates its cached entries with the entity tag the server sent, and reuses them if they have not changed.
+        const response = await fetch(`${API_ENDPOINT}/api/entries/${rotationId}`, {
+            credentials: "include",
+            headers: defaultHeaders()
+        })
+
         if (response.ok) {
             const data: EntryStructure = await response.json()
 
             entries.value[rotationId] = data
-            cacheAndMemoEntries(rotationId, entries.value[rotationId])
+            memoEntries(rotationId, entries.value[rotationId])
 
             return true
         }
@@ -285,6 +340,9 @@ const useEntries = () => {
         createSubtask,
         updateSubtask,
         deleteSubtask,
+        reorderSupertasks,
+        reorderTasks,
+        reorderSubtasks,
         fetchEntries
     }
 }
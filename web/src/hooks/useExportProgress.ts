@@ -1,99 +1,42 @@
 import { Ref, inject } from "vue"
-import { EntryStructure, UserTaskStructure } from "../models/tasks"
 import { Rotation } from "../models/rotation"
+import { API_ENDPOINT } from "../utilities"
 
-import jsPDF from "jspdf"
+import useJwt from "./useJwt"
 
+// The report is rendered by the API, so trainees and program directors download the same paginated document.
 const useExportProgress = () => {
-    const entries = inject<Ref<Record<number, EntryStructure>>>("entries")!
     const selectedRotation = inject<Ref<Rotation | null>>("selectedRotation")!
+    const { defaultHeaders } = useJwt()
 
-    const exportProgress = (name: string, tasks: UserTaskStructure) => {
+    const exportProgress = async (userId: number | null): Promise<boolean> => {
         if (!selectedRotation.value) {
-            return
+            return false
         }
 
-        const offset = 15
-        const indent = 15
-        const multiplier = 10
-        const spacing = 7
-    
-        const pdf = new jsPDF({
-            format: "a4",
-            orientation: "p"
+        const path = userId === null ? `${selectedRotation.value.id}` : `${userId}/${selectedRotation.value.id}`
+        const response = await fetch(`${API_ENDPOINT}/api/progress/report/${path}`, {
+            credentials: "include",
+            headers: defaultHeaders()
         })
-    
-        pdf.setFontSize(10)
-        pdf.text(`"${name}" Export for ${selectedRotation.value.name}`, 15, 10)
-    
-        let currentHeight = 0
-        let lineCount = 0
-    
-        const checkPageBreak = () => {
-            if (currentHeight > 3000) {
-                pdf.addPage()
 
-                lineCount = 0
-                currentHeight = 0
-            }
+        if (!response.ok) {
+            return false
         }
-    
-        const supertasks = entries.value[selectedRotation.value.id]
-        for (let i = 0; i < supertasks.length; i++) {
-            const supertask = supertasks[i]
-            checkPageBreak()
-    
-            let y = offset + spacing + (lineCount * multiplier)
-            pdf.text(supertask.entry.title, indent, y)
-    
-            for (let j = 0; j < supertask.children.length; j++) {
-                const task = supertask.children[j]
-                checkPageBreak()
-    
-                y = offset + (spacing * 2) + (lineCount * multiplier)
-                pdf.text(`- ${task.entry.title}`, indent * 2, y)
-    
-                for (let k = 0; k < task.children.length; k++) {
-                    const subtask = task.children[k]
-                    checkPageBreak()
-    
-                    y = offset + (spacing * 3) + (lineCount * multiplier)
-                    pdf.text(`- ${subtask.title}${tasks[subtask.id]?.isCompleted ? " - Complete" : " - Not Complete"}`, indent * 3, y)
 
-                    const comment = tasks[subtask.id]?.comment
-                    if (comment) {
-                        checkPageBreak()
-    
-                        const maxWidth = pdf.internal.pageSize.width - (indent * 5)
-                        const splitComment = pdf.splitTextToSize(`- ${comment}`, maxWidth)
-    
-                        for (let l = 0; l < splitComment.length; l++) {
-                            const line = splitComment[l]
+        const filename = response.headers.get("Content-Disposition")?.match(/filename="?([^"]+)"?/)?.[1] ?? "progress-report.pdf"
+        const url = URL.createObjectURL(await response.blob())
 
-                            y = offset + (spacing * 4) + ((lineCount + l) * multiplier)
-                            pdf.text(line, indent * 4, y)
-                        }
-    
-                        currentHeight += offset + (spacing * 4) + ((lineCount + splitComment.length - 1) * multiplier)
-                        lineCount += splitComment.length
-                    }
-    
-                    currentHeight += y
-                    lineCount += 1
-                }
-    
-                currentHeight += y
-                lineCount += 1
-            }
-    
-            currentHeight += y
-            lineCount += 1
-        }
-    
-        pdf.save(`${name.toLowerCase().replace(" ", "-")}-export.pdf`)
+        const link = document.createElement("a")
+        link.href = url
+        link.download = filename
+        link.click()
+
+        URL.revokeObjectURL(url)
+        return true
     }
-    
+
     return { exportProgress }
 }
 
-export default useExportProgress
\ No newline at end of file
+export default useExportProgress
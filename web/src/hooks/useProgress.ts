@@ -1,6 +1,7 @@
 import { Ref, inject } from "vue"
 import { EntryStructure, UserTaskStructure } from "../models/tasks"
 
+// Computed locally so that progress updates as soon as a task changes, using the same definition as `/api/progress`.
 const useProgress = (userTasks: UserTaskStructure) => {
     const entries = inject<Ref<Record<number, EntryStructure>>>("entries")!
 
@@ -27,7 +27,12 @@ export const useRotations = () => {
             const rotation: Rotation = {
                 id: data.rotationId,
                 name,
-                lastUpdated: data.lastUpdated
+                description: "",
+                location: "",
+                durationWeeks: null,
+                category: "core",
+                lastUpdated: data.lastUpdated,
+                archivedAt: null
             }
 
             rotations.value[data.rotationId] = rotation;
@@ -37,10 +42,10 @@ export const useRotations = () => {
         return false
     }
 
-    const deleteRotation = async (rotationId: number): Promise<boolean> => {
-        const response = await fetch(`${API_ENDPOINT}/api/rotations/delete`, {
+    const archiveRotation = async (rotationId: number): Promise<boolean> => {
+        const response = await fetch(`${API_ENDPOINT}/api/rotations/archive`, {
             credentials: "include",
-            method: "DELETE",
+            method: "PATCH",
             headers: defaultHeaders(),
             body: JSON.stringify({ rotationId })
         })
@@ -55,7 +60,7 @@ export const useRotations = () => {
 
     return {
         createRotation,
-        deleteRotation
+        archiveRotation
     }
 }
 
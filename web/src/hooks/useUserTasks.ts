@@ -87,6 +87,19 @@ const useUserTasks = () => {
             })
 
             if (response.ok) {
+                const task = tasks.value[rotationId][subtaskId]
+                const now = new Date().toISOString()
+
+                // Changing the completion withdraws the task from verification.
+                if (task.isCompleted !== isCompleted) {
+                    task.status = "draft"
+                    task.completedAt = isCompleted ? now : null
+                }
+
+                if (task.isCompleted !== isCompleted || task.comment !== comment) {
+                    task.updatedAt = now
+                }
+
                 tasks.value[rotationId][subtaskId].isCompleted = isCompleted
                 tasks.value[rotationId][subtaskId].comment = comment
 
@@ -109,13 +122,19 @@ const useUserTasks = () => {
 
             if (response.ok) {
                 const data: CreateUserTaskResponse = await response.json()
+                const now = new Date().toISOString()
 
                 tasks.value[rotationId][subtaskId] = {
                     id: data.id,
                     userId: session.value.id,
                     subtaskId,
                     isCompleted,
-                    comment
+                    comment,
+                    status: "draft",
+                    encounterCount: 0,
+                    createdAt: now,
+                    updatedAt: now,
+                    completedAt: isCompleted ? now : null
                 }
 
                 userTasksMemo.set(key, tasks.value[rotationId])
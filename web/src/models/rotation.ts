@@ -1,5 +1,12 @@
+export type RotationCategory = "core" | "elective"
+
 export interface Rotation {
     id: number,
     name: string,
+    description: string,
+    location: string,
+    durationWeeks: number | null,
+    category: RotationCategory,
     lastUpdated: string,
+    archivedAt: string | null
 }
\ No newline at end of file
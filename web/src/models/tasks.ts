@@ -1,29 +1,40 @@
+export type VerificationStatus = "draft" | "submitted" | "verified" | "returned"
+
 export interface UserTask {
     id: number,
     userId: number,
     subtaskId: number,
     isCompleted: boolean,
-    comment: string
+    comment: string,
+    status: VerificationStatus,
+    encounterCount: number,
+    createdAt: string,
+    updatedAt: string,
+    completedAt: string | null
 }
 
 export interface Supertask {
     id: number,
     title: string,
-    rotationId: number
+    rotationId: number,
+    revision: number
 }
 
 export interface Task {
     id: number,
     supertaskId: number,
     title: string,
-    rotationId: number
+    rotationId: number,
+    revision: number
 }
 
 export interface Subtask {
     id: number,
     taskId: number,
     title: string,
-    rotationId: number
+    rotationId: number,
+    requiredCount: number,
+    revision: number
 }
 
 export interface EntryLevel<T = (Supertask | Task | Subtask), U = any> {